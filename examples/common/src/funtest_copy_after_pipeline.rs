@@ -0,0 +1,95 @@
+//! Texture copies issued right after a pipeline ran.
+//!
+//! Running a pipeline binds its framebuffer, which on WebGL2 replaces the read framebuffer binding
+//! too. Texture copies read through the read framebuffer, so they must not assume the previously
+//! bound one is still in place: otherwise, the copied texels come from the framebuffer the pipeline
+//! rendered to instead of the source texture.
+
+use crate::{Example, InputAction, LoopFeedback, PlatformServices};
+use luminance_front::{
+  context::GraphicsContext,
+  framebuffer::Framebuffer,
+  pipeline::PipelineState,
+  pixel::NormRGBA8UI,
+  texture::{Dim2, GenMipmaps, Sampler, Texture},
+  Backend,
+};
+
+const SIZE: [u32; 2] = [2, 2];
+
+// texels of the source texture, all different from the clear color of the framebuffer
+const TEXELS: [u8; 16] = [
+  0, 0, 255, 255, 0, 255, 0, 255, 255, 255, 0, 255, 0, 255, 255, 255,
+];
+
+pub struct LocalExample {
+  framebuffer: Framebuffer<Dim2, NormRGBA8UI, ()>,
+  src: Texture<Dim2, NormRGBA8UI>,
+  texture_copy: Texture<Dim2, NormRGBA8UI>,
+  framebuffer_copy: Texture<Dim2, NormRGBA8UI>,
+}
+
+impl Example for LocalExample {
+  fn bootstrap(
+    _: &mut impl PlatformServices,
+    context: &mut impl GraphicsContext<Backend = Backend>,
+  ) -> Self {
+    let framebuffer = context
+      .new_framebuffer(SIZE, 0, Sampler::default())
+      .unwrap();
+    let src = context
+      .new_texture_raw(SIZE, 0, Sampler::default(), GenMipmaps::No, &TEXELS)
+      .unwrap();
+    let texture_copy = context
+      .new_texture_no_texels(SIZE, 0, Sampler::default())
+      .unwrap();
+    let framebuffer_copy = context
+      .new_texture_no_texels(SIZE, 0, Sampler::default())
+      .unwrap();
+
+    LocalExample {
+      framebuffer,
+      src,
+      texture_copy,
+      framebuffer_copy,
+    }
+  }
+
+  fn render_frame(
+    mut self,
+    _: f32,
+    _: Framebuffer<Dim2, (), ()>,
+    _: impl Iterator<Item = InputAction>,
+    context: &mut impl GraphicsContext<Backend = Backend>,
+  ) -> LoopFeedback<Self> {
+    // clear the framebuffer to red, leaving it bound
+    context
+      .new_pipeline_gate()
+      .pipeline(
+        &self.framebuffer,
+        &PipelineState::default().set_clear_color([1., 0., 0., 1.]),
+        |_, _| Ok(()),
+      )
+      .assume()
+      .into_result()
+      .unwrap();
+
+    self
+      .texture_copy
+      .copy_from(&self.src, 0, [0, 0], 0, [0, 0], SIZE)
+      .unwrap();
+    let texels = self.texture_copy.get_raw_texels().unwrap();
+    log::info!("texture copy after pipeline: {:?}", texels);
+    assert_eq!(texels, TEXELS);
+
+    self
+      .framebuffer_copy
+      .copy_from_framebuffer(&self.framebuffer, 0, [0, 0], 0, [0, 0], SIZE)
+      .unwrap();
+    let texels = self.framebuffer_copy.get_raw_texels().unwrap();
+    log::info!("framebuffer copy after pipeline: {:?}", texels);
+    assert_eq!(texels, [255, 0, 0, 255].repeat(4));
+
+    LoopFeedback::Exit
+  }
+}
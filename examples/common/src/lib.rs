@@ -51,6 +51,8 @@ pub mod funtest_360_manually_drop_framebuffer;
 #[cfg(feature = "funtest")]
 pub mod funtest_483_indices_mut_corruption;
 #[cfg(feature = "funtest")]
+pub mod funtest_copy_after_pipeline;
+#[cfg(feature = "funtest")]
 pub mod funtest_flatten_slice;
 #[cfg(all(feature = "funtest", feature = "funtest-gl33-f64-uniform"))]
 pub mod funtest_gl33_f64_uniform;
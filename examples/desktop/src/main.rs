@@ -211,6 +211,7 @@ examples! {
   "funtest-flatten-slice", funtest_flatten_slice,
   "funtest-pixel-array-encoding", funtest_pixel_array_encoding,
   "funtest-483-indices-mut-corruption", funtest_483_indices_mut_corruption,
+  "funtest-copy-after-pipeline", funtest_copy_after_pipeline,
 }
 
 fn main() {
@@ -72,6 +72,8 @@ impl error::Error for AttrError {}
 /// syntax:
 ///
 ///   #[key(sub_key = "lit")]
+///
+/// Integer literals, as in `#[key(sub_key = 8)]`, are accepted too.
 pub(crate) fn get_field_attr_once<'a, A, T>(
   field_ident: &Ident,
   attrs: A,
@@ -97,10 +99,20 @@ where
                 return Err(AttrError::several(field_ident.clone(), key, sub_key));
               }
 
-              if let Lit::Str(ref strlit) = mnv.lit {
-                lit = Some(strlit.parse().map_err(|_| {
-                  AttrError::cannot_parse_attribute(field_ident.clone(), key, sub_key)
-                })?);
+              match mnv.lit {
+                Lit::Str(ref strlit) => {
+                  lit = Some(strlit.parse().map_err(|_| {
+                    AttrError::cannot_parse_attribute(field_ident.clone(), key, sub_key)
+                  })?);
+                }
+
+                Lit::Int(ref intlit) => {
+                  lit = Some(syn::parse_str(&intlit.to_string()).map_err(|_| {
+                    AttrError::cannot_parse_attribute(field_ident.clone(), key, sub_key)
+                  })?);
+                }
+
+                _ => (),
               }
             } else {
               let ident_str = mnv
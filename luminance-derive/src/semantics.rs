@@ -82,6 +82,7 @@ pub(crate) fn generate_enum_semantics_impl(
   let mut name_branches = Vec::new();
   let mut field_based_gen = Vec::new();
   let mut semantics_set = Vec::new();
+  let mut glsl_decls = Vec::new();
 
   let mut errors = Vec::new();
 
@@ -111,6 +112,15 @@ pub(crate) fn generate_enum_semantics_impl(
           }
         });
 
+        // GLSL declaration of the vertex input
+        glsl_decls.push(quote! {
+          decls.push_str(&format!(
+            "in {} {};\n",
+            <#repr_ty_name as luminance::vertex::VertexAttrib>::VERTEX_ATTRIB_DESC.glsl_type(),
+            #sem_name
+          ));
+        });
+
         // field-based code generation
         let field_gen = quote! {
           // vertex attrib type
@@ -197,6 +207,14 @@ pub(crate) fn generate_enum_semantics_impl(
       }
     }
 
+    impl luminance::vertex::SemanticsDeclarations for #ident {
+      fn input_declarations() -> String {
+        let mut decls = String::new();
+        #(#glsl_decls)*
+        decls
+      }
+    }
+
     // easy parsing
     impl std::str::FromStr for #ident {
       type Err = ();
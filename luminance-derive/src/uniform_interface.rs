@@ -3,10 +3,13 @@ use proc_macro::TokenStream;
 use quote::quote;
 use std::error;
 use std::fmt;
-use syn::{DataStruct, Fields, Ident, Path, PathArguments, Type, TypePath};
+use syn::{
+  DataStruct, Fields, GenericArgument, Ident, LitInt, Path, PathArguments, Type, TypeArray,
+  TypePath, TypeReference,
+};
 
 // accepted sub keys for the "vertex" key
-const KNOWN_SUBKEYS: &[&str] = &["name", "unbound"];
+const KNOWN_SUBKEYS: &[&str] = &["name", "unbound", "flatten", "len"];
 
 #[non_exhaustive]
 #[derive(Debug)]
@@ -15,6 +18,10 @@ pub(crate) enum DeriveUniformInterfaceError {
   UnsupportedUnit,
   UnboundError(AttrError),
   NameError(AttrError),
+  FlattenError(AttrError),
+  LenError(AttrError),
+  UnboundFlattenedField(Ident),
+  LenOnNonSliceUniform(Ident),
   IncorrectlyWrappedType(Type),
 }
 
@@ -35,6 +42,22 @@ impl DeriveUniformInterfaceError {
     DeriveUniformInterfaceError::NameError(e)
   }
 
+  pub(crate) fn flatten_error(e: AttrError) -> Self {
+    DeriveUniformInterfaceError::FlattenError(e)
+  }
+
+  pub(crate) fn len_error(e: AttrError) -> Self {
+    DeriveUniformInterfaceError::LenError(e)
+  }
+
+  pub(crate) fn unbound_flattened_field(field: Ident) -> Self {
+    DeriveUniformInterfaceError::UnboundFlattenedField(field)
+  }
+
+  pub(crate) fn len_on_non_slice_uniform(field: Ident) -> Self {
+    DeriveUniformInterfaceError::LenOnNonSliceUniform(field)
+  }
+
   pub(crate) fn incorrectly_wrapped_type(ty: Type) -> Self {
     DeriveUniformInterfaceError::IncorrectlyWrappedType(ty)
   }
@@ -47,6 +70,18 @@ impl fmt::Display for DeriveUniformInterfaceError {
       DeriveUniformInterfaceError::UnsupportedUnit => f.write_str("unsupported unit struct"),
       DeriveUniformInterfaceError::UnboundError(ref e) => write!(f, "unbound error: {}", e),
       DeriveUniformInterfaceError::NameError(ref e) => write!(f, "name error: {}", e),
+      DeriveUniformInterfaceError::FlattenError(ref e) => write!(f, "flatten error: {}", e),
+      DeriveUniformInterfaceError::LenError(ref e) => write!(f, "len error: {}", e),
+      DeriveUniformInterfaceError::UnboundFlattenedField(ref field) => {
+        write!(f, "flattened field {} cannot be unbound", field)
+      }
+      DeriveUniformInterfaceError::LenOnNonSliceUniform(ref field) => {
+        write!(
+          f,
+          "len can only be set on slice uniforms, which {} is not",
+          field
+        )
+      }
       DeriveUniformInterfaceError::IncorrectlyWrappedType(ref t) => write!(
         f,
         "incorrectly wrapped uniform type: {:?} (should be Uniform<YourTypeHere>)",
@@ -61,6 +96,8 @@ impl error::Error for DeriveUniformInterfaceError {
     match self {
       DeriveUniformInterfaceError::UnboundError(e) => Some(e),
       DeriveUniformInterfaceError::NameError(e) => Some(e),
+      DeriveUniformInterfaceError::FlattenError(e) => Some(e),
+      DeriveUniformInterfaceError::LenError(e) => Some(e),
       _ => None,
     }
   }
@@ -79,6 +116,11 @@ pub(crate) fn generate_uniform_interface_impl(
       let mut field_names = Vec::new();
       // collect field types so that we can implement UniformInterface<S> where $t: Uniform<S>
       let mut field_where_clause = Vec::new();
+      // collect flattened field types so that we can implement UniformInterface<S> where
+      // $t: UniformInterface<S>
+      let mut nested_where_clause = Vec::new();
+      // GLSL declarations of the uniforms; flattened fields are not declared
+      let mut field_glsl_decls = Vec::new();
 
       for field in named_fields.named {
         let field_ident = field.ident.unwrap();
@@ -100,6 +142,76 @@ pub(crate) fn generate_uniform_interface_impl(
             })
             .map_err(DeriveUniformInterfaceError::name_error)?;
 
+        let flatten = get_field_flag_once(
+          &ident,
+          field.attrs.iter(),
+          "uniform",
+          "flatten",
+          KNOWN_SUBKEYS,
+        )
+        .map_err(DeriveUniformInterfaceError::flatten_error)?;
+
+        let len = get_field_attr_once::<_, LitInt>(
+          &ident,
+          field.attrs.iter(),
+          "uniform",
+          "len",
+          KNOWN_SUBKEYS,
+        )
+        .map(Some)
+        .or_else(|e| match e {
+          AttrError::CannotFindAttribute(..) => Ok(None),
+
+          _ => Err(e),
+        })
+        .map_err(DeriveUniformInterfaceError::len_error)?;
+
+        field_names.push(field_ident.clone());
+
+        if flatten {
+          if unbound {
+            return Err(DeriveUniformInterfaceError::unbound_flattened_field(
+              field_ident,
+            ));
+          }
+
+          // nested interfaces are built with their name as prefix; arrays of nested interfaces are
+          // built element by element, with the index in the prefix
+          let field_ty = field.ty;
+
+          if let Type::Array(TypeArray {
+            ref elem, ref len, ..
+          }) = field_ty
+          {
+            field_decls.push(quote! {
+              let #field_ident: #field_ty = {
+                let mut elems = Vec::with_capacity(#len);
+
+                for i in 0..#len {
+                  elems.push(builder.ask_nested::<#elem, _>(format!("{}[{}].", #name, i), &mut ())?);
+                }
+
+                match std::convert::TryFrom::try_from(elems) {
+                  Ok(elems) => elems,
+                  Err(_) => unreachable!(),
+                }
+              };
+            });
+            nested_where_clause.push(quote! {
+              #elem: luminance::shader::UniformInterface<S>
+            });
+          } else {
+            field_decls.push(quote! {
+              let #field_ident = builder.ask_nested::<#field_ty, _>(concat!(#name, "."), &mut ())?;
+            });
+            nested_where_clause.push(quote! {
+              #field_ty: luminance::shader::UniformInterface<S>
+            });
+          }
+
+          continue;
+        }
+
         // the build call is the code that gets a uniform and possibly fails if bound; also handles
         // renaming
         let build_call = if unbound {
@@ -112,23 +224,45 @@ pub(crate) fn generate_uniform_interface_impl(
           }
         };
 
+        let is_slice = is_slice_uniform(&field.ty);
         let field_ty = extract_uniform_type(&field.ty).ok_or(
           DeriveUniformInterfaceError::incorrectly_wrapped_type(field.ty),
         )?;
-        field_names.push(field_ident.clone());
         field_decls.push(quote! {
           let #field_ident = #build_call;
         });
         field_where_clause.push(quote! {
           #field_ty: luminance::backend::shader::Uniformable<S>
         });
+
+        // slices are typed after their elements, so they can only be declared if the GLSL array
+        // length is given; otherwise, they must be declared in the sources
+        let decl = match (is_slice, len) {
+          (false, None) => quote! { format!("uniform {} {};\n", ty, #name) },
+          (true, Some(len)) => quote! { format!("uniform {} {}[{}];\n", ty, #name, #len) },
+          (false, Some(_)) => {
+            return Err(DeriveUniformInterfaceError::len_on_non_slice_uniform(
+              field_ident,
+            ))
+          }
+          (true, None) => continue,
+        };
+
+        field_glsl_decls.push(quote! {
+          let ty = unsafe { <#field_ty as luminance::backend::shader::Uniformable<S>>::ty() };
+
+          if ty != luminance::shader::UniformType::BufferBinding {
+            decls.push_str(&#decl);
+          }
+        });
       }
 
       let output = quote! {
         impl<S> luminance::shader::UniformInterface<S> for #ident
         where
           S: ?Sized + luminance::backend::shader::Shader,
-          #(#field_where_clause),*,
+          #(#field_where_clause,)*
+          #(#nested_where_clause,)*
         {
           fn uniform_interface<'a>(
             builder: &mut luminance::shader::UniformBuilder<'a, S>,
@@ -140,6 +274,18 @@ pub(crate) fn generate_uniform_interface_impl(
             Ok(iface)
           }
         }
+
+        impl<S> luminance::shader::UniformDeclarations<S> for #ident
+        where
+          S: ?Sized + luminance::backend::shader::Shader,
+          #(#field_where_clause,)*
+        {
+          fn uniform_declarations() -> String {
+            let mut decls = String::new();
+            #(#field_glsl_decls)*
+            decls
+          }
+        }
       };
 
       Ok(output.into())
@@ -169,3 +315,22 @@ fn extract_uniform_type(ty: &Type) -> Option<proc_macro2::TokenStream> {
     None
   }
 }
+
+// whether the type T in Uniform<T> is a slice, as in Uniform<&'a [T]>
+fn is_slice_uniform(ty: &Type) -> bool {
+  if let Type::Path(TypePath {
+    path: Path { ref segments, .. },
+    ..
+  }) = ty
+  {
+    if let Some(PathArguments::AngleBracketed(ref bracketed_args)) =
+      segments.first().map(|segment| &segment.arguments)
+    {
+      return bracketed_args.args.iter().any(|arg| {
+        matches!(arg, GenericArgument::Type(Type::Reference(TypeReference { elem, .. })) if matches!(**elem, Type::Slice(_)))
+      });
+    }
+  }
+
+  false
+}
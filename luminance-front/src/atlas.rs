@@ -0,0 +1,5 @@
+use crate::Backend;
+
+pub use luminance::atlas::{AtlasDimension, AtlasError, AtlasId, AtlasRegion, ShelfAllocator};
+
+pub type Atlas<D, P> = luminance::atlas::Atlas<Backend, D, P>;
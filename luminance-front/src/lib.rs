@@ -56,9 +56,17 @@
 //!   - `"gl33"`: OpenGL 3.3 implementation.
 //! - **WebGL 2**:
 //!   - `"webgl2"`: WebGL 2 implementation.
+//! - **Pixel formats**:
+//!   - `"half"`: half-precision floating-point pixel formats, such as `RGBA16F`.
+//! - **Integrations**:
+//!   - `"image"`: create textures from the images of the [image] crate, and read textures and
+//!     framebuffers back as images.
+//!   - `"png"` and `"exr"`: save textures and framebuffers to PNG and OpenEXR files.
 //!
 //! [luminance]: https://crates.io/crates/luminance
+//! [image]: https://crates.io/crates/image
 
+pub mod atlas;
 pub mod context;
 pub mod framebuffer;
 pub mod pipeline;
@@ -74,6 +82,8 @@ pub mod texture;
 pub use luminance::blending;
 pub use luminance::depth_test;
 pub use luminance::face_culling;
+#[cfg(feature = "image")]
+pub use luminance::image;
 pub use luminance::pixel;
 pub use luminance::render_state;
 pub use luminance::scissor;
@@ -1,8 +1,14 @@
 use crate::Backend;
 
-pub use luminance::pipeline::{PipelineError, PipelineState, TextureBinding, Viewport};
+pub use luminance::pipeline::{
+  BufferTextureBinding, PipelineError, PipelineState, TextureBinding, Viewport,
+};
 
 pub type Pipeline<'a> = luminance::pipeline::Pipeline<'a, Backend>;
 pub type PipelineGate<'a> = luminance::pipeline::PipelineGate<'a, Backend>;
 pub type BoundTexture<'a, D, P> = luminance::pipeline::BoundTexture<'a, Backend, D, P>;
+pub type BoundDynTexture<'a, D> = luminance::pipeline::BoundDynTexture<'a, Backend, D>;
 pub type Render<E> = luminance::pipeline::Render<E>;
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "gl33"))]
+pub type BoundBufferTexture<'a, P> = luminance::pipeline::BoundBufferTexture<'a, Backend, P>;
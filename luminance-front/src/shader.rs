@@ -1,7 +1,11 @@
 use crate::Backend;
 
+#[cfg(feature = "naga")]
+pub use luminance::shader::cross;
+pub use luminance::shader::watcher::{FileWatcher, ProgramSources, ProgramWatchError};
 pub use luminance::shader::{
-  ProgramError, ProgramWarning, StageError, StageType, TessellationStages, Uniform,
+  cache, permutation, preprocessor, reflection, GlslTarget, ProgramError, ProgramWarning,
+  StageError, StageType, TessellationStages, Uniform, UniformCacheStats, UniformDeclarations,
   UniformInterface, UniformType, UniformWarning, VertexAttribWarning,
 };
 
@@ -12,3 +16,7 @@ pub type AdaptationFailure<Sem, Out, Uni> =
   luminance::shader::AdaptationFailure<Backend, Sem, Out, Uni>;
 pub type ProgramInterface<'a> = luminance::shader::ProgramInterface<'a, Backend>;
 pub type Program<Sem, Out, Uni> = luminance::shader::Program<Backend, Sem, Out, Uni>;
+pub type ProgramWatcher<Sem, Out, Uni> =
+  luminance::shader::watcher::ProgramWatcher<Backend, Sem, Out, Uni>;
+pub type ProgramPermutations<Sem, Out, Uni> =
+  luminance::shader::permutation::ProgramPermutations<Backend, Sem, Out, Uni>;
@@ -1,8 +1,15 @@
 use crate::Backend;
 
 pub use luminance::texture::{
-  CubeFace, Cubemap, Dim, Dim1, Dim1Array, Dim2, Dim2Array, Dim3, Dimensionable, GenMipmaps,
-  MagFilter, MinFilter, Sampler, TextureError, Wrap,
+  CubeFace, Cubemap, CubemapArray, Dim, Dim1, Dim1Array, Dim2, Dim2Array, Dim3, Dimensionable,
+  GenMipmaps, MagFilter, MinFilter, SampledWith, Sampler, Swizzle, SwizzleComponent, TextureError,
+  TextureHandle, Wrap,
 };
 
 pub type Texture<D, P> = luminance::texture::Texture<Backend, D, P>;
+pub type DynTexture<D> = luminance::texture::DynTexture<Backend, D>;
+pub type SamplerObject = luminance::texture::SamplerObject<Backend>;
+pub type PendingReadback<P> = luminance::texture::PendingReadback<Backend, P>;
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "gl33"))]
+pub type BufferTexture<P> = luminance::texture::BufferTexture<Backend, P>;
@@ -1,12 +1,19 @@
 //! OpenGL 3.3 backend.
 
+#[cfg(feature = "GL_ARB_bindless_texture")]
+pub mod bindless;
 mod buffer;
+mod buffer_texture;
 mod depth_test;
 mod framebuffer;
 mod pipeline;
 mod pixel;
 mod query;
+mod readback;
+mod sampler;
 mod shader;
+#[cfg(feature = "GL_ARB_gl_spirv")]
+pub mod spirv;
 mod state;
 mod tess;
 mod texture;
@@ -0,0 +1,180 @@
+//! `GL_ARB_bindless_texture` support.
+//!
+//! The [gl] crate doesn’t expose extension entry points, so they must be loaded separately with
+//! [`load_with`], once the OpenGL context is current — typically right after calling
+//! `gl::load_with`:
+//!
+//! ```ignore
+//! luminance_gl::gl33::bindless::load_with(|s| window.get_proc_address(s) as *const _);
+//! ```
+//!
+//! Until then, or if the extension is not supported by the driver, every bindless operation fails
+//! with [`TextureError::BindlessFailed`].
+//!
+//! [gl]: https://crates.io/crates/gl
+//! [`TextureError::BindlessFailed`]: luminance::texture::TextureError::BindlessFailed
+
+use gl::types::*;
+use luminance::backend::shader::Uniformable;
+use luminance::backend::texture::TextureBindless;
+use luminance::pixel::{Pixel, SamplerType};
+use luminance::shader::{Uniform, UniformType};
+use luminance::texture::{Dimensionable, SampledWith, TextureError, TextureHandle};
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+use crate::gl33::shader::{sampler_uniform_type, Program};
+use crate::gl33::texture::Texture;
+use crate::gl33::GL33;
+
+// declare the entry points along with a loading flag and a wrapper calling through the pointer
+macro_rules! bindless_fns {
+  ($($ptr:ident, $name:ident, $gl_name:literal, fn($($arg:ident: $arg_ty:ty),*) $(-> $ret:ty)?;)*) => {
+    $(
+      static $ptr: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
+
+      pub(crate) unsafe fn $name($($arg: $arg_ty),*) $(-> $ret)? {
+        let f: extern "system" fn($($arg_ty),*) $(-> $ret)? = mem::transmute($ptr.load(Ordering::Relaxed));
+        f($($arg),*)
+      }
+    )*
+
+    /// Load the `GL_ARB_bindless_texture` entry points with the given loader function, which
+    /// returns the address of an OpenGL function given its name.
+    pub fn load_with<F>(mut loader: F)
+    where
+      F: FnMut(&'static str) -> *const c_void,
+    {
+      $(
+        $ptr.store(loader($gl_name) as *mut c_void, Ordering::Relaxed);
+      )*
+    }
+
+    /// Whether all the entry points were successfully loaded.
+    pub(crate) fn is_loaded() -> bool {
+      $(!$ptr.load(Ordering::Relaxed).is_null())&&*
+    }
+  };
+}
+
+bindless_fns! {
+  GET_TEXTURE_HANDLE, get_texture_handle, "glGetTextureHandleARB",
+    fn(texture: GLuint) -> GLuint64;
+  MAKE_TEXTURE_HANDLE_RESIDENT, make_texture_handle_resident, "glMakeTextureHandleResidentARB",
+    fn(handle: GLuint64);
+  MAKE_TEXTURE_HANDLE_NON_RESIDENT, make_texture_handle_non_resident,
+    "glMakeTextureHandleNonResidentARB", fn(handle: GLuint64);
+  UNIFORM_HANDLE, uniform_handle, "glUniformHandleui64ARB",
+    fn(location: GLint, value: GLuint64);
+  UNIFORM_HANDLE_V, uniform_handle_v, "glUniformHandleui64vARB",
+    fn(location: GLint, count: GLsizei, value: *const GLuint64);
+}
+
+/// Bindless state of a texture.
+#[derive(Debug, Default)]
+pub(crate) struct BindlessState {
+  /// Handle of the texture, once requested.
+  pub(crate) handle: Option<GLuint64>,
+  /// Whether the handle is resident.
+  pub(crate) resident: bool,
+}
+
+unsafe impl<D, P> TextureBindless<D, P> for GL33
+where
+  D: Dimensionable,
+  P: Pixel,
+{
+  unsafe fn bindless_handle(texture: &mut Self::TextureRepr) -> Result<u64, TextureError> {
+    if let Some(handle) = texture.bindless.handle {
+      return Ok(handle);
+    }
+
+    check_bindless_support(texture)?;
+
+    let handle = get_texture_handle(texture.handle);
+
+    if handle == 0 {
+      return Err(TextureError::bindless_failed(
+        "cannot get a bindless handle for the texture",
+      ));
+    }
+
+    texture.bindless.handle = Some(handle);
+    Ok(handle)
+  }
+
+  unsafe fn set_bindless_residency(
+    texture: &mut Self::TextureRepr,
+    resident: bool,
+  ) -> Result<(), TextureError> {
+    let handle = texture
+      .bindless
+      .handle
+      .ok_or_else(|| TextureError::bindless_failed("the texture has no bindless handle yet"))?;
+
+    if texture.bindless.resident != resident {
+      if resident {
+        make_texture_handle_resident(handle);
+      } else {
+        make_texture_handle_non_resident(handle);
+      }
+
+      texture.bindless.resident = resident;
+    }
+
+    Ok(())
+  }
+}
+
+fn check_bindless_support(texture: &Texture) -> Result<(), TextureError> {
+  if !texture
+    .state
+    .borrow_mut()
+    .has_extension("GL_ARB_bindless_texture")
+  {
+    Err(TextureError::bindless_failed(
+      "GL_ARB_bindless_texture is not supported",
+    ))
+  } else if !is_loaded() {
+    Err(TextureError::bindless_failed(
+      "GL_ARB_bindless_texture entry points are not loaded",
+    ))
+  } else {
+    Ok(())
+  }
+}
+
+unsafe impl<D, S> Uniformable<GL33> for TextureHandle<D, S>
+where
+  D: SampledWith<S>,
+  S: SamplerType,
+{
+  unsafe fn ty() -> UniformType {
+    sampler_uniform_type::<D, S>()
+  }
+
+  unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+    uniform_handle(uniform.index(), self.handle())
+  }
+}
+
+unsafe impl<D, S> Uniformable<GL33> for &[TextureHandle<D, S>]
+where
+  D: SampledWith<S>,
+  S: SamplerType,
+{
+  unsafe fn ty() -> UniformType {
+    sampler_uniform_type::<D, S>()
+  }
+
+  unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+    // TextureHandle is a transparent wrapper around the 64-bit handle
+    uniform_handle_v(
+      uniform.index(),
+      self.len() as GLsizei,
+      self.as_ptr() as *const GLuint64,
+    )
+  }
+}
@@ -0,0 +1,158 @@
+//! Buffer textures support for OpenGL 3.3.
+
+use gl::types::*;
+use luminance::backend::texture::BufferTexture as BufferTextureBackend;
+use luminance::pixel::{Pixel, PixelFormat};
+use luminance::texture::TextureError;
+use std::cell::RefCell;
+use std::mem;
+use std::os::raw::c_void;
+use std::rc::Rc;
+
+use crate::gl33::pixel::opengl_pixel_format;
+use crate::gl33::state::GLState;
+use crate::gl33::GL33;
+
+pub struct BufferTexture {
+  pub(crate) handle: GLuint, // handle to the GPU texture object
+  buffer: GLuint,            // handle to the GPU buffer holding the texels
+  state: Rc<RefCell<GLState>>,
+}
+
+impl Drop for BufferTexture {
+  fn drop(&mut self) {
+    unsafe {
+      gl::DeleteTextures(1, &self.handle);
+
+      self.state.borrow_mut().unbind_buffer(self.buffer);
+      gl::DeleteBuffers(1, &self.buffer);
+    }
+  }
+}
+
+impl BufferTexture {
+  unsafe fn new<T>(
+    state: &Rc<RefCell<GLState>>,
+    pf: PixelFormat,
+    texels: &[T],
+  ) -> Result<Self, TextureError> {
+    let iformat =
+      buffer_texture_internal_format(pf).ok_or(TextureError::UnsupportedPixelFormat(pf))?;
+
+    let mut buffer: GLuint = 0;
+    gl::GenBuffers(1, &mut buffer);
+    gl::BindBuffer(gl::TEXTURE_BUFFER, buffer);
+    gl::BufferData(
+      gl::TEXTURE_BUFFER,
+      mem::size_of_val(texels) as GLsizeiptr,
+      texels.as_ptr() as *const c_void,
+      gl::DYNAMIC_DRAW,
+    );
+    gl::BindBuffer(gl::TEXTURE_BUFFER, 0);
+
+    let mut gfx_state = state.borrow_mut();
+    let handle = gfx_state.create_texture();
+    gfx_state.bind_texture(gl::TEXTURE_BUFFER, handle);
+    gl::TexBuffer(gl::TEXTURE_BUFFER, iformat, buffer);
+
+    Ok(BufferTexture {
+      handle,
+      buffer,
+      state: state.clone(),
+    })
+  }
+
+  unsafe fn upload_part<T>(&mut self, offset: usize, texels: &[T]) {
+    gl::BindBuffer(gl::TEXTURE_BUFFER, self.buffer);
+    gl::BufferSubData(
+      gl::TEXTURE_BUFFER,
+      (offset * mem::size_of::<T>()) as GLintptr,
+      mem::size_of_val(texels) as GLsizeiptr,
+      texels.as_ptr() as *const c_void,
+    );
+    gl::BindBuffer(gl::TEXTURE_BUFFER, 0);
+  }
+}
+
+unsafe impl<P> BufferTextureBackend<P> for GL33
+where
+  P: Pixel,
+{
+  type BufferTextureRepr = BufferTexture;
+
+  unsafe fn new_buffer_texture(
+    &mut self,
+    texels: &[P::Encoding],
+  ) -> Result<Self::BufferTextureRepr, TextureError> {
+    BufferTexture::new(&self.state, P::pixel_format(), texels)
+  }
+
+  unsafe fn new_buffer_texture_raw(
+    &mut self,
+    texels: &[P::RawEncoding],
+  ) -> Result<Self::BufferTextureRepr, TextureError> {
+    BufferTexture::new(&self.state, P::pixel_format(), texels)
+  }
+
+  unsafe fn upload_buffer_texture_part(
+    texture: &mut Self::BufferTextureRepr,
+    offset: usize,
+    texels: &[P::Encoding],
+  ) -> Result<(), TextureError> {
+    texture.upload_part(offset, texels);
+    Ok(())
+  }
+
+  unsafe fn upload_buffer_texture_part_raw(
+    texture: &mut Self::BufferTextureRepr,
+    offset: usize,
+    texels: &[P::RawEncoding],
+  ) -> Result<(), TextureError> {
+    // offset is expressed in texels, which are made of several raw items
+    texture.upload_part(offset * P::pixel_format().raw_len(), texels);
+    Ok(())
+  }
+}
+
+// Sized internal format to use for a buffer texture, if the pixel format is allowed for buffer
+// textures.
+fn buffer_texture_internal_format(pf: PixelFormat) -> Option<GLenum> {
+  let (_, iformat, _) = opengl_pixel_format(pf)?;
+
+  match iformat {
+    gl::R8
+    | gl::R16
+    | gl::R16F
+    | gl::R32F
+    | gl::R8I
+    | gl::R16I
+    | gl::R32I
+    | gl::R8UI
+    | gl::R16UI
+    | gl::R32UI
+    | gl::RG8
+    | gl::RG16
+    | gl::RG16F
+    | gl::RG32F
+    | gl::RG8I
+    | gl::RG16I
+    | gl::RG32I
+    | gl::RG8UI
+    | gl::RG16UI
+    | gl::RG32UI
+    | gl::RGB32F
+    | gl::RGB32I
+    | gl::RGB32UI
+    | gl::RGBA8
+    | gl::RGBA16
+    | gl::RGBA16F
+    | gl::RGBA32F
+    | gl::RGBA8I
+    | gl::RGBA16I
+    | gl::RGBA32I
+    | gl::RGBA8UI
+    | gl::RGBA16UI
+    | gl::RGBA32UI => Some(iformat),
+    _ => None,
+  }
+}
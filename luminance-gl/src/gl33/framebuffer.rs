@@ -18,7 +18,7 @@ where
   pub(crate) handle: GLuint,
   renderbuffer: Option<GLuint>,
   pub(crate) size: D::Size,
-  state: Rc<RefCell<GLState>>,
+  pub(crate) state: Rc<RefCell<GLState>>,
 }
 
 impl<D> Drop for Framebuffer<D>
@@ -6,7 +6,10 @@ use crate::gl33::{
 };
 use luminance::{
   backend::{
-    pipeline::{Pipeline as PipelineBackend, PipelineBase, PipelineTexture},
+    pipeline::{
+      Pipeline as PipelineBackend, PipelineBase, PipelineBufferTexture, PipelineDynTexture,
+      PipelineTexture, PipelineTextureSampler,
+    },
     render_gate::RenderGate,
     shading_gate::ShadingGate,
     tess::Tess,
@@ -63,6 +66,32 @@ where
   }
 }
 
+pub struct BoundBufferTexture {
+  pub(crate) unit: u32,
+  state: Rc<RefCell<GLState>>,
+}
+
+impl Drop for BoundBufferTexture {
+  fn drop(&mut self) {
+    // place the binding into the free list
+    let mut state = self.state.borrow_mut();
+    state.binding_stack_mut().free_texture_units.push(self.unit);
+  }
+}
+
+pub struct BoundDynTexture {
+  pub(crate) unit: u32,
+  state: Rc<RefCell<GLState>>,
+}
+
+impl Drop for BoundDynTexture {
+  fn drop(&mut self) {
+    // place the binding into the free list
+    let mut state = self.state.borrow_mut();
+    state.binding_stack_mut().free_texture_units.push(self.unit);
+  }
+}
+
 unsafe impl PipelineBase for GL33 {
   type PipelineRepr = Pipeline;
 
@@ -158,16 +187,11 @@ where
     P: Pixel,
   {
     let mut state = pipeline.state.borrow_mut();
-    let bstack = state.binding_stack_mut();
-
-    let unit = bstack.free_texture_units.pop().unwrap_or_else(|| {
-      // no more free units; reserve one
-      let unit = bstack.next_texture_unit;
-      bstack.next_texture_unit += 1;
-      unit
-    });
+    let unit = reserve_texture_unit(&mut state);
 
     state.bind_texture_at(texture.target, texture.handle, unit);
+    // a sampler object might still be bound to that unit from a previous binding
+    state.bind_sampler_at(0, unit);
 
     Ok(BoundTexture {
       unit,
@@ -181,6 +205,96 @@ where
   }
 }
 
+unsafe impl<D, P> PipelineTextureSampler<D, P> for GL33
+where
+  D: Dimensionable,
+  P: Pixel,
+{
+  unsafe fn bind_texture_with_sampler(
+    pipeline: &Self::PipelineRepr,
+    texture: &Self::TextureRepr,
+    sampler: &Self::SamplerObjectRepr,
+  ) -> Result<Self::BoundTextureRepr, PipelineError> {
+    let mut state = pipeline.state.borrow_mut();
+    let unit = reserve_texture_unit(&mut state);
+
+    state.bind_texture_at(texture.target, texture.handle, unit);
+    state.bind_sampler_at(sampler.handle, unit);
+
+    Ok(BoundTexture {
+      unit,
+      state: pipeline.state.clone(),
+      _phantom: PhantomData,
+    })
+  }
+}
+
+unsafe impl<D> PipelineDynTexture<D> for GL33
+where
+  D: Dimensionable,
+{
+  type BoundDynTextureRepr = BoundDynTexture;
+
+  unsafe fn bind_dyn_texture(
+    pipeline: &Self::PipelineRepr,
+    texture: &Self::TextureRepr,
+  ) -> Result<Self::BoundDynTextureRepr, PipelineError> {
+    let mut state = pipeline.state.borrow_mut();
+    let unit = reserve_texture_unit(&mut state);
+
+    state.bind_texture_at(texture.target, texture.handle, unit);
+    // a sampler object might still be bound to that unit from a previous binding
+    state.bind_sampler_at(0, unit);
+
+    Ok(BoundDynTexture {
+      unit,
+      state: pipeline.state.clone(),
+    })
+  }
+
+  unsafe fn dyn_texture_binding(bound: &Self::BoundDynTextureRepr) -> u32 {
+    bound.unit
+  }
+}
+
+unsafe impl<P> PipelineBufferTexture<P> for GL33
+where
+  P: Pixel,
+{
+  type BoundBufferTextureRepr = BoundBufferTexture;
+
+  unsafe fn bind_buffer_texture(
+    pipeline: &Self::PipelineRepr,
+    texture: &Self::BufferTextureRepr,
+  ) -> Result<Self::BoundBufferTextureRepr, PipelineError> {
+    let mut state = pipeline.state.borrow_mut();
+    let unit = reserve_texture_unit(&mut state);
+
+    state.bind_texture_at(gl::TEXTURE_BUFFER, texture.handle, unit);
+
+    Ok(BoundBufferTexture {
+      unit,
+      state: pipeline.state.clone(),
+    })
+  }
+
+  unsafe fn buffer_texture_binding(bound: &Self::BoundBufferTextureRepr) -> u32 {
+    bound.unit
+  }
+}
+
+/// Get a free texture unit, reserving a new one if none is available.
+fn reserve_texture_unit(state: &mut GLState) -> u32 {
+  let bstack = state.binding_stack_mut();
+
+  bstack.free_texture_units.pop().unwrap_or_else(|| {
+    // no more free units; reserve one
+    let unit = bstack.next_texture_unit;
+    bstack.next_texture_unit += 1;
+    unit
+  })
+}
+
 unsafe impl<V, I, W> TessGate<V, I, W, Interleaved> for GL33
 where
   V: TessVertexData<Interleaved, Data = Vec<V>>,
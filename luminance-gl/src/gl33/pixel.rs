@@ -23,6 +23,7 @@ pub(crate) fn opengl_pixel_format(pf: PixelFormat) -> Option<(GLenum, GLenum, GL
     (Format::R(Size::Sixteen), Type::Unsigned) => {
       Some((gl::RED_INTEGER, gl::R16UI, gl::UNSIGNED_SHORT))
     }
+    (Format::R(Size::Sixteen), Type::Floating) => Some((gl::RED, gl::R16F, gl::HALF_FLOAT)),
 
     (Format::R(Size::ThirtyTwo), Type::NormUnsigned) => {
       Some((gl::RED_INTEGER, gl::RED, gl::UNSIGNED_INT))
@@ -60,6 +61,9 @@ pub(crate) fn opengl_pixel_format(pf: PixelFormat) -> Option<(GLenum, GLenum, GL
     (Format::RG(Size::Sixteen, Size::Sixteen), Type::Unsigned) => {
       Some((gl::RG_INTEGER, gl::RG16UI, gl::UNSIGNED_SHORT))
     }
+    (Format::RG(Size::Sixteen, Size::Sixteen), Type::Floating) => {
+      Some((gl::RG, gl::RG16F, gl::HALF_FLOAT))
+    }
 
     (Format::RG(Size::ThirtyTwo, Size::ThirtyTwo), Type::NormUnsigned) => {
       Some((gl::RG, gl::RG, gl::UNSIGNED_INT))
@@ -103,6 +107,9 @@ pub(crate) fn opengl_pixel_format(pf: PixelFormat) -> Option<(GLenum, GLenum, GL
     (Format::RGB(Size::Sixteen, Size::Sixteen, Size::Sixteen), Type::Unsigned) => {
       Some((gl::RGB_INTEGER, gl::RGB16UI, gl::UNSIGNED_SHORT))
     }
+    (Format::RGB(Size::Sixteen, Size::Sixteen, Size::Sixteen), Type::Floating) => {
+      Some((gl::RGB, gl::RGB16F, gl::HALF_FLOAT))
+    }
 
     (Format::RGB(Size::Eleven, Size::Eleven, Size::Ten), Type::Floating) => {
       Some((gl::RGB, gl::R11F_G11F_B10F, gl::FLOAT))
@@ -152,6 +159,9 @@ pub(crate) fn opengl_pixel_format(pf: PixelFormat) -> Option<(GLenum, GLenum, GL
     (Format::RGBA(Size::Sixteen, Size::Sixteen, Size::Sixteen, Size::Sixteen), Type::Unsigned) => {
       Some((gl::RGBA_INTEGER, gl::RGBA16UI, gl::UNSIGNED_SHORT))
     }
+    (Format::RGBA(Size::Sixteen, Size::Sixteen, Size::Sixteen, Size::Sixteen), Type::Floating) => {
+      Some((gl::RGBA, gl::RGBA16F, gl::HALF_FLOAT))
+    }
 
     (
       Format::RGBA(Size::ThirtyTwo, Size::ThirtyTwo, Size::ThirtyTwo, Size::ThirtyTwo),
@@ -174,6 +184,19 @@ pub(crate) fn opengl_pixel_format(pf: PixelFormat) -> Option<(GLenum, GLenum, GL
       Type::Floating,
     ) => Some((gl::RGBA, gl::RGBA32F, gl::FLOAT)),
 
+    // packed formats
+    (Format::RGBA(Size::Ten, Size::Ten, Size::Ten, Size::Two), Type::NormUnsigned) => {
+      Some((gl::RGBA, gl::RGB10_A2, gl::UNSIGNED_INT_2_10_10_10_REV))
+    }
+    (Format::RGBA(Size::Ten, Size::Ten, Size::Ten, Size::Two), Type::Unsigned) => Some((
+      gl::RGBA_INTEGER,
+      gl::RGB10_A2UI,
+      gl::UNSIGNED_INT_2_10_10_10_REV,
+    )),
+    (Format::RGBE(Size::Nine, Size::Nine, Size::Nine, Size::Five), Type::Floating) => {
+      Some((gl::RGB, gl::RGB9_E5, gl::UNSIGNED_INT_5_9_9_9_REV))
+    }
+
     // sRGB
     (Format::SRGB(Size::Eight, Size::Eight, Size::Eight), Type::NormUnsigned) => {
       Some((gl::RGB, gl::SRGB8, gl::UNSIGNED_BYTE))
@@ -188,6 +211,14 @@ pub(crate) fn opengl_pixel_format(pf: PixelFormat) -> Option<(GLenum, GLenum, GL
       Some((gl::RGBA, gl::SRGB8_ALPHA8, gl::BYTE))
     }
 
+    (Format::Depth(Size::Sixteen), Type::NormUnsigned) => Some((
+      gl::DEPTH_COMPONENT,
+      gl::DEPTH_COMPONENT16,
+      gl::UNSIGNED_SHORT,
+    )),
+    (Format::Depth(Size::TwentyFour), Type::NormUnsigned) => {
+      Some((gl::DEPTH_COMPONENT, gl::DEPTH_COMPONENT24, gl::UNSIGNED_INT))
+    }
     (Format::Depth(Size::ThirtyTwo), Type::Floating) => {
       Some((gl::DEPTH_COMPONENT, gl::DEPTH_COMPONENT32F, gl::FLOAT))
     }
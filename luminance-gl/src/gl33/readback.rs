@@ -0,0 +1,228 @@
+//! Asynchronous texel readback support for OpenGL 3.3.
+//!
+//! Texels are read into a pixel buffer object (PBO) and a fence sync is inserted right after, so
+//! that we know when the GPU is done with the transfer without stalling it.
+
+use gl::types::*;
+use luminance::backend::texture::{
+  FramebufferReadback, PendingReadback as PendingReadbackBackend, PendingReadbackBase,
+  TextureReadback,
+};
+use luminance::pixel::Pixel;
+use luminance::texture::{Dimensionable, TextureError};
+use std::cell::RefCell;
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+use std::rc::Rc;
+
+use crate::gl33::pixel::opengl_pixel_format;
+use crate::gl33::state::GLState;
+use crate::gl33::texture::{read_texels_part, set_pack_alignment, texels_part_len};
+use crate::gl33::GL33;
+
+pub struct PendingReadback {
+  buffer: GLuint,
+  sync: GLsync,
+  bytes_len: usize,
+  state: Rc<RefCell<GLState>>,
+}
+
+impl Drop for PendingReadback {
+  fn drop(&mut self) {
+    unsafe {
+      gl::DeleteSync(self.sync);
+      self.state.borrow_mut().unbind_buffer(self.buffer);
+      gl::DeleteBuffers(1, &self.buffer);
+    }
+  }
+}
+
+impl PendingReadback {
+  /// Create a PBO of `bytes_len` bytes, bind it as pixel pack buffer and run `read` to start the
+  /// transfer.
+  unsafe fn new<F>(
+    state: &Rc<RefCell<GLState>>,
+    bytes_len: usize,
+    read: F,
+  ) -> Result<Self, TextureError>
+  where
+    F: FnOnce(&mut GLState) -> Result<(), TextureError>,
+  {
+    let mut buffer: GLuint = 0;
+    gl::GenBuffers(1, &mut buffer);
+    gl::BindBuffer(gl::PIXEL_PACK_BUFFER, buffer);
+    gl::BufferData(
+      gl::PIXEL_PACK_BUFFER,
+      bytes_len as GLsizeiptr,
+      ptr::null(),
+      gl::STREAM_READ,
+    );
+
+    let r = read(&mut state.borrow_mut());
+
+    // the rest of the backend expects reads to go to client memory
+    gl::BindBuffer(gl::PIXEL_PACK_BUFFER, 0);
+
+    if let Err(e) = r {
+      gl::DeleteBuffers(1, &buffer);
+      return Err(e);
+    }
+
+    let sync = gl::FenceSync(gl::SYNC_GPU_COMMANDS_COMPLETE, 0);
+    // make sure the fence eventually gets signaled, even if nothing else is submitted
+    gl::Flush();
+
+    Ok(PendingReadback {
+      buffer,
+      sync,
+      bytes_len,
+      state: state.clone(),
+    })
+  }
+
+  /// Wait on the fence for at most `timeout` nanoseconds.
+  ///
+  /// Returns whether the transfer is complete.
+  unsafe fn client_wait(&self, timeout: GLuint64) -> Result<bool, TextureError> {
+    match gl::ClientWaitSync(self.sync, gl::SYNC_FLUSH_COMMANDS_BIT, timeout) {
+      gl::ALREADY_SIGNALED | gl::CONDITION_SATISFIED => Ok(true),
+      gl::TIMEOUT_EXPIRED => Ok(false),
+      _ => Err(TextureError::cannot_retrieve_texels(
+        "failed to wait for the readback fence",
+      )),
+    }
+  }
+
+  unsafe fn texels<T>(&self) -> Vec<T>
+  where
+    T: Copy + Default,
+  {
+    let mut texels = vec![T::default(); self.bytes_len / mem::size_of::<T>()];
+
+    gl::BindBuffer(gl::COPY_READ_BUFFER, self.buffer);
+    gl::GetBufferSubData(
+      gl::COPY_READ_BUFFER,
+      0,
+      self.bytes_len as GLsizeiptr,
+      texels.as_mut_ptr() as *mut c_void,
+    );
+    gl::BindBuffer(gl::COPY_READ_BUFFER, 0);
+
+    texels
+  }
+}
+
+unsafe impl PendingReadbackBase for GL33 {
+  type PendingReadbackRepr = PendingReadback;
+}
+
+unsafe impl<P> PendingReadbackBackend<P> for GL33
+where
+  P: Pixel,
+{
+  unsafe fn try_get_readback(
+    pending: &mut Self::PendingReadbackRepr,
+  ) -> Result<Option<Vec<P::RawEncoding>>, TextureError>
+  where
+    P::RawEncoding: Copy + Default,
+  {
+    if pending.client_wait(0)? {
+      Ok(Some(pending.texels()))
+    } else {
+      Ok(None)
+    }
+  }
+
+  unsafe fn wait_readback(
+    pending: &mut Self::PendingReadbackRepr,
+  ) -> Result<Vec<P::RawEncoding>, TextureError>
+  where
+    P::RawEncoding: Copy + Default,
+  {
+    // wait by steps of one second, so that a spurious timeout doesn’t make us give up
+    while !pending.client_wait(1_000_000_000)? {}
+
+    Ok(pending.texels())
+  }
+}
+
+unsafe impl<D, P> TextureReadback<D, P> for GL33
+where
+  D: Dimensionable,
+  P: Pixel,
+{
+  unsafe fn start_texture_readback(
+    texture: &Self::TextureRepr,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+  ) -> Result<Self::PendingReadbackRepr, TextureError> {
+    let pf = P::pixel_format();
+    let texel_bytes = pf.raw_len() * mem::size_of::<P::RawEncoding>();
+    let bytes_len = texels_part_len::<D>(size) * texel_bytes;
+
+    PendingReadback::new(&texture.state, bytes_len, |state| {
+      // with a pixel pack buffer bound, the destination is an offset into that buffer
+      read_texels_part::<D, P>(state, texture, level, offset, size, ptr::null_mut())
+    })
+  }
+}
+
+unsafe impl<FD, P> FramebufferReadback<FD, P> for GL33
+where
+  FD: Dimensionable,
+  P: Pixel,
+{
+  unsafe fn start_framebuffer_readback(
+    framebuffer: &Self::FramebufferRepr,
+    attachment_index: usize,
+    offset: [u32; 2],
+    size: [u32; 2],
+  ) -> Result<Self::PendingReadbackRepr, TextureError> {
+    let pf = P::pixel_format();
+
+    if pf.is_depth_pixel() {
+      return Err(TextureError::cannot_retrieve_texels(
+        "only color attachments can be read back",
+      ));
+    }
+
+    let (format, _, ty) =
+      opengl_pixel_format(pf).ok_or(TextureError::UnsupportedPixelFormat(pf))?;
+
+    let read_buffer = if framebuffer.handle == 0 {
+      if attachment_index != 0 {
+        return Err(TextureError::cannot_retrieve_texels(
+          "back buffers only have a single color attachment",
+        ));
+      }
+
+      gl::BACK
+    } else {
+      gl::COLOR_ATTACHMENT0 + attachment_index as GLenum
+    };
+
+    let [w, h] = size;
+    let texel_bytes = pf.raw_len() * mem::size_of::<P::RawEncoding>();
+    let bytes_len = (w * h) as usize * texel_bytes;
+
+    PendingReadback::new(&framebuffer.state, bytes_len, |state| {
+      state.bind_read_framebuffer(framebuffer.handle);
+      gl::ReadBuffer(read_buffer);
+
+      set_pack_alignment((pf.format.bytes_len() * w as usize) % 8);
+      gl::ReadPixels(
+        offset[0] as GLint,
+        offset[1] as GLint,
+        w as GLsizei,
+        h as GLsizei,
+        format,
+        ty,
+        ptr::null_mut(),
+      );
+
+      Ok(())
+    })
+  }
+}
@@ -0,0 +1,57 @@
+//! Standalone sampler objects support for OpenGL 3.3.
+
+use gl::types::*;
+use luminance::backend::texture::SamplerObject as SamplerObjectBackend;
+use luminance::texture::{Sampler, TextureError};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::gl33::state::GLState;
+use crate::gl33::texture::{apply_sampler, SamplerTarget};
+use crate::gl33::GL33;
+
+pub struct SamplerObject {
+  pub(crate) handle: GLuint,
+  state: Rc<RefCell<GLState>>,
+}
+
+impl Drop for SamplerObject {
+  fn drop(&mut self) {
+    self.state.borrow_mut().unbind_sampler(self.handle);
+
+    unsafe {
+      gl::DeleteSamplers(1, &self.handle);
+    }
+  }
+}
+
+unsafe impl SamplerObjectBackend for GL33 {
+  type SamplerObjectRepr = SamplerObject;
+
+  unsafe fn new_sampler_object(
+    &mut self,
+    sampler: Sampler,
+  ) -> Result<Self::SamplerObjectRepr, TextureError> {
+    let mut handle = 0;
+    gl::GenSamplers(1, &mut handle);
+
+    if handle == 0 {
+      return Err(TextureError::sampler_creation_failed(
+        "cannot create sampler object",
+      ));
+    }
+
+    apply_sampler(
+      &mut self.state.borrow_mut(),
+      SamplerTarget::SamplerObject(handle),
+      sampler,
+    );
+
+    let sampler = SamplerObject {
+      handle,
+      state: self.state.clone(),
+    };
+
+    Ok(sampler)
+  }
+}
@@ -2,16 +2,23 @@ use gl;
 use gl::types::*;
 use std::ffi::CString;
 use std::ptr::{null, null_mut};
+use std::slice;
 
 use crate::gl33::GL33;
-use luminance::backend::shader::{Shader, Uniformable};
-use luminance::pipeline::{BufferBinding, TextureBinding};
+use luminance::backend::shader::{
+  Shader, ShaderBinary, ShaderReflection, ShaderSpirv, ShaderUniformCache, Uniformable,
+};
+use luminance::pipeline::{BufferBinding, BufferTextureBinding, TextureBinding};
 use luminance::pixel::{SamplerType, Type as PixelType};
+use luminance::shader::preprocessor::with_prelude;
+use luminance::shader::reflection::{
+  ActiveAttribute, ActiveUniform, ActiveUniformBlock, UniformBlockMember,
+};
 use luminance::shader::{
-  ProgramError, StageError, StageType, TessellationStages, Uniform, UniformType, UniformWarning,
-  VertexAttribWarning,
+  GlslTarget, ProgramError, StageError, StageType, TessellationStages, Uniform, UniformCache,
+  UniformCacheStats, UniformType, UniformWarning, VertexAttribWarning,
 };
-use luminance::texture::{Dim, Dimensionable};
+use luminance::texture::{Dim, SampledWith};
 use luminance::vertex::Semantics;
 
 #[derive(Debug)]
@@ -31,6 +38,7 @@ impl Drop for Stage {
 #[derive(Debug)]
 pub struct Program {
   pub(crate) handle: GLuint,
+  uniform_cache: Option<UniformCache<UniformKey>>,
 }
 
 impl Drop for Program {
@@ -42,6 +50,33 @@ impl Drop for Program {
 }
 
 impl Program {
+  fn new(handle: GLuint) -> Self {
+    Program {
+      handle,
+      uniform_cache: None,
+    }
+  }
+
+  /// Check whether `value` was the last value uploaded to the uniform identified by `key`, and
+  /// remember it otherwise.
+  ///
+  /// `T` must not contain any padding byte.
+  unsafe fn is_uniform_cached<T>(&mut self, key: UniformKey, value: &[T]) -> bool {
+    match self.uniform_cache {
+      Some(ref mut cache) => {
+        if cache.is_cached(key, value) {
+          return true;
+        }
+
+        // the upload overwrites every other uniform sharing one of its locations
+        cache.invalidate(|other| *other != key && other.overlaps(&key));
+        false
+      }
+
+      None => false,
+    }
+  }
+
   fn link(&self) -> Result<(), ProgramError> {
     let handle = self.handle;
 
@@ -68,6 +103,38 @@ impl Program {
   }
 }
 
+/// Uniform a value is cached for.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum UniformKey {
+  /// Uniform location.
+  Location(GLint),
+  /// Uniform array location and number of elements; element `i` lives at location + `i`.
+  Array(GLint, GLsizei),
+  /// Uniform block index.
+  Block(GLint),
+}
+
+impl UniformKey {
+  /// Range of locations the uniform spans.
+  fn locations(&self) -> Option<(GLint, GLint)> {
+    match *self {
+      UniformKey::Location(location) => Some((location, location + 1)),
+      UniformKey::Array(location, len) => Some((location, location + len)),
+      UniformKey::Block(_) => None,
+    }
+  }
+
+  /// Whether both uniforms share a location.
+  fn overlaps(&self, other: &Self) -> bool {
+    match (self.locations(), other.locations()) {
+      (Some((start, end)), Some((other_start, other_end))) => {
+        start < other_end && other_start < end
+      }
+      _ => false,
+    }
+  }
+}
+
 pub struct UniformBuilder {
   handle: GLuint,
 }
@@ -133,27 +200,7 @@ unsafe impl Shader for GL33 {
     gl::ShaderSource(handle, 1, [c_src.as_ptr()].as_ptr(), null());
     gl::CompileShader(handle);
 
-    let mut compiled: GLint = gl::FALSE.into();
-    gl::GetShaderiv(handle, gl::COMPILE_STATUS, &mut compiled);
-
-    if compiled == gl::TRUE.into() {
-      Ok(Stage { handle, ty })
-    } else {
-      let mut log_len: GLint = 0;
-      gl::GetShaderiv(handle, gl::INFO_LOG_LENGTH, &mut log_len);
-
-      let mut log: Vec<u8> = Vec::with_capacity(log_len as usize);
-      gl::GetShaderInfoLog(handle, log_len, null_mut(), log.as_mut_ptr() as *mut GLchar);
-
-      gl::DeleteShader(handle);
-
-      log.set_len(log_len as usize);
-
-      Err(StageError::compilation_failed(
-        ty,
-        String::from_utf8(log).unwrap(),
-      ))
-    }
+    compiled_stage(handle, ty)
   }
 
   unsafe fn new_program(
@@ -182,7 +229,7 @@ unsafe impl Shader for GL33 {
 
     gl::AttachShader(handle, fragment.handle);
 
-    let program = Program { handle };
+    let program = Program::new(handle);
     program.link().map(move |_| program)
   }
 
@@ -230,6 +277,363 @@ unsafe impl Shader for GL33 {
   }
 }
 
+// check the compilation status of a stage and get it, or its compilation log if it failed
+unsafe fn compiled_stage(handle: GLuint, ty: StageType) -> Result<Stage, StageError> {
+  let mut compiled: GLint = gl::FALSE.into();
+  gl::GetShaderiv(handle, gl::COMPILE_STATUS, &mut compiled);
+
+  if compiled == gl::TRUE.into() {
+    Ok(Stage { handle, ty })
+  } else {
+    let mut log_len: GLint = 0;
+    gl::GetShaderiv(handle, gl::INFO_LOG_LENGTH, &mut log_len);
+
+    let mut log: Vec<u8> = Vec::with_capacity(log_len as usize);
+    gl::GetShaderInfoLog(handle, log_len, null_mut(), log.as_mut_ptr() as *mut GLchar);
+
+    gl::DeleteShader(handle);
+
+    log.set_len(log_len as usize);
+
+    Err(StageError::compilation_failed(
+      ty,
+      String::from_utf8(log).unwrap(),
+    ))
+  }
+}
+
+unsafe impl ShaderSpirv for GL33 {
+  #[cfg(feature = "GL_ARB_gl_spirv")]
+  unsafe fn supports_spirv(&mut self) -> bool {
+    crate::gl33::spirv::is_loaded() && self.state.borrow_mut().has_extension("GL_ARB_gl_spirv")
+  }
+
+  #[cfg(not(feature = "GL_ARB_gl_spirv"))]
+  unsafe fn supports_spirv(&mut self) -> bool {
+    false
+  }
+
+  #[cfg(feature = "GL_ARB_gl_spirv")]
+  unsafe fn new_stage_from_spirv(
+    &mut self,
+    ty: StageType,
+    module: &[u8],
+    entry_point: &str,
+  ) -> Result<Self::StageRepr, StageError> {
+    use crate::gl33::spirv::{specialize_shader, SHADER_BINARY_FORMAT_SPIR_V};
+
+    if !self.supports_spirv() {
+      return Err(StageError::unsupported_spirv(ty));
+    }
+
+    let handle = gl::CreateShader(opengl_shader_type(ty));
+
+    if handle == 0 {
+      return Err(StageError::compilation_failed(
+        ty,
+        "unable to create shader stage",
+      ));
+    }
+
+    gl::ShaderBinary(
+      1,
+      &handle,
+      SHADER_BINARY_FORMAT_SPIR_V,
+      module.as_ptr() as *const _,
+      module.len() as GLsizei,
+    );
+
+    let c_entry_point = CString::new(entry_point.as_bytes()).unwrap();
+    specialize_shader(handle, c_entry_point.as_ptr(), 0, null(), null());
+
+    compiled_stage(handle, ty)
+  }
+
+  #[cfg(not(feature = "GL_ARB_gl_spirv"))]
+  unsafe fn new_stage_from_spirv(
+    &mut self,
+    ty: StageType,
+    _: &[u8],
+    _: &str,
+  ) -> Result<Self::StageRepr, StageError> {
+    Err(StageError::unsupported_spirv(ty))
+  }
+
+  unsafe fn glsl_target(&mut self) -> GlslTarget {
+    GlslTarget::Glsl330
+  }
+}
+
+// program binaries are core since OpenGL 4.1 and otherwise provided by GL_ARB_get_program_binary; if
+// the entry points are not loaded, or if the driver doesn’t support any binary format, programs are
+// always compiled from their sources
+unsafe impl ShaderBinary for GL33 {
+  fn source_prelude() -> String {
+    glsl_pragma()
+  }
+
+  unsafe fn set_binary_retrievable(program: &mut Self::ProgramRepr) {
+    if gl::ProgramParameteri::is_loaded() {
+      gl::ProgramParameteri(
+        program.handle,
+        gl::PROGRAM_BINARY_RETRIEVABLE_HINT,
+        gl::TRUE.into(),
+      );
+    }
+  }
+
+  unsafe fn program_binary(program: &Self::ProgramRepr) -> Option<(u32, Vec<u8>)> {
+    if !gl::GetProgramBinary::is_loaded() {
+      return None;
+    }
+
+    let mut len: GLint = 0;
+    gl::GetProgramiv(program.handle, gl::PROGRAM_BINARY_LENGTH, &mut len);
+
+    if len <= 0 {
+      return None;
+    }
+
+    let mut binary = vec![0u8; len as usize];
+    let mut written: GLsizei = 0;
+    let mut format: GLenum = 0;
+    gl::GetProgramBinary(
+      program.handle,
+      len,
+      &mut written,
+      &mut format,
+      binary.as_mut_ptr() as *mut _,
+    );
+
+    if written <= 0 {
+      return None;
+    }
+
+    binary.truncate(written as usize);
+    Some((format, binary))
+  }
+
+  unsafe fn new_program_from_binary(
+    &mut self,
+    format: u32,
+    binary: &[u8],
+  ) -> Option<Self::ProgramRepr> {
+    if !gl::ProgramBinary::is_loaded() {
+      return None;
+    }
+
+    let mut formats_len: GLint = 0;
+    gl::GetIntegerv(gl::NUM_PROGRAM_BINARY_FORMATS, &mut formats_len);
+
+    if formats_len <= 0 {
+      return None;
+    }
+
+    let program = Program::new(gl::CreateProgram());
+    gl::ProgramBinary(
+      program.handle,
+      format,
+      binary.as_ptr() as *const _,
+      binary.len() as GLsizei,
+    );
+
+    // a rejected binary leaves the program unlinked
+    let mut linked: GLint = gl::FALSE.into();
+    gl::GetProgramiv(program.handle, gl::LINK_STATUS, &mut linked);
+
+    if linked == gl::TRUE.into() {
+      Some(program)
+    } else {
+      None
+    }
+  }
+}
+
+unsafe impl ShaderUniformCache for GL33 {
+  unsafe fn set_uniform_cache(program: &mut Self::ProgramRepr, enabled: bool) {
+    if !enabled {
+      program.uniform_cache = None;
+    } else if program.uniform_cache.is_none() {
+      program.uniform_cache = Some(UniformCache::default());
+    }
+  }
+
+  unsafe fn uniform_cache_stats(program: &Self::ProgramRepr) -> Option<UniformCacheStats> {
+    program.uniform_cache.as_ref().map(UniformCache::stats)
+  }
+
+  unsafe fn reset_uniform_cache_stats(program: &mut Self::ProgramRepr) {
+    if let Some(ref mut cache) = program.uniform_cache {
+      cache.reset_stats();
+    }
+  }
+}
+
+unsafe impl ShaderReflection for GL33 {
+  unsafe fn active_uniforms(program: &Self::ProgramRepr) -> Vec<ActiveUniform> {
+    let handle = program.handle;
+    let mut count: GLint = 0;
+    gl::GetProgramiv(handle, gl::ACTIVE_UNIFORMS, &mut count);
+
+    (0..count as GLuint)
+      .filter_map(|index| {
+        // uniform block members are reported with their block
+        let mut block: GLint = -1;
+        gl::GetActiveUniformsiv(handle, 1, &index, gl::UNIFORM_BLOCK_INDEX, &mut block);
+
+        if block != -1 {
+          return None;
+        }
+
+        let (name, size, glty) = active_uniform(handle, index);
+        let c_name = CString::new(name.as_bytes()).unwrap();
+        let location = gl::GetUniformLocation(handle, c_name.as_ptr() as *const GLchar);
+
+        Some(ActiveUniform {
+          name,
+          ty: uniform_type(glty),
+          array_size: size as usize,
+          location,
+        })
+      })
+      .collect()
+  }
+
+  unsafe fn active_uniform_blocks(program: &Self::ProgramRepr) -> Vec<ActiveUniformBlock> {
+    let handle = program.handle;
+    let mut count: GLint = 0;
+    gl::GetProgramiv(handle, gl::ACTIVE_UNIFORM_BLOCKS, &mut count);
+
+    let mut max_len: GLint = 0;
+    gl::GetProgramiv(
+      handle,
+      gl::ACTIVE_UNIFORM_BLOCK_MAX_NAME_LENGTH,
+      &mut max_len,
+    );
+
+    (0..count as GLuint)
+      .map(|index| {
+        let mut name = vec![0u8; max_len.max(1) as usize];
+        let mut len: GLsizei = 0;
+        gl::GetActiveUniformBlockName(
+          handle,
+          index,
+          max_len,
+          &mut len,
+          name.as_mut_ptr() as *mut GLchar,
+        );
+        name.truncate(len as usize);
+
+        let mut size: GLint = 0;
+        gl::GetActiveUniformBlockiv(handle, index, gl::UNIFORM_BLOCK_DATA_SIZE, &mut size);
+
+        let mut members_len: GLint = 0;
+        gl::GetActiveUniformBlockiv(
+          handle,
+          index,
+          gl::UNIFORM_BLOCK_ACTIVE_UNIFORMS,
+          &mut members_len,
+        );
+
+        let mut members = vec![0 as GLint; members_len as usize];
+        if members_len > 0 {
+          gl::GetActiveUniformBlockiv(
+            handle,
+            index,
+            gl::UNIFORM_BLOCK_ACTIVE_UNIFORM_INDICES,
+            members.as_mut_ptr(),
+          );
+        }
+
+        let members = members
+          .into_iter()
+          .map(|member| {
+            let member = member as GLuint;
+            let (name, size, glty) = active_uniform(handle, member);
+
+            let mut offset: GLint = 0;
+            gl::GetActiveUniformsiv(handle, 1, &member, gl::UNIFORM_OFFSET, &mut offset);
+
+            UniformBlockMember {
+              name,
+              ty: uniform_type(glty),
+              array_size: size as usize,
+              offset: offset as usize,
+            }
+          })
+          .collect();
+
+        ActiveUniformBlock {
+          name: String::from_utf8_lossy(&name).into_owned(),
+          index,
+          size: size as usize,
+          members,
+        }
+      })
+      .collect()
+  }
+
+  unsafe fn active_attributes(program: &Self::ProgramRepr) -> Vec<ActiveAttribute> {
+    let handle = program.handle;
+    let mut count: GLint = 0;
+    gl::GetProgramiv(handle, gl::ACTIVE_ATTRIBUTES, &mut count);
+
+    let mut max_len: GLint = 0;
+    gl::GetProgramiv(handle, gl::ACTIVE_ATTRIBUTE_MAX_LENGTH, &mut max_len);
+
+    (0..count as GLuint)
+      .map(|index| {
+        let mut name = vec![0u8; max_len.max(1) as usize];
+        let mut len: GLsizei = 0;
+        let mut size: GLint = 0;
+        let mut glty: GLenum = 0;
+        gl::GetActiveAttrib(
+          handle,
+          index,
+          max_len,
+          &mut len,
+          &mut size,
+          &mut glty,
+          name.as_mut_ptr() as *mut GLchar,
+        );
+        name.truncate(len as usize);
+
+        let c_name = CString::new(name.clone()).unwrap();
+        let location = gl::GetAttribLocation(handle, c_name.as_ptr() as *const GLchar);
+
+        ActiveAttribute {
+          name: String::from_utf8_lossy(&name).into_owned(),
+          ty: uniform_type(glty),
+          location,
+        }
+      })
+      .collect()
+  }
+}
+
+// get the name, size and OpenGL type of an active uniform
+unsafe fn active_uniform(program: GLuint, index: GLuint) -> (String, GLint, GLenum) {
+  let mut max_len: GLint = 0;
+  gl::GetProgramiv(program, gl::ACTIVE_UNIFORM_MAX_LENGTH, &mut max_len);
+
+  let mut name = vec![0u8; max_len.max(1) as usize];
+  let mut len: GLsizei = 0;
+  let mut size: GLint = 0;
+  let mut glty: GLenum = 0;
+  gl::GetActiveUniform(
+    program,
+    index,
+    max_len,
+    &mut len,
+    &mut size,
+    &mut glty,
+    name.as_mut_ptr() as *mut GLchar,
+  );
+  name.truncate(len as usize);
+
+  (String::from_utf8_lossy(&name).into_owned(), size, glty)
+}
+
 fn opengl_shader_type(t: StageType) -> GLenum {
   match t {
     StageType::TessellationControlShader => gl::TESS_CONTROL_SHADER,
@@ -240,20 +644,24 @@ fn opengl_shader_type(t: StageType) -> GLenum {
   }
 }
 
-#[cfg(feature = "GL_ARB_gpu_shader_fp64")]
-const GLSL_PRAGMA: &str = "#version 330 core\n\
-                           #extension GL_ARB_separate_shader_objects : require\n
-                           #extension GL_ARB_gpu_shader_fp64 : require\n";
-#[cfg(not(feature = "GL_ARB_gpu_shader_fp64"))]
 const GLSL_PRAGMA: &str = "#version 330 core\n\
                            #extension GL_ARB_separate_shader_objects : require\n";
 
-fn glsl_pragma_src(src: &str) -> String {
+fn glsl_pragma() -> String {
+  // only extended when extension features are enabled
+  #[allow(unused_mut)]
   let mut pragma = String::from(GLSL_PRAGMA);
-  pragma.push_str(src);
+  #[cfg(feature = "GL_ARB_gpu_shader_fp64")]
+  pragma.push_str("#extension GL_ARB_gpu_shader_fp64 : require\n");
+  #[cfg(feature = "GL_ARB_bindless_texture")]
+  pragma.push_str("#extension GL_ARB_bindless_texture : require\n");
   pragma
 }
 
+fn glsl_pragma_src(src: &str) -> String {
+  with_prelude(&glsl_pragma(), src)
+}
+
 fn uniform_type_match(program: GLuint, name: &str, ty: UniformType) -> Result<(), UniformWarning> {
   let mut size: GLint = 0;
   let mut glty: GLuint = 0;
@@ -295,78 +703,111 @@ fn uniform_type_match(program: GLuint, name: &str, ty: UniformType) -> Result<()
   check_types_match(name, ty, glty)
 }
 
-#[allow(clippy::cognitive_complexity)]
-fn check_types_match(name: &str, ty: UniformType, glty: GLuint) -> Result<(), UniformWarning> {
-  // helper macro to check type mismatch for each variant
-  macro_rules! milkcheck {
-    ($ty:expr, $( ( $v:tt, $t:tt ) ),*) => {
-      match $ty {
-        $(
-          UniformType::$v => {
-            if glty == gl::$t {
-              Ok(())
-            } else {
-              Err(UniformWarning::type_mismatch(name, ty))
-            }
-          }
-        )*
+// GLSL types luminance supports, along with their OpenGL type
+const UNIFORM_TYPES: &[(UniformType, GLenum)] = &[
+  // scalars
+  (UniformType::Int, gl::INT),
+  (UniformType::UInt, gl::UNSIGNED_INT),
+  (UniformType::Float, gl::FLOAT),
+  (UniformType::Double, gl::DOUBLE),
+  (UniformType::Bool, gl::BOOL),
+  // vectors
+  (UniformType::IVec2, gl::INT_VEC2),
+  (UniformType::IVec3, gl::INT_VEC3),
+  (UniformType::IVec4, gl::INT_VEC4),
+  (UniformType::UIVec2, gl::UNSIGNED_INT_VEC2),
+  (UniformType::UIVec3, gl::UNSIGNED_INT_VEC3),
+  (UniformType::UIVec4, gl::UNSIGNED_INT_VEC4),
+  (UniformType::Vec2, gl::FLOAT_VEC2),
+  (UniformType::Vec3, gl::FLOAT_VEC3),
+  (UniformType::Vec4, gl::FLOAT_VEC4),
+  (UniformType::DVec2, gl::DOUBLE_VEC2),
+  (UniformType::DVec3, gl::DOUBLE_VEC3),
+  (UniformType::DVec4, gl::DOUBLE_VEC4),
+  (UniformType::BVec2, gl::BOOL_VEC2),
+  (UniformType::BVec3, gl::BOOL_VEC3),
+  (UniformType::BVec4, gl::BOOL_VEC4),
+  // matrices
+  (UniformType::M22, gl::FLOAT_MAT2),
+  (UniformType::M33, gl::FLOAT_MAT3),
+  (UniformType::M44, gl::FLOAT_MAT4),
+  (UniformType::DM22, gl::DOUBLE_MAT2),
+  (UniformType::DM33, gl::DOUBLE_MAT3),
+  (UniformType::DM44, gl::DOUBLE_MAT4),
+  // textures
+  (UniformType::ISampler1D, gl::INT_SAMPLER_1D),
+  (UniformType::ISampler2D, gl::INT_SAMPLER_2D),
+  (UniformType::ISampler3D, gl::INT_SAMPLER_3D),
+  (UniformType::ISampler1DArray, gl::INT_SAMPLER_1D_ARRAY),
+  (UniformType::ISampler2DArray, gl::INT_SAMPLER_2D_ARRAY),
+  (UniformType::UISampler1D, gl::UNSIGNED_INT_SAMPLER_1D),
+  (UniformType::UISampler2D, gl::UNSIGNED_INT_SAMPLER_2D),
+  (UniformType::UISampler3D, gl::UNSIGNED_INT_SAMPLER_3D),
+  (
+    UniformType::UISampler1DArray,
+    gl::UNSIGNED_INT_SAMPLER_1D_ARRAY,
+  ),
+  (
+    UniformType::UISampler2DArray,
+    gl::UNSIGNED_INT_SAMPLER_2D_ARRAY,
+  ),
+  (UniformType::Sampler1D, gl::SAMPLER_1D),
+  (UniformType::Sampler2D, gl::SAMPLER_2D),
+  (UniformType::Sampler3D, gl::SAMPLER_3D),
+  (UniformType::Sampler1DArray, gl::SAMPLER_1D_ARRAY),
+  (UniformType::Sampler2DArray, gl::SAMPLER_2D_ARRAY),
+  (UniformType::ICubemap, gl::INT_SAMPLER_CUBE),
+  (UniformType::UICubemap, gl::UNSIGNED_INT_SAMPLER_CUBE),
+  (UniformType::Cubemap, gl::SAMPLER_CUBE),
+  (UniformType::ICubemapArray, gl::INT_SAMPLER_CUBE_MAP_ARRAY),
+  (
+    UniformType::UICubemapArray,
+    gl::UNSIGNED_INT_SAMPLER_CUBE_MAP_ARRAY,
+  ),
+  (UniformType::CubemapArray, gl::SAMPLER_CUBE_MAP_ARRAY),
+  (UniformType::ISamplerBuffer, gl::INT_SAMPLER_BUFFER),
+  (
+    UniformType::UISamplerBuffer,
+    gl::UNSIGNED_INT_SAMPLER_BUFFER,
+  ),
+  (UniformType::SamplerBuffer, gl::SAMPLER_BUFFER),
+  (UniformType::Sampler1DShadow, gl::SAMPLER_1D_SHADOW),
+  (UniformType::Sampler2DShadow, gl::SAMPLER_2D_SHADOW),
+  (
+    UniformType::Sampler1DArrayShadow,
+    gl::SAMPLER_1D_ARRAY_SHADOW,
+  ),
+  (
+    UniformType::Sampler2DArrayShadow,
+    gl::SAMPLER_2D_ARRAY_SHADOW,
+  ),
+  (UniformType::CubemapShadow, gl::SAMPLER_CUBE_SHADOW),
+  (
+    UniformType::CubemapArrayShadow,
+    gl::SAMPLER_CUBE_MAP_ARRAY_SHADOW,
+  ),
+  (UniformType::ISampler2DMS, gl::INT_SAMPLER_2D_MULTISAMPLE),
+  (
+    UniformType::UISampler2DMS,
+    gl::UNSIGNED_INT_SAMPLER_2D_MULTISAMPLE,
+  ),
+  (UniformType::Sampler2DMS, gl::SAMPLER_2D_MULTISAMPLE),
+];
 
-        _ => Err(UniformWarning::unsupported_type(name, ty))
-      }
-    }
+fn check_types_match(name: &str, ty: UniformType, glty: GLuint) -> Result<(), UniformWarning> {
+  match UNIFORM_TYPES.iter().find(|(t, _)| *t == ty) {
+    Some((_, t)) if *t == glty => Ok(()),
+    Some(_) => Err(UniformWarning::type_mismatch(name, ty)),
+    None => Err(UniformWarning::unsupported_type(name, ty)),
   }
+}
 
-  milkcheck!(
-    ty,
-    // scalars
-    (Int, INT),
-    (UInt, UNSIGNED_INT),
-    (Float, FLOAT),
-    (Double, DOUBLE),
-    (Bool, BOOL),
-    // vectors
-    (IVec2, INT_VEC2),
-    (IVec3, INT_VEC3),
-    (IVec4, INT_VEC4),
-    (UIVec2, UNSIGNED_INT_VEC2),
-    (UIVec3, UNSIGNED_INT_VEC3),
-    (UIVec4, UNSIGNED_INT_VEC4),
-    (Vec2, FLOAT_VEC2),
-    (Vec3, FLOAT_VEC3),
-    (Vec4, FLOAT_VEC4),
-    (DVec2, DOUBLE_VEC2),
-    (DVec3, DOUBLE_VEC3),
-    (DVec4, DOUBLE_VEC4),
-    (BVec2, BOOL_VEC2),
-    (BVec3, BOOL_VEC3),
-    (BVec4, BOOL_VEC4),
-    // matrices
-    (M22, FLOAT_MAT2),
-    (M33, FLOAT_MAT3),
-    (M44, FLOAT_MAT4),
-    (DM22, DOUBLE_MAT2),
-    (DM33, DOUBLE_MAT3),
-    (DM44, DOUBLE_MAT4),
-    // textures
-    (ISampler1D, INT_SAMPLER_1D),
-    (ISampler2D, INT_SAMPLER_2D),
-    (ISampler3D, INT_SAMPLER_3D),
-    (ISampler1DArray, INT_SAMPLER_1D_ARRAY),
-    (ISampler2DArray, INT_SAMPLER_2D_ARRAY),
-    (UISampler1D, UNSIGNED_INT_SAMPLER_1D),
-    (UISampler2D, UNSIGNED_INT_SAMPLER_2D),
-    (UISampler3D, UNSIGNED_INT_SAMPLER_3D),
-    (UISampler1DArray, UNSIGNED_INT_SAMPLER_1D_ARRAY),
-    (UISampler2DArray, UNSIGNED_INT_SAMPLER_2D_ARRAY),
-    (Sampler1D, SAMPLER_1D),
-    (Sampler2D, SAMPLER_2D),
-    (Sampler3D, SAMPLER_3D),
-    (Sampler1DArray, SAMPLER_1D_ARRAY),
-    (Sampler2DArray, SAMPLER_2D_ARRAY),
-    (ICubemap, INT_SAMPLER_CUBE),
-    (UICubemap, UNSIGNED_INT_SAMPLER_CUBE),
-    (Cubemap, SAMPLER_CUBE)
-  )
+// get the UniformType of an OpenGL type, if supported
+fn uniform_type(glty: GLenum) -> Option<UniformType> {
+  UNIFORM_TYPES
+    .iter()
+    .find(|(_, t)| *t == glty)
+    .map(|(ty, _)| *ty)
 }
 
 fn bind_vertex_attribs_locations<Sem>(program: &Program) -> Vec<VertexAttribWarning>
@@ -416,7 +857,14 @@ macro_rules! impl_Uniformable {
         UniformType::$uty
       }
 
-      unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+      unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+        if program.is_uniform_cached(
+          UniformKey::Array(uniform.index(), self.len() as GLsizei),
+          self,
+        ) {
+          return;
+        }
+
         gl::$f(uniform.index(), self.len() as GLsizei, self.as_ptr() as _);
       }
     }
@@ -428,7 +876,14 @@ macro_rules! impl_Uniformable {
         UniformType::$uty
       }
 
-      unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+      unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+        if program.is_uniform_cached(
+          UniformKey::Array(uniform.index(), self.len() as GLsizei),
+          self,
+        ) {
+          return;
+        }
+
         gl::$f(uniform.index(), self.len() as GLsizei, self.as_ptr());
       }
     }
@@ -440,7 +895,11 @@ macro_rules! impl_Uniformable {
         UniformType::$uty
       }
 
-      unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+      unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+        if program.is_uniform_cached(UniformKey::Location(uniform.index()), &self[..]) {
+          return;
+        }
+
         gl::$f(uniform.index(), 1, self.as_ptr());
       }
     }
@@ -452,7 +911,14 @@ macro_rules! impl_Uniformable {
         UniformType::$uty
       }
 
-      unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+      unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+        if program.is_uniform_cached(
+          UniformKey::Location(uniform.index()),
+          slice::from_ref(&self),
+        ) {
+          return;
+        }
+
         gl::$f(uniform.index(), self);
       }
     }
@@ -465,7 +931,14 @@ macro_rules! impl_Uniformable {
         UniformType::$uty
       }
 
-      unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+      unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+        if program.is_uniform_cached(
+          UniformKey::Array(uniform.index(), self.len() as GLsizei),
+          self,
+        ) {
+          return;
+        }
+
         gl::$f(
           uniform.index(),
           self.len() as GLsizei,
@@ -482,7 +955,14 @@ macro_rules! impl_Uniformable {
         UniformType::$uty
       }
 
-      unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+      unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+        if program.is_uniform_cached(
+          UniformKey::Location(uniform.index()),
+          slice::from_ref(&self),
+        ) {
+          return;
+        }
+
         gl::$f(uniform.index(), 1, gl::FALSE, self.as_ptr() as _);
       }
     }
@@ -562,8 +1042,14 @@ unsafe impl Uniformable<GL33> for bool {
     UniformType::Bool
   }
 
-  unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
-    gl::Uniform1ui(uniform.index(), self as u32);
+  unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+    let v = self as u32;
+
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), slice::from_ref(&v)) {
+      return;
+    }
+
+    gl::Uniform1ui(uniform.index(), v);
   }
 }
 
@@ -572,8 +1058,13 @@ unsafe impl Uniformable<GL33> for [bool; 2] {
     UniformType::BVec2
   }
 
-  unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+  unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
     let v = [self[0] as u32, self[1] as u32];
+
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     gl::Uniform2uiv(uniform.index(), 1, v.as_ptr() as _);
   }
 }
@@ -583,8 +1074,13 @@ unsafe impl Uniformable<GL33> for [bool; 3] {
     UniformType::BVec3
   }
 
-  unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+  unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
     let v = [self[0] as u32, self[1] as u32, self[2] as u32];
+
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     gl::Uniform3uiv(uniform.index(), 1, v.as_ptr() as _);
   }
 }
@@ -594,13 +1090,18 @@ unsafe impl Uniformable<GL33> for [bool; 4] {
     UniformType::BVec4
   }
 
-  unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+  unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
     let v = [
       self[0] as u32,
       self[1] as u32,
       self[2] as u32,
       self[3] as u32,
     ];
+
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     gl::Uniform4uiv(uniform.index(), 1, v.as_ptr() as _);
   }
 }
@@ -610,9 +1111,13 @@ unsafe impl<'a> Uniformable<GL33> for &'a [bool] {
     UniformType::Bool
   }
 
-  unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+  unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
     let v: Vec<_> = self.iter().map(|x| *x as u32).collect();
 
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     gl::Uniform1uiv(uniform.index(), v.len() as GLsizei, v.as_ptr() as _);
   }
 }
@@ -622,9 +1127,13 @@ unsafe impl<'a> Uniformable<GL33> for &'a [[bool; 2]] {
     UniformType::BVec2
   }
 
-  unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+  unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
     let v: Vec<_> = self.iter().map(|x| [x[0] as u32, x[1] as u32]).collect();
 
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     gl::Uniform2uiv(uniform.index(), v.len() as GLsizei, v.as_ptr() as _);
   }
 }
@@ -634,12 +1143,16 @@ unsafe impl<'a> Uniformable<GL33> for &'a [[bool; 3]] {
     UniformType::BVec3
   }
 
-  unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+  unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
     let v: Vec<_> = self
       .iter()
       .map(|x| [x[0] as u32, x[1] as u32, x[2] as u32])
       .collect();
 
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     gl::Uniform3uiv(uniform.index(), v.len() as GLsizei, v.as_ptr() as _);
   }
 }
@@ -649,12 +1162,16 @@ unsafe impl<'a> Uniformable<GL33> for &'a [[bool; 4]] {
     UniformType::BVec4
   }
 
-  unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
+  unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
     let v: Vec<_> = self
       .iter()
       .map(|x| [x[0] as u32, x[1] as u32, x[2] as u32, x[3] as u32])
       .collect();
 
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     gl::Uniform4uiv(uniform.index(), v.len() as GLsizei, v.as_ptr() as _);
   }
 }
@@ -665,60 +1182,265 @@ unsafe impl<T> Uniformable<GL33> for BufferBinding<T> {
   }
 
   unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
-    gl::UniformBlockBinding(
-      program.handle,
-      uniform.index() as GLuint,
-      self.binding() as GLuint,
-    )
+    let binding = self.binding() as GLuint;
+
+    if program.is_uniform_cached(
+      UniformKey::Block(uniform.index()),
+      slice::from_ref(&binding),
+    ) {
+      return;
+    }
+
+    gl::UniformBlockBinding(program.handle, uniform.index() as GLuint, binding)
+  }
+}
+
+/// Type of the sampler uniform used to access a texture of dimension `D` with the sampler type `S`.
+pub(crate) fn sampler_uniform_type<D, S>() -> UniformType
+where
+  D: SampledWith<S>,
+  S: SamplerType,
+{
+  if S::depth_comparison() {
+    return match D::dim() {
+      Dim::Dim1 => UniformType::Sampler1DShadow,
+      Dim::Dim2 => UniformType::Sampler2DShadow,
+      // 3D textures don’t implement SampledWith<Shadow>
+      Dim::Dim3 => unreachable!("3D textures have no shadow sampler"),
+      Dim::Cubemap => UniformType::CubemapShadow,
+      Dim::Dim1Array => UniformType::Sampler1DArrayShadow,
+      Dim::Dim2Array => UniformType::Sampler2DArrayShadow,
+      Dim::CubemapArray => UniformType::CubemapArrayShadow,
+    };
+  }
+
+  match (S::sample_type(), D::dim()) {
+    (PixelType::NormIntegral, Dim::Dim1) => UniformType::Sampler1D,
+    (PixelType::NormUnsigned, Dim::Dim1) => UniformType::Sampler1D,
+    (PixelType::Integral, Dim::Dim1) => UniformType::ISampler1D,
+    (PixelType::Unsigned, Dim::Dim1) => UniformType::UISampler1D,
+    (PixelType::Floating, Dim::Dim1) => UniformType::Sampler1D,
+
+    (PixelType::NormIntegral, Dim::Dim2) => UniformType::Sampler2D,
+    (PixelType::NormUnsigned, Dim::Dim2) => UniformType::Sampler2D,
+    (PixelType::Integral, Dim::Dim2) => UniformType::ISampler2D,
+    (PixelType::Unsigned, Dim::Dim2) => UniformType::UISampler2D,
+    (PixelType::Floating, Dim::Dim2) => UniformType::Sampler2D,
+
+    (PixelType::NormIntegral, Dim::Dim3) => UniformType::Sampler3D,
+    (PixelType::NormUnsigned, Dim::Dim3) => UniformType::Sampler3D,
+    (PixelType::Integral, Dim::Dim3) => UniformType::ISampler3D,
+    (PixelType::Unsigned, Dim::Dim3) => UniformType::UISampler3D,
+    (PixelType::Floating, Dim::Dim3) => UniformType::Sampler3D,
+
+    (PixelType::NormIntegral, Dim::Cubemap) => UniformType::Cubemap,
+    (PixelType::NormUnsigned, Dim::Cubemap) => UniformType::Cubemap,
+    (PixelType::Integral, Dim::Cubemap) => UniformType::ICubemap,
+    (PixelType::Unsigned, Dim::Cubemap) => UniformType::UICubemap,
+    (PixelType::Floating, Dim::Cubemap) => UniformType::Cubemap,
+
+    (PixelType::NormIntegral, Dim::Dim1Array) => UniformType::Sampler1DArray,
+    (PixelType::NormUnsigned, Dim::Dim1Array) => UniformType::Sampler1DArray,
+    (PixelType::Integral, Dim::Dim1Array) => UniformType::ISampler1DArray,
+    (PixelType::Unsigned, Dim::Dim1Array) => UniformType::UISampler1DArray,
+    (PixelType::Floating, Dim::Dim1Array) => UniformType::Sampler1DArray,
+
+    (PixelType::NormIntegral, Dim::Dim2Array) => UniformType::Sampler2DArray,
+    (PixelType::NormUnsigned, Dim::Dim2Array) => UniformType::Sampler2DArray,
+    (PixelType::Integral, Dim::Dim2Array) => UniformType::ISampler2DArray,
+    (PixelType::Unsigned, Dim::Dim2Array) => UniformType::UISampler2DArray,
+    (PixelType::Floating, Dim::Dim2Array) => UniformType::Sampler2DArray,
+
+    (PixelType::NormIntegral, Dim::CubemapArray) => UniformType::CubemapArray,
+    (PixelType::NormUnsigned, Dim::CubemapArray) => UniformType::CubemapArray,
+    (PixelType::Integral, Dim::CubemapArray) => UniformType::ICubemapArray,
+    (PixelType::Unsigned, Dim::CubemapArray) => UniformType::UICubemapArray,
+    (PixelType::Floating, Dim::CubemapArray) => UniformType::CubemapArray,
   }
 }
 
 unsafe impl<D, S> Uniformable<GL33> for TextureBinding<D, S>
 where
-  D: Dimensionable,
+  D: SampledWith<S>,
   S: SamplerType,
 {
   unsafe fn ty() -> UniformType {
-    match (S::sample_type(), D::dim()) {
-      (PixelType::NormIntegral, Dim::Dim1) => UniformType::Sampler1D,
-      (PixelType::NormUnsigned, Dim::Dim1) => UniformType::Sampler1D,
-      (PixelType::Integral, Dim::Dim1) => UniformType::ISampler1D,
-      (PixelType::Unsigned, Dim::Dim1) => UniformType::UISampler1D,
-      (PixelType::Floating, Dim::Dim1) => UniformType::Sampler1D,
-
-      (PixelType::NormIntegral, Dim::Dim2) => UniformType::Sampler2D,
-      (PixelType::NormUnsigned, Dim::Dim2) => UniformType::Sampler2D,
-      (PixelType::Integral, Dim::Dim2) => UniformType::ISampler2D,
-      (PixelType::Unsigned, Dim::Dim2) => UniformType::UISampler2D,
-      (PixelType::Floating, Dim::Dim2) => UniformType::Sampler2D,
-
-      (PixelType::NormIntegral, Dim::Dim3) => UniformType::Sampler3D,
-      (PixelType::NormUnsigned, Dim::Dim3) => UniformType::Sampler3D,
-      (PixelType::Integral, Dim::Dim3) => UniformType::ISampler3D,
-      (PixelType::Unsigned, Dim::Dim3) => UniformType::UISampler3D,
-      (PixelType::Floating, Dim::Dim3) => UniformType::Sampler3D,
-
-      (PixelType::NormIntegral, Dim::Cubemap) => UniformType::Cubemap,
-      (PixelType::NormUnsigned, Dim::Cubemap) => UniformType::Cubemap,
-      (PixelType::Integral, Dim::Cubemap) => UniformType::ICubemap,
-      (PixelType::Unsigned, Dim::Cubemap) => UniformType::UICubemap,
-      (PixelType::Floating, Dim::Cubemap) => UniformType::Cubemap,
-
-      (PixelType::NormIntegral, Dim::Dim1Array) => UniformType::Sampler1DArray,
-      (PixelType::NormUnsigned, Dim::Dim1Array) => UniformType::Sampler1DArray,
-      (PixelType::Integral, Dim::Dim1Array) => UniformType::ISampler1DArray,
-      (PixelType::Unsigned, Dim::Dim1Array) => UniformType::UISampler1DArray,
-      (PixelType::Floating, Dim::Dim1Array) => UniformType::Sampler1DArray,
-
-      (PixelType::NormIntegral, Dim::Dim2Array) => UniformType::Sampler2DArray,
-      (PixelType::NormUnsigned, Dim::Dim2Array) => UniformType::Sampler2DArray,
-      (PixelType::Integral, Dim::Dim2Array) => UniformType::ISampler2DArray,
-      (PixelType::Unsigned, Dim::Dim2Array) => UniformType::UISampler2DArray,
-      (PixelType::Floating, Dim::Dim2Array) => UniformType::Sampler2DArray,
-    }
-  }
-
-  unsafe fn update(self, _: &mut Program, uniform: &Uniform<Self>) {
-    gl::Uniform1i(uniform.index(), self.binding() as GLint)
+    sampler_uniform_type::<D, S>()
+  }
+
+  unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+    let binding = self.binding() as GLint;
+
+    if program.is_uniform_cached(
+      UniformKey::Location(uniform.index()),
+      slice::from_ref(&binding),
+    ) {
+      return;
+    }
+
+    gl::Uniform1i(uniform.index(), binding)
+  }
+}
+
+unsafe impl<S> Uniformable<GL33> for BufferTextureBinding<S>
+where
+  S: SamplerType,
+{
+  unsafe fn ty() -> UniformType {
+    match S::sample_type() {
+      PixelType::Integral => UniformType::ISamplerBuffer,
+      PixelType::Unsigned => UniformType::UISamplerBuffer,
+      PixelType::NormIntegral | PixelType::NormUnsigned | PixelType::Floating => {
+        UniformType::SamplerBuffer
+      }
+    }
+  }
+
+  unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+    let binding = self.binding() as GLint;
+
+    if program.is_uniform_cached(
+      UniformKey::Location(uniform.index()),
+      slice::from_ref(&binding),
+    ) {
+      return;
+    }
+
+    gl::Uniform1i(uniform.index(), binding)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  macro_rules! all_uniform_types {
+    ($($variant:ident),*) => {
+      // every uniform type that maps to a GL type; uniform blocks (BufferBinding) don’t
+      const ALL_UNIFORM_TYPES: &[UniformType] = &[$(UniformType::$variant),*];
+
+      // fails to compile when a variant is added to UniformType without being listed above
+      #[allow(dead_code)]
+      fn exhaustive(ty: UniformType) {
+        match ty {
+          UniformType::BufferBinding $(| UniformType::$variant)* => (),
+        }
+      }
+    };
+  }
+
+  all_uniform_types!(
+    Int,
+    UInt,
+    Float,
+    Double,
+    Bool,
+    IVec2,
+    IVec3,
+    IVec4,
+    UIVec2,
+    UIVec3,
+    UIVec4,
+    Vec2,
+    Vec3,
+    Vec4,
+    DVec2,
+    DVec3,
+    DVec4,
+    BVec2,
+    BVec3,
+    BVec4,
+    M22,
+    M33,
+    M44,
+    DM22,
+    DM33,
+    DM44,
+    ISampler1D,
+    ISampler2D,
+    ISampler3D,
+    ISampler1DArray,
+    ISampler2DArray,
+    UISampler1D,
+    UISampler2D,
+    UISampler3D,
+    UISampler1DArray,
+    UISampler2DArray,
+    Sampler1D,
+    Sampler2D,
+    Sampler3D,
+    Sampler1DArray,
+    Sampler2DArray,
+    ICubemap,
+    UICubemap,
+    Cubemap,
+    ICubemapArray,
+    UICubemapArray,
+    CubemapArray,
+    ISamplerBuffer,
+    UISamplerBuffer,
+    SamplerBuffer,
+    Sampler1DShadow,
+    Sampler2DShadow,
+    Sampler1DArrayShadow,
+    Sampler2DArrayShadow,
+    CubemapShadow,
+    CubemapArrayShadow,
+    ISampler2DMS,
+    UISampler2DMS,
+    Sampler2DMS
+  );
+
+  #[test]
+  fn uniform_types_round_trip() {
+    for &ty in ALL_UNIFORM_TYPES {
+      let glty = UNIFORM_TYPES
+        .iter()
+        .find(|&&(t, _)| t == ty)
+        .map(|&(_, glty)| glty);
+
+      assert!(glty.is_some(), "{:?} has no GL type", ty);
+      assert_eq!(uniform_type(glty.unwrap()), Some(ty));
+    }
+
+    assert_eq!(UNIFORM_TYPES.len(), ALL_UNIFORM_TYPES.len());
+  }
+
+  #[test]
+  fn uniform_types_no_duplicate() {
+    for (i, &(ty, glty)) in UNIFORM_TYPES.iter().enumerate() {
+      for &(ty2, glty2) in &UNIFORM_TYPES[i + 1..] {
+        assert_ne!(ty, ty2, "{:?} is listed twice", ty);
+        assert_ne!(glty, glty2, "{:?} and {:?} share the same GL type", ty, ty2);
+      }
+    }
+  }
+
+  #[test]
+  fn array_upload_invalidates_overlapping_uniforms() {
+    let mut program = Program {
+      handle: 0,
+      uniform_cache: Some(UniformCache::default()),
+    };
+
+    unsafe {
+      assert!(!program.is_uniform_cached(UniformKey::Location(1), &[1.]));
+      assert!(!program.is_uniform_cached(UniformKey::Location(4), &[4.]));
+
+      // lights[0..4] at location 0
+      let lights = [0., 2., 0., 0.];
+      assert!(!program.is_uniform_cached(UniformKey::Array(0, 4), &lights));
+      assert!(program.is_uniform_cached(UniformKey::Array(0, 4), &lights));
+
+      // lights[1] was overwritten by the array; location 4 is past its end
+      assert!(!program.is_uniform_cached(UniformKey::Location(1), &[1.]));
+      assert!(program.is_uniform_cached(UniformKey::Location(4), &[4.]));
+
+      // and the array is stale now that lights[1] changed
+      assert!(!program.is_uniform_cached(UniformKey::Array(0, 4), &lights));
+    }
+
+    // the program was never created, so it must not be deleted
+    std::mem::forget(program);
   }
 }
@@ -0,0 +1,64 @@
+//! `GL_ARB_gl_spirv` support.
+//!
+//! The [gl] crate doesn’t expose the entry point needed to create stages from SPIR-V modules, so
+//! it must be loaded separately with [`load_with`], once the OpenGL context is current — typically
+//! right after calling `gl::load_with`:
+//!
+//! ```ignore
+//! luminance_gl::gl33::spirv::load_with(|s| window.get_proc_address(s) as *const _);
+//! ```
+//!
+//! Until then, or if the extension is not supported by the driver, SPIR-V modules are
+//! cross-compiled to GLSL if the `naga` feature of luminance is enabled, and rejected otherwise.
+//!
+//! [gl]: https://crates.io/crates/gl
+
+use gl::types::*;
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+/// `GL_SHADER_BINARY_FORMAT_SPIR_V_ARB`.
+pub(crate) const SHADER_BINARY_FORMAT_SPIR_V: GLenum = 0x9551;
+
+static SPECIALIZE_SHADER: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
+
+pub(crate) unsafe fn specialize_shader(
+  shader: GLuint,
+  entry_point: *const GLchar,
+  num_specialization_constants: GLuint,
+  constant_index: *const GLuint,
+  constant_value: *const GLuint,
+) {
+  let f: extern "system" fn(GLuint, *const GLchar, GLuint, *const GLuint, *const GLuint) =
+    mem::transmute(SPECIALIZE_SHADER.load(Ordering::Relaxed));
+  f(
+    shader,
+    entry_point,
+    num_specialization_constants,
+    constant_index,
+    constant_value,
+  )
+}
+
+/// Load the `GL_ARB_gl_spirv` entry point with the given loader function, which returns the
+/// address of an OpenGL function given its name.
+pub fn load_with<F>(mut loader: F)
+where
+  F: FnMut(&'static str) -> *const c_void,
+{
+  let mut f = loader("glSpecializeShaderARB");
+
+  // OpenGL 4.6 has it in core
+  if f.is_null() {
+    f = loader("glSpecializeShader");
+  }
+
+  SPECIALIZE_SHADER.store(f as *mut c_void, Ordering::Relaxed);
+}
+
+/// Whether the entry point was successfully loaded.
+pub(crate) fn is_loaded() -> bool {
+  !SPECIALIZE_SHADER.load(Ordering::Relaxed).is_null()
+}
@@ -15,6 +15,10 @@ use std::{cell::RefCell, error, ffi::CStr, fmt, marker::PhantomData, os::raw::c_
 // Note: disable on no_std.
 thread_local!(static TLS_ACQUIRE_GFX_STATE: RefCell<Option<()>> = RefCell::new(Some(())));
 
+// GL_EXT_texture_filter_anisotropic symbols, which are not part of the core profile bindings.
+pub(crate) const TEXTURE_MAX_ANISOTROPY: GLenum = 0x84FE;
+const MAX_TEXTURE_MAX_ANISOTROPY: GLenum = 0x84FF;
+
 #[derive(Debug)]
 pub(crate) struct BindingStack {
   pub(crate) next_texture_unit: u32,
@@ -136,6 +140,9 @@ pub struct GLState {
   current_texture_unit: Cached<GLenum>,
   bound_textures: Vec<(GLenum, GLuint)>,
 
+  // sampler objects bound to texture units; 0 means the texture’s own parameters are used
+  bound_samplers: Vec<GLuint>,
+
   // texture buffer used to optimize texture creation; regular textures typically will never ask
   // for fetching from this set but framebuffers, who often generate several textures, might use
   // this opportunity to get N textures (color, depth and stencil) at once, in a single CPU / GPU
@@ -155,6 +162,11 @@ pub struct GLState {
 
   // framebuffer
   bound_draw_framebuffer: Cached<GLuint>,
+  bound_read_framebuffer: Cached<GLuint>,
+
+  // a special framebuffer used to read from textures, e.g. when copying texels between textures
+  // without glCopyImageSubData; it is never created until needed
+  readback_framebuffer: Option<GLuint>,
 
   // vertex array
   bound_vertex_array: GLuint,
@@ -179,6 +191,12 @@ pub struct GLState {
 
   /// Maximum number of elements a texture array can hold.
   max_texture_array_elements: Option<usize>,
+
+  // supported extensions; cached when asked the first time and then re-used
+  extensions: Option<Vec<String>>,
+
+  // maximum anisotropy; cached when asked the first time and then re-used
+  max_texture_max_anisotropy: Option<Option<f32>>,
 }
 
 impl GLState {
@@ -221,11 +239,14 @@ impl GLState {
       let patch_vertex_nb = Cached::new(0);
       let current_texture_unit = Cached::new(get_ctx_current_texture_unit()?);
       let bound_textures = vec![(gl::TEXTURE_2D, 0); 48]; // 48 is the platform minimal requirement
+      let bound_samplers = vec![0; 48]; // same as bound_textures
       let texture_swimming_pool = Vec::new();
       let bound_uniform_buffers = vec![0; 36]; // 36 is the platform minimal requirement
       let bound_array_buffer = 0;
       let bound_element_array_buffer = 0;
       let bound_draw_framebuffer = Cached::new(get_ctx_bound_draw_framebuffer()?);
+      let bound_read_framebuffer = Cached::new(get_ctx_bound_read_framebuffer()?);
+      let readback_framebuffer = None;
       let bound_vertex_array = get_ctx_bound_vertex_array()?;
       let current_program = get_ctx_current_program()?;
       let srgb_framebuffer_enabled = Cached::new(get_ctx_srgb_framebuffer_enabled()?);
@@ -236,6 +257,8 @@ impl GLState {
       let gl_version = None;
       let glsl_version = None;
       let max_texture_array_elements = None;
+      let extensions = None;
+      let max_texture_max_anisotropy = None;
 
       Ok(GLState {
         _a: PhantomData,
@@ -255,11 +278,14 @@ impl GLState {
         patch_vertex_nb,
         current_texture_unit,
         bound_textures,
+        bound_samplers,
         texture_swimming_pool,
         bound_uniform_buffers,
         bound_array_buffer,
         bound_element_array_buffer,
         bound_draw_framebuffer,
+        bound_read_framebuffer,
+        readback_framebuffer,
         bound_vertex_array,
         current_program,
         srgb_framebuffer_enabled,
@@ -270,6 +296,8 @@ impl GLState {
         gl_version,
         glsl_version,
         max_texture_array_elements,
+        extensions,
+        max_texture_max_anisotropy,
       })
     }
   }
@@ -292,6 +320,7 @@ impl GLState {
   /// Invalidate the currently in-use framebuffer.
   pub fn invalidate_framebuffer(&mut self) {
     self.bound_draw_framebuffer.invalidate();
+    self.bound_read_framebuffer.invalidate();
   }
 
   /// Invalidate the currently in-use element array buffer.
@@ -456,6 +485,49 @@ impl GLState {
     })
   }
 
+  /// Check whether an OpenGL extension is supported, e.g. `"GL_EXT_texture_filter_anisotropic"`.
+  ///
+  /// Cache the list of extensions on the first call and then re-use it for later calls.
+  pub fn has_extension(&mut self, name: &str) -> bool {
+    let extensions = self.extensions.get_or_insert_with(|| unsafe {
+      let mut count = 0;
+      gl::GetIntegerv(gl::NUM_EXTENSIONS, &mut count);
+
+      (0..count as GLuint)
+        .map(|i| {
+          let ext_ptr = gl::GetStringi(gl::EXTENSIONS, i);
+          let ext = CStr::from_ptr(ext_ptr as *const c_char);
+          ext.to_string_lossy().into_owned()
+        })
+        .collect()
+    });
+
+    extensions.iter().any(|ext| ext == name)
+  }
+
+  /// Get the maximum anisotropy supported for texture filtering, if anisotropic filtering is
+  /// available at all.
+  ///
+  /// Cache the number on the first call and then re-use it for later calls.
+  pub fn get_max_texture_max_anisotropy(&mut self) -> Option<f32> {
+    if let Some(max) = self.max_texture_max_anisotropy {
+      return max;
+    }
+
+    let max = if self.has_extension("GL_EXT_texture_filter_anisotropic")
+      || self.has_extension("GL_ARB_texture_filter_anisotropic")
+    {
+      let mut max = 0.;
+      unsafe { gl::GetFloatv(MAX_TEXTURE_MAX_ANISOTROPY, &mut max) };
+      Some(max)
+    } else {
+      None
+    };
+
+    self.max_texture_max_anisotropy = Some(max);
+    max
+  }
+
   pub(crate) fn binding_stack_mut(&mut self) -> &mut BindingStack {
     &mut self.binding_stack
   }
@@ -743,6 +815,33 @@ impl GLState {
     }
   }
 
+  /// Bind a sampler object at the provided texture unit. Binding `0` reverts to the bound
+  /// texture’s own sampling parameters.
+  pub(crate) unsafe fn bind_sampler_at(&mut self, handle: GLuint, unit: u32) {
+    let unit = unit as usize;
+
+    if unit >= self.bound_samplers.len() {
+      // not enough registered texture units; let’s grow a bit more
+      self.bound_samplers.resize(unit + 1, 0);
+    }
+
+    if self.bound_samplers[unit] != handle {
+      gl::BindSampler(unit as GLuint, handle);
+      self.bound_samplers[unit] = handle;
+    }
+  }
+
+  /// Forget about a sampler object that is about to be deleted.
+  ///
+  /// OpenGL unbinds deleted sampler objects from all texture units, so the cache must reflect it.
+  pub(crate) fn unbind_sampler(&mut self, handle: GLuint) {
+    for bound in &mut self.bound_samplers {
+      if *bound == handle {
+        *bound = 0;
+      }
+    }
+  }
+
   pub(crate) unsafe fn bind_array_buffer(&mut self, handle: GLuint, bind: Bind) {
     if bind == Bind::Forced || self.bound_array_buffer != handle {
       gl::BindBuffer(gl::ARRAY_BUFFER, handle);
@@ -778,6 +877,21 @@ impl GLState {
     }
   }
 
+  pub(crate) unsafe fn bind_read_framebuffer(&mut self, handle: GLuint) {
+    if self.bound_read_framebuffer.is_invalid(&handle) {
+      gl::BindFramebuffer(gl::READ_FRAMEBUFFER, handle);
+      self.bound_read_framebuffer.set(handle);
+    }
+  }
+
+  pub(crate) fn create_or_get_readback_framebuffer(&mut self) -> GLuint {
+    *self.readback_framebuffer.get_or_insert_with(|| {
+      let mut handle = 0;
+      unsafe { gl::GenFramebuffers(1, &mut handle) };
+      handle
+    })
+  }
+
   pub(crate) unsafe fn bind_vertex_array(&mut self, handle: GLuint, bind: Bind) {
     if bind == Bind::Forced || self.bound_vertex_array != handle {
       gl::BindVertexArray(handle);
@@ -812,6 +926,15 @@ impl GLState {
   }
 }
 
+impl Drop for GLState {
+  fn drop(&mut self) {
+    // drop the readback framebuffer if it was allocated
+    if let Some(handle) = self.readback_framebuffer {
+      unsafe { gl::DeleteFramebuffers(1, &handle) };
+    }
+  }
+}
+
 /// Should the binding be cached or forced to the provided value?
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub(crate) enum Bind {
@@ -1116,6 +1239,12 @@ unsafe fn get_ctx_bound_draw_framebuffer() -> Result<GLuint, StateQueryError> {
   Ok(bound as GLuint)
 }
 
+unsafe fn get_ctx_bound_read_framebuffer() -> Result<GLuint, StateQueryError> {
+  let mut bound = 0 as GLint;
+  gl::GetIntegerv(gl::READ_FRAMEBUFFER_BINDING, &mut bound);
+  Ok(bound as GLuint)
+}
+
 unsafe fn get_ctx_bound_vertex_array() -> Result<GLuint, StateQueryError> {
   let mut bound = 0 as GLint;
   gl::GetIntegerv(gl::VERTEX_ARRAY_BINDING, &mut bound);
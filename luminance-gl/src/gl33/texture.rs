@@ -1,9 +1,12 @@
 use gl;
 use gl::types::*;
-use luminance::backend::texture::{Texture as TextureBackend, TextureBase};
+use luminance::backend::texture::{
+  DynTexture, Texture as TextureBackend, TextureBase, TextureCopy, TextureCopyFramebuffer,
+};
 use luminance::pixel::{Pixel, PixelFormat};
 use luminance::texture::{
-  Dim, Dimensionable, GenMipmaps, MagFilter, MinFilter, Sampler, TextureError, Wrap,
+  Dim, Dimensionable, GenMipmaps, MagFilter, MinFilter, Sampler, SwizzleComponent, TextureError,
+  Wrap,
 };
 use std::cell::RefCell;
 use std::mem;
@@ -11,21 +14,30 @@ use std::os::raw::c_void;
 use std::ptr;
 use std::rc::Rc;
 
+#[cfg(feature = "GL_ARB_bindless_texture")]
+use crate::gl33::bindless::BindlessState;
 use crate::gl33::depth_test::depth_comparison_to_glenum;
 use crate::gl33::pixel::opengl_pixel_format;
-use crate::gl33::state::GLState;
+use crate::gl33::state::{GLState, TEXTURE_MAX_ANISOTROPY};
 use crate::gl33::GL33;
 
 pub struct Texture {
   pub(crate) handle: GLuint, // handle to the GPU texture object
   pub(crate) target: GLenum, // “type” of the texture; used for bindings
   mipmaps: usize,
-  state: Rc<RefCell<GLState>>,
+  pub(crate) state: Rc<RefCell<GLState>>,
+  #[cfg(feature = "GL_ARB_bindless_texture")]
+  pub(crate) bindless: BindlessState,
 }
 
 impl Drop for Texture {
   fn drop(&mut self) {
     unsafe {
+      #[cfg(feature = "GL_ARB_bindless_texture")]
+      if let (Some(handle), true) = (self.bindless.handle, self.bindless.resident) {
+        crate::gl33::bindless::make_texture_handle_non_resident(handle);
+      }
+
       gl::DeleteTextures(1, &self.handle);
     }
   }
@@ -46,24 +58,7 @@ where
     mipmaps: usize,
     sampler: Sampler,
   ) -> Result<Self::TextureRepr, TextureError> {
-    let mipmaps = mipmaps + 1; // + 1 prevent having 0 mipmaps
-    let target = opengl_target(D::dim());
-
-    let mut state = self.state.borrow_mut();
-
-    let handle = state.create_texture();
-    state.bind_texture(target, handle);
-
-    create_texture::<D>(target, size, mipmaps, P::pixel_format(), sampler)?;
-
-    let texture = Texture {
-      handle,
-      target,
-      mipmaps,
-      state: self.state.clone(),
-    };
-
-    Ok(texture)
+    new_texture::<D>(self, size, mipmaps, sampler, P::pixel_format())
   }
 
   unsafe fn mipmaps(texture: &Self::TextureRepr) -> usize {
@@ -106,7 +101,7 @@ where
 
     gfx_state.bind_texture(texture.target, texture.handle);
 
-    upload_texels::<D, P, P::Encoding>(texture.target, offset, size, texels)?;
+    upload_texels::<D, P::Encoding>(texture.target, 0, offset, size, P::pixel_format(), texels)?;
 
     if gen_mipmaps == GenMipmaps::Yes {
       gl::GenerateMipmap(texture.target);
@@ -137,7 +132,7 @@ where
 
     gfx_state.bind_texture(texture.target, texture.handle);
 
-    upload_texels::<D, P, P::RawEncoding>(texture.target, offset, size, texels)?;
+    upload_texels::<D, P::RawEncoding>(texture.target, 0, offset, size, P::pixel_format(), texels)?;
 
     if gen_mipmaps == GenMipmaps::Yes {
       gl::GenerateMipmap(texture.target);
@@ -163,6 +158,60 @@ where
     )
   }
 
+  unsafe fn upload_part_level(
+    texture: &mut Self::TextureRepr,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+    texels: &[P::Encoding],
+  ) -> Result<(), TextureError> {
+    check_mipmap_level(texture, level, TextureError::CannotUploadTexels)?;
+
+    let mut gfx_state = texture.state.borrow_mut();
+
+    gfx_state.bind_texture(texture.target, texture.handle);
+
+    upload_texels::<D, P::Encoding>(
+      texture.target,
+      level as GLint,
+      offset,
+      size,
+      P::pixel_format(),
+      texels,
+    )?;
+
+    gfx_state.bind_texture(texture.target, 0);
+
+    Ok(())
+  }
+
+  unsafe fn upload_part_level_raw(
+    texture: &mut Self::TextureRepr,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+    texels: &[P::RawEncoding],
+  ) -> Result<(), TextureError> {
+    check_mipmap_level(texture, level, TextureError::CannotUploadTexels)?;
+
+    let mut gfx_state = texture.state.borrow_mut();
+
+    gfx_state.bind_texture(texture.target, texture.handle);
+
+    upload_texels::<D, P::RawEncoding>(
+      texture.target,
+      level as GLint,
+      offset,
+      size,
+      P::pixel_format(),
+      texels,
+    )?;
+
+    gfx_state.bind_texture(texture.target, 0);
+
+    Ok(())
+  }
+
   unsafe fn get_raw_texels(
     texture: &Self::TextureRepr,
     _: D::Size,
@@ -188,7 +237,7 @@ where
     set_pack_alignment(skip_bytes);
 
     // resize the vec to allocate enough space to host the returned texels
-    let mut texels = vec![Default::default(); (w * h) as usize * pf.channels_len()];
+    let mut texels = vec![Default::default(); (w * h) as usize * pf.raw_len()];
 
     gl::GetTexImage(
       texture.target,
@@ -203,6 +252,40 @@ where
     Ok(texels)
   }
 
+  unsafe fn get_raw_texels_part(
+    texture: &Self::TextureRepr,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+  ) -> Result<Vec<P::RawEncoding>, TextureError>
+  where
+    P::RawEncoding: Copy + Default,
+  {
+    if level >= texture.mipmaps {
+      return Err(TextureError::cannot_retrieve_texels(format!(
+        "mipmap level {} out of bounds (texture has {} levels)",
+        level, texture.mipmaps
+      )));
+    }
+
+    let pf = P::pixel_format();
+
+    // resize the vec to allocate enough space to host the returned texels
+    let texels_nb = texels_part_len::<D>(size) * pf.raw_len();
+    let mut texels = vec![Default::default(); texels_nb];
+
+    read_texels_part::<D, P>(
+      &mut texture.state.borrow_mut(),
+      texture,
+      level,
+      offset,
+      size,
+      texels.as_mut_ptr() as *mut u8,
+    )?;
+
+    Ok(texels)
+  }
+
   unsafe fn resize(
     texture: &mut Self::TextureRepr,
     size: D::Size,
@@ -211,10 +294,255 @@ where
     let mipmaps = mipmaps + 1; // + 1 to prevent having 0 mipmaps
     let mut state = texture.state.borrow_mut();
     state.bind_texture(texture.target, texture.handle);
-    create_texture_storage::<D>(size, mipmaps, P::pixel_format())
+    create_texture_storage::<D>(size, mipmaps, P::pixel_format())?;
+
+    texture.mipmaps = mipmaps;
+    Ok(())
+  }
+}
+
+unsafe impl<D, P> TextureCopy<D, P> for GL33
+where
+  D: Dimensionable,
+  P: Pixel,
+{
+  unsafe fn copy_texture(
+    dst: &mut Self::TextureRepr,
+    src: &Self::TextureRepr,
+    src_level: usize,
+    src_offset: D::Offset,
+    dst_level: usize,
+    dst_offset: D::Offset,
+    size: D::Size,
+  ) -> Result<(), TextureError> {
+    check_mipmap_level(src, src_level, TextureError::CannotCopyTexels)?;
+    check_mipmap_level(dst, dst_level, TextureError::CannotCopyTexels)?;
+
+    let mut state = dst.state.borrow_mut();
+    let [sx, sy, sz] = texture_offset::<D>(src_offset);
+    let [dx, dy, dz] = texture_offset::<D>(dst_offset);
+    let [w, h, d] = texture_region::<D>(size);
+
+    if state.has_extension("GL_ARB_copy_image") {
+      gl::CopyImageSubData(
+        src.handle,
+        src.target,
+        src_level as GLint,
+        sx,
+        sy,
+        sz,
+        dst.handle,
+        dst.target,
+        dst_level as GLint,
+        dx,
+        dy,
+        dz,
+        w,
+        h,
+        d,
+      );
+
+      return Ok(());
+    }
+
+    // no glCopyImageSubData; attach each source layer to the readback framebuffer and copy from it
+    let attachment = bind_readback_framebuffer(&mut state, P::pixel_format());
+    state.bind_texture(dst.target, dst.handle);
+
+    let src_level = src_level as GLint;
+    let dst_level = dst_level as GLint;
+
+    match D::dim() {
+      Dim::Dim1 => {
+        attach_read_layer::<D>(src, attachment, src_level, 0);
+        gl::CopyTexSubImage1D(dst.target, dst_level, dx, sx, 0, w);
+      }
+
+      Dim::Dim2 => {
+        attach_read_layer::<D>(src, attachment, src_level, 0);
+        gl::CopyTexSubImage2D(dst.target, dst_level, dx, dy, sx, sy, w, h);
+      }
+
+      Dim::Cubemap => {
+        attach_read_layer::<D>(src, attachment, src_level, sz);
+        gl::CopyTexSubImage2D(
+          gl::TEXTURE_CUBE_MAP_POSITIVE_X + dz as GLenum,
+          dst_level,
+          dx,
+          dy,
+          sx,
+          sy,
+          w,
+          h,
+        );
+      }
+
+      // each layer of a 1D array is a row
+      Dim::Dim1Array => {
+        for layer in 0..h {
+          attach_read_layer::<D>(src, attachment, src_level, sy + layer);
+          gl::CopyTexSubImage2D(dst.target, dst_level, dx, dy + layer, sx, 0, w, 1);
+        }
+      }
+
+      Dim::Dim3 | Dim::Dim2Array | Dim::CubemapArray => {
+        for layer in 0..d {
+          attach_read_layer::<D>(src, attachment, src_level, sz + layer);
+          gl::CopyTexSubImage3D(dst.target, dst_level, dx, dy, dz + layer, sx, sy, w, h);
+        }
+      }
+    }
+
+    // detach the source texture so that it doesn’t stay referenced by the readback framebuffer
+    gl::FramebufferTexture(gl::READ_FRAMEBUFFER, attachment, 0, 0);
+
+    Ok(())
+  }
+}
+
+unsafe impl<D, P, FD> TextureCopyFramebuffer<D, P, FD> for GL33
+where
+  D: Dimensionable,
+  P: Pixel,
+  FD: Dimensionable,
+{
+  unsafe fn copy_from_framebuffer(
+    dst: &mut Self::TextureRepr,
+    framebuffer: &Self::FramebufferRepr,
+    attachment_index: usize,
+    src_offset: [u32; 2],
+    dst_level: usize,
+    dst_offset: D::Offset,
+    size: [u32; 2],
+  ) -> Result<(), TextureError> {
+    check_mipmap_level(dst, dst_level, TextureError::CannotCopyTexels)?;
+
+    let read_buffer = if framebuffer.handle == 0 {
+      if attachment_index != 0 {
+        return Err(TextureError::cannot_copy_texels(
+          "back buffers only have a single color attachment",
+        ));
+      }
+
+      gl::BACK
+    } else {
+      gl::COLOR_ATTACHMENT0 + attachment_index as GLenum
+    };
+
+    let mut state = dst.state.borrow_mut();
+    state.bind_read_framebuffer(framebuffer.handle);
+    state.bind_texture(dst.target, dst.handle);
+    gl::ReadBuffer(read_buffer);
+
+    let [sx, sy] = [src_offset[0] as GLint, src_offset[1] as GLint];
+    let [dx, dy, dz] = texture_offset::<D>(dst_offset);
+    let [w, h] = [size[0] as GLsizei, size[1] as GLsizei];
+    let dst_level = dst_level as GLint;
+
+    match D::dim() {
+      Dim::Dim1 => gl::CopyTexSubImage1D(dst.target, dst_level, dx, sx, sy, w),
+      Dim::Dim2 | Dim::Dim1Array => {
+        gl::CopyTexSubImage2D(dst.target, dst_level, dx, dy, sx, sy, w, h)
+      }
+      Dim::Cubemap => gl::CopyTexSubImage2D(
+        gl::TEXTURE_CUBE_MAP_POSITIVE_X + dz as GLenum,
+        dst_level,
+        dx,
+        dy,
+        sx,
+        sy,
+        w,
+        h,
+      ),
+      Dim::Dim3 | Dim::Dim2Array | Dim::CubemapArray => {
+        gl::CopyTexSubImage3D(dst.target, dst_level, dx, dy, dz, sx, sy, w, h)
+      }
+    }
+
+    Ok(())
+  }
+}
+
+unsafe impl<D> DynTexture<D> for GL33
+where
+  D: Dimensionable,
+{
+  unsafe fn new_dyn_texture(
+    &mut self,
+    size: D::Size,
+    mipmaps: usize,
+    sampler: Sampler,
+    pixel_format: PixelFormat,
+  ) -> Result<Self::TextureRepr, TextureError> {
+    new_texture::<D>(self, size, mipmaps, sampler, pixel_format)
+  }
+
+  unsafe fn dyn_mipmaps(texture: &Self::TextureRepr) -> usize {
+    texture.mipmaps
+  }
+
+  unsafe fn upload_dyn_part_raw(
+    texture: &mut Self::TextureRepr,
+    pixel_format: PixelFormat,
+    gen_mipmaps: GenMipmaps,
+    offset: D::Offset,
+    size: D::Size,
+    texels: &[u8],
+  ) -> Result<(), TextureError> {
+    let mut gfx_state = texture.state.borrow_mut();
+
+    gfx_state.bind_texture(texture.target, texture.handle);
+
+    upload_texels::<D, u8>(texture.target, 0, offset, size, pixel_format, texels)?;
+
+    if gen_mipmaps == GenMipmaps::Yes {
+      gl::GenerateMipmap(texture.target);
+    }
+
+    gfx_state.bind_texture(texture.target, 0);
+
+    Ok(())
   }
 }
 
+unsafe fn new_texture<D>(
+  gl33: &mut GL33,
+  size: D::Size,
+  mipmaps: usize,
+  sampler: Sampler,
+  pf: PixelFormat,
+) -> Result<Texture, TextureError>
+where
+  D: Dimensionable,
+{
+  let mipmaps = mipmaps + 1; // + 1 prevent having 0 mipmaps
+  let target = opengl_target(D::dim());
+
+  let mut state = gl33.state.borrow_mut();
+
+  if D::dim() == Dim::CubemapArray && !state.has_extension("GL_ARB_texture_cube_map_array") {
+    return Err(TextureError::texture_storage_creation_failed(
+      "cubemap array textures require GL_ARB_texture_cube_map_array",
+    ));
+  }
+
+  let handle = state.create_texture();
+  state.bind_texture(target, handle);
+
+  create_texture::<D>(&mut state, target, size, mipmaps, pf, sampler)?;
+
+  let texture = Texture {
+    handle,
+    target,
+    mipmaps,
+    state: gl33.state.clone(),
+    #[cfg(feature = "GL_ARB_bindless_texture")]
+    bindless: BindlessState::default(),
+  };
+
+  Ok(texture)
+}
+
 pub(crate) fn opengl_target(d: Dim) -> GLenum {
   match d {
     Dim::Dim1 => gl::TEXTURE_1D,
@@ -223,10 +551,12 @@ pub(crate) fn opengl_target(d: Dim) -> GLenum {
     Dim::Cubemap => gl::TEXTURE_CUBE_MAP,
     Dim::Dim1Array => gl::TEXTURE_1D_ARRAY,
     Dim::Dim2Array => gl::TEXTURE_2D_ARRAY,
+    Dim::CubemapArray => gl::TEXTURE_CUBE_MAP_ARRAY,
   }
 }
 
 pub(crate) unsafe fn create_texture<D>(
+  state: &mut GLState,
   target: GLenum,
   size: D::Size,
   mipmaps: usize,
@@ -237,7 +567,7 @@ where
   D: Dimensionable,
 {
   set_texture_levels(target, mipmaps);
-  apply_sampler_to_texture(target, sampler);
+  apply_sampler(state, SamplerTarget::Texture(target), sampler);
   create_texture_storage::<D>(size, mipmaps, pf)
 }
 
@@ -248,59 +578,143 @@ fn set_texture_levels(target: GLenum, mipmaps: usize) {
   }
 }
 
-fn apply_sampler_to_texture(target: GLenum, sampler: Sampler) {
+/// Object sampling parameters are applied to.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum SamplerTarget {
+  /// A texture, bound to the given target.
+  Texture(GLenum),
+  /// A standalone sampler object.
+  SamplerObject(GLuint),
+}
+
+impl SamplerTarget {
+  unsafe fn parameteri(self, pname: GLenum, param: GLint) {
+    match self {
+      SamplerTarget::Texture(target) => gl::TexParameteri(target, pname, param),
+      SamplerTarget::SamplerObject(handle) => gl::SamplerParameteri(handle, pname, param),
+    }
+  }
+
+  unsafe fn parameterf(self, pname: GLenum, param: GLfloat) {
+    match self {
+      SamplerTarget::Texture(target) => gl::TexParameterf(target, pname, param),
+      SamplerTarget::SamplerObject(handle) => gl::SamplerParameterf(handle, pname, param),
+    }
+  }
+
+  unsafe fn parameterfv(self, pname: GLenum, params: &[GLfloat]) {
+    match self {
+      SamplerTarget::Texture(target) => gl::TexParameterfv(target, pname, params.as_ptr()),
+      SamplerTarget::SamplerObject(handle) => {
+        gl::SamplerParameterfv(handle, pname, params.as_ptr())
+      }
+    }
+  }
+}
+
+pub(crate) fn apply_sampler(state: &mut GLState, target: SamplerTarget, sampler: Sampler) {
+  let mirror_clamp = state.has_extension("GL_ARB_texture_mirror_clamp_to_edge")
+    || state.has_extension("GL_EXT_texture_mirror_clamp");
+
   unsafe {
-    gl::TexParameteri(
-      target,
+    target.parameteri(
       gl::TEXTURE_WRAP_R,
-      opengl_wrap(sampler.wrap_r) as GLint,
+      opengl_wrap(sampler.wrap_r, mirror_clamp) as GLint,
     );
-    gl::TexParameteri(
-      target,
+    target.parameteri(
       gl::TEXTURE_WRAP_S,
-      opengl_wrap(sampler.wrap_s) as GLint,
+      opengl_wrap(sampler.wrap_s, mirror_clamp) as GLint,
     );
-    gl::TexParameteri(
-      target,
+    target.parameteri(
       gl::TEXTURE_WRAP_T,
-      opengl_wrap(sampler.wrap_t) as GLint,
+      opengl_wrap(sampler.wrap_t, mirror_clamp) as GLint,
     );
-    gl::TexParameteri(
-      target,
+    target.parameteri(
       gl::TEXTURE_MIN_FILTER,
       opengl_min_filter(sampler.min_filter) as GLint,
     );
-    gl::TexParameteri(
-      target,
+    target.parameteri(
       gl::TEXTURE_MAG_FILTER,
       opengl_mag_filter(sampler.mag_filter) as GLint,
     );
 
     match sampler.depth_comparison {
       Some(fun) => {
-        gl::TexParameteri(
-          target,
+        target.parameteri(
           gl::TEXTURE_COMPARE_FUNC,
           depth_comparison_to_glenum(fun) as GLint,
         );
-        gl::TexParameteri(
-          target,
+        target.parameteri(
           gl::TEXTURE_COMPARE_MODE,
           gl::COMPARE_REF_TO_TEXTURE as GLint,
         );
       }
       None => {
-        gl::TexParameteri(target, gl::TEXTURE_COMPARE_MODE, gl::NONE as GLint);
+        target.parameteri(gl::TEXTURE_COMPARE_MODE, gl::NONE as GLint);
+      }
+    }
+
+    target.parameterf(gl::TEXTURE_MIN_LOD, sampler.min_lod);
+    target.parameterf(gl::TEXTURE_MAX_LOD, sampler.max_lod);
+    target.parameterf(gl::TEXTURE_LOD_BIAS, sampler.lod_bias);
+    target.parameterfv(gl::TEXTURE_BORDER_COLOR, &sampler.border_color);
+  }
+
+  if let Some(anisotropy) = sampler.max_anisotropy {
+    match state.get_max_texture_max_anisotropy() {
+      Some(max) => unsafe {
+        target.parameterf(TEXTURE_MAX_ANISOTROPY, anisotropy.max(1.).min(max));
+      },
+
+      None => {
+        log::warn!("anisotropic filtering is not supported; ignoring max_anisotropy");
       }
     }
   }
+
+  match target {
+    SamplerTarget::Texture(target) => unsafe {
+      let swizzle = sampler.swizzle;
+      let mask = [
+        opengl_swizzle_component(swizzle.r) as GLint,
+        opengl_swizzle_component(swizzle.g) as GLint,
+        opengl_swizzle_component(swizzle.b) as GLint,
+        opengl_swizzle_component(swizzle.a) as GLint,
+      ];
+
+      gl::TexParameteriv(target, gl::TEXTURE_SWIZZLE_RGBA, mask.as_ptr());
+    },
+
+    SamplerTarget::SamplerObject(_) if !sampler.swizzle.is_identity() => {
+      log::warn!("swizzling is part of the texture state; ignoring swizzle for sampler object");
+    }
+
+    _ => (),
+  }
 }
 
-fn opengl_wrap(wrap: Wrap) -> GLenum {
+fn opengl_swizzle_component(component: SwizzleComponent) -> GLenum {
+  match component {
+    SwizzleComponent::Red => gl::RED,
+    SwizzleComponent::Green => gl::GREEN,
+    SwizzleComponent::Blue => gl::BLUE,
+    SwizzleComponent::Alpha => gl::ALPHA,
+    SwizzleComponent::Zero => gl::ZERO,
+    SwizzleComponent::One => gl::ONE,
+  }
+}
+
+fn opengl_wrap(wrap: Wrap, mirror_clamp: bool) -> GLenum {
   match wrap {
     Wrap::ClampToEdge => gl::CLAMP_TO_EDGE,
     Wrap::Repeat => gl::REPEAT,
     Wrap::MirroredRepeat => gl::MIRRORED_REPEAT,
+    Wrap::ClampToBorder => gl::CLAMP_TO_BORDER,
+    Wrap::MirrorClampToEdge if mirror_clamp => gl::MIRROR_CLAMP_TO_EDGE,
+    Wrap::MirrorClampToEdge => {
+      log::warn!("mirror clamp to edge wrapping is not supported; using clamp to edge instead");
+      gl::CLAMP_TO_EDGE
+    }
   }
 }
 
@@ -404,6 +818,19 @@ where
           );
           Ok(())
         }
+
+        // cubemap array texture
+        Dim::CubemapArray => {
+          create_cubemap_array_storage(
+            format,
+            iformat,
+            encoding,
+            D::width(size),
+            D::depth(size),
+            mipmaps,
+          );
+          Ok(())
+        }
       }
     }
 
@@ -530,6 +957,35 @@ fn create_cubemap_storage(
   }
 }
 
+// layer-faces are not mipmapped: only the face size shrinks with the level
+fn create_cubemap_array_storage(
+  format: GLenum,
+  iformat: GLenum,
+  encoding: GLenum,
+  s: u32,
+  layer_faces: u32,
+  mipmaps: usize,
+) {
+  for level in 0..mipmaps {
+    let s = s / (1 << level as u32);
+
+    unsafe {
+      gl::TexImage3D(
+        gl::TEXTURE_CUBE_MAP_ARRAY,
+        level as GLint,
+        iformat as GLint,
+        s as GLsizei,
+        s as GLsizei,
+        layer_faces as GLsizei,
+        0,
+        format,
+        encoding,
+        ptr::null(),
+      )
+    };
+  }
+}
+
 // set the unpack alignment for uploading aligned texels
 fn set_unpack_alignment(skip_bytes: usize) {
   let unpack_alignment = match skip_bytes {
@@ -543,7 +999,7 @@ fn set_unpack_alignment(skip_bytes: usize) {
 }
 
 // set the pack alignment for downloading aligned texels
-fn set_pack_alignment(skip_bytes: usize) {
+pub(crate) fn set_pack_alignment(skip_bytes: usize) {
   let pack_alignment = match skip_bytes {
     0 => 8,
     2 => 2,
@@ -555,19 +1011,19 @@ fn set_pack_alignment(skip_bytes: usize) {
 }
 
 // Upload texels into the texture’s memory. Becareful of the type of texels you send down.
-fn upload_texels<D, P, T>(
+fn upload_texels<D, T>(
   target: GLenum,
+  level: GLint,
   off: D::Offset,
   size: D::Size,
+  pf: PixelFormat,
   texels: &[T],
 ) -> Result<(), TextureError>
 where
   D: Dimensionable,
-  P: Pixel,
 {
   // number of bytes in the input texels argument
   let input_bytes = texels.len() * mem::size_of::<T>();
-  let pf = P::pixel_format();
   let pf_size = pf.format.bytes_len();
   let expected_bytes = D::count(size) * pf_size;
 
@@ -587,7 +1043,7 @@ where
       Dim::Dim1 => unsafe {
         gl::TexSubImage1D(
           target,
-          0,
+          level,
           D::x_offset(off) as GLint,
           D::width(size) as GLsizei,
           format,
@@ -599,7 +1055,7 @@ where
       Dim::Dim2 => unsafe {
         gl::TexSubImage2D(
           target,
-          0,
+          level,
           D::x_offset(off) as GLint,
           D::y_offset(off) as GLint,
           D::width(size) as GLsizei,
@@ -613,7 +1069,7 @@ where
       Dim::Dim3 => unsafe {
         gl::TexSubImage3D(
           target,
-          0,
+          level,
           D::x_offset(off) as GLint,
           D::y_offset(off) as GLint,
           D::z_offset(off) as GLint,
@@ -629,7 +1085,7 @@ where
       Dim::Cubemap => unsafe {
         gl::TexSubImage2D(
           gl::TEXTURE_CUBE_MAP_POSITIVE_X + D::z_offset(off),
-          0,
+          level,
           D::x_offset(off) as GLint,
           D::y_offset(off) as GLint,
           D::width(size) as GLsizei,
@@ -643,7 +1099,7 @@ where
       Dim::Dim1Array => unsafe {
         gl::TexSubImage2D(
           target,
-          0,
+          level,
           D::x_offset(off) as GLint,
           D::y_offset(off) as GLint,
           D::width(size) as GLsizei,
@@ -654,10 +1110,10 @@ where
         );
       },
 
-      Dim::Dim2Array => unsafe {
+      Dim::Dim2Array | Dim::CubemapArray => unsafe {
         gl::TexSubImage3D(
           target,
-          0,
+          level,
           D::x_offset(off) as GLint,
           D::y_offset(off) as GLint,
           D::z_offset(off) as GLint,
@@ -684,3 +1140,188 @@ where
 {
   D::width(size) * D::height(size) * D::depth(size)
 }
+
+/// Number of texels in a region of a texture.
+pub(crate) fn texels_part_len<D>(size: D::Size) -> usize
+where
+  D: Dimensionable,
+{
+  let [w, h, d] = texture_region::<D>(size);
+  (w * h * d) as usize
+}
+
+/// Read a region of a texture’s mipmap `level` into `dst`.
+///
+/// `dst` must be large enough to hold [`texels_part_len`] texels of `texel_bytes` bytes each. If a
+/// buffer is bound to `GL_PIXEL_PACK_BUFFER`, `dst` is interpreted as a byte offset into that
+/// buffer instead.
+pub(crate) unsafe fn read_texels_part<D, P>(
+  state: &mut GLState,
+  texture: &Texture,
+  level: usize,
+  offset: D::Offset,
+  size: D::Size,
+  dst: *mut u8,
+) -> Result<(), TextureError>
+where
+  D: Dimensionable,
+  P: Pixel,
+{
+  check_mipmap_level(texture, level, TextureError::CannotRetrieveTexels)?;
+
+  let pf = P::pixel_format();
+  let texel_bytes = pf.raw_len() * mem::size_of::<P::RawEncoding>();
+
+  let (format, _, ty) = opengl_pixel_format(pf).ok_or(TextureError::UnsupportedPixelFormat(pf))?;
+
+  let [x, y, z] = texture_offset::<D>(offset);
+  let [w, h, d] = texture_region::<D>(size);
+  let level = level as GLint;
+
+  // set the packing alignment based on the number of bytes to skip
+  let skip_bytes = (pf.format.bytes_len() * w as usize) % 8;
+  set_pack_alignment(skip_bytes);
+
+  if state.has_extension("GL_ARB_get_texture_sub_image") {
+    gl::GetTextureSubImage(
+      texture.handle,
+      level,
+      x,
+      y,
+      z,
+      w,
+      h,
+      d,
+      format,
+      ty,
+      ((w * h * d) as usize * texel_bytes) as GLsizei,
+      dst as *mut c_void,
+    );
+
+    return Ok(());
+  }
+
+  // no glGetTextureSubImage; attach each layer to the readback framebuffer and read from it
+  let attachment = bind_readback_framebuffer(state, pf);
+
+  // (rows per layer, number of layers, first layer, first row)
+  let (rows, layers, first_layer, first_row) = match D::dim() {
+    Dim::Dim1 | Dim::Dim2 => (h, 1, 0, y),
+    Dim::Cubemap => (h, 1, z, y),
+    // each layer of a 1D array is a row
+    Dim::Dim1Array => (1, h, y, 0),
+    Dim::Dim3 | Dim::Dim2Array | Dim::CubemapArray => (h, d, z, y),
+  };
+  let layer_bytes = (w * rows) as usize * texel_bytes;
+
+  for layer in 0..layers {
+    attach_read_layer::<D>(texture, attachment, level, first_layer + layer);
+    gl::ReadPixels(
+      x,
+      first_row,
+      w,
+      rows,
+      format,
+      ty,
+      dst.wrapping_add(layer as usize * layer_bytes) as *mut c_void,
+    );
+  }
+
+  // detach the texture so that it doesn’t stay referenced by the readback framebuffer
+  gl::FramebufferTexture(gl::READ_FRAMEBUFFER, attachment, 0, 0);
+
+  Ok(())
+}
+
+/// Bind the readback framebuffer as read framebuffer and return the attachment point textures
+/// with the given pixel format must be attached to.
+unsafe fn bind_readback_framebuffer(state: &mut GLState, pf: PixelFormat) -> GLenum {
+  let readback_fb = state.create_or_get_readback_framebuffer();
+  state.bind_read_framebuffer(readback_fb);
+
+  if pf.is_depth_pixel() {
+    gl::DEPTH_ATTACHMENT
+  } else {
+    gl::ReadBuffer(gl::COLOR_ATTACHMENT0);
+    gl::COLOR_ATTACHMENT0
+  }
+}
+
+/// Attach a single layer of a texture’s mipmap `level` to the read framebuffer.
+///
+/// `layer` is ignored for textures without layers, is the face index for cubemaps and the
+/// layer-face index for cubemap arrays.
+unsafe fn attach_read_layer<D>(texture: &Texture, attachment: GLenum, level: GLint, layer: GLint)
+where
+  D: Dimensionable,
+{
+  match D::dim() {
+    Dim::Dim1 | Dim::Dim2 => {
+      gl::FramebufferTexture(gl::READ_FRAMEBUFFER, attachment, texture.handle, level)
+    }
+
+    Dim::Cubemap => gl::FramebufferTexture2D(
+      gl::READ_FRAMEBUFFER,
+      attachment,
+      gl::TEXTURE_CUBE_MAP_POSITIVE_X + layer as GLenum,
+      texture.handle,
+      level,
+    ),
+
+    Dim::Dim1Array | Dim::Dim3 | Dim::Dim2Array | Dim::CubemapArray => gl::FramebufferTextureLayer(
+      gl::READ_FRAMEBUFFER,
+      attachment,
+      texture.handle,
+      level,
+      layer,
+    ),
+  }
+}
+
+fn check_mipmap_level(
+  texture: &Texture,
+  level: usize,
+  error: fn(String) -> TextureError,
+) -> Result<(), TextureError> {
+  if level < texture.mipmaps {
+    Ok(())
+  } else {
+    Err(error(format!(
+      "mipmap level {} out of bounds (texture has {} levels)",
+      level, texture.mipmaps
+    )))
+  }
+}
+
+/// Texel offset as (x, y, z), where y and z might be layers or cubemap faces, as expected by
+/// copy functions.
+fn texture_offset<D>(off: D::Offset) -> [GLint; 3]
+where
+  D: Dimensionable,
+{
+  let x = D::x_offset(off) as GLint;
+
+  match D::dim() {
+    Dim::Dim1 => [x, 0, 0],
+    Dim::Dim2 | Dim::Dim1Array => [x, D::y_offset(off) as GLint, 0],
+    Dim::Dim3 | Dim::Cubemap | Dim::Dim2Array | Dim::CubemapArray => {
+      [x, D::y_offset(off) as GLint, D::z_offset(off) as GLint]
+    }
+  }
+}
+
+/// Region size as (width, height, depth), as expected by copy functions. Cubemap regions span a
+/// single face; cubemap array regions span whole layers (six layer-faces each).
+fn texture_region<D>(size: D::Size) -> [GLsizei; 3]
+where
+  D: Dimensionable,
+{
+  let w = D::width(size) as GLsizei;
+  let h = D::height(size) as GLsizei;
+
+  match D::dim() {
+    Dim::Dim1 => [w, 1, 1],
+    Dim::Dim2 | Dim::Dim1Array | Dim::Cubemap => [w, h, 1],
+    Dim::Dim3 | Dim::Dim2Array | Dim::CubemapArray => [w, h, D::depth(size) as GLsizei],
+  }
+}
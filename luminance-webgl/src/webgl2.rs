@@ -6,6 +6,8 @@ pub mod framebuffer;
 pub mod pipeline;
 pub mod pixel;
 pub mod query;
+pub mod readback;
+pub mod sampler;
 pub mod shader;
 pub mod state;
 pub mod tess;
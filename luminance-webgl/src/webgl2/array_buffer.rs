@@ -25,16 +25,23 @@ macro_rules! impl_IntoArrayBuffer {
       }
     }
 
-    impl_tuple_IntoArrayBuffer!($t, ($t, $t), 2, $buffer);
-    impl_tuple_IntoArrayBuffer!($t, ($t, $t, $t), 3, $buffer);
-    impl_tuple_IntoArrayBuffer!($t, ($t, $t, $t, $t), 4, $buffer);
+    impl_aggregates_IntoArrayBuffer!($t);
+  };
+}
 
-    impl_array_IntoArrayBuffer!($t, $buffer);
+// implement IntoArrayBuffer for tuples and arrays of a type already implementing it
+macro_rules! impl_aggregates_IntoArrayBuffer {
+  ($t:ty) => {
+    impl_tuple_IntoArrayBuffer!($t, ($t, $t), 2);
+    impl_tuple_IntoArrayBuffer!($t, ($t, $t, $t), 3);
+    impl_tuple_IntoArrayBuffer!($t, ($t, $t, $t, $t), 4);
+
+    impl_array_IntoArrayBuffer!($t);
   };
 }
 
 macro_rules! impl_tuple_IntoArrayBuffer {
-  ($t:ty, $tuple:ty, $n:literal, $buffer:ty) => {
+  ($t:ty, $tuple:ty, $n:literal) => {
     // statically assert that [T; 3] has the same size as (T, T, T)
     // this checks that the from_raw_parts cast has the correct value for $n and $tuple
     const _: fn() = || {
@@ -46,20 +53,20 @@ macro_rules! impl_tuple_IntoArrayBuffer {
         let slice: &[$t] =
           std::slice::from_raw_parts(texels.as_ptr() as *const $t, texels.len() * $n);
 
-        <$buffer>::view(slice).into()
+        <$t>::into_array_buffer(slice)
       }
     }
   };
 }
 
 macro_rules! impl_array_IntoArrayBuffer {
-  ($t:ty, $buffer:ty) => {
+  ($t:ty) => {
     impl<const N: usize> IntoArrayBuffer for [$t; N] {
       unsafe fn into_array_buffer(texels: &[Self]) -> js_sys::Object {
         let slice: &[$t] =
           std::slice::from_raw_parts(texels.as_ptr() as *const $t, texels.len() * N);
 
-        <$buffer>::view(slice).into()
+        <$t>::into_array_buffer(slice)
       }
     }
   };
@@ -74,3 +81,15 @@ impl_IntoArrayBuffer!(i32, js_sys::Int32Array);
 
 impl_IntoArrayBuffer!(f32, js_sys::Float32Array);
 impl_IntoArrayBuffer!(f64, js_sys::Float64Array);
+
+// half-precision floats have no JavaScript array type; they are passed as their bit patterns
+#[cfg(feature = "half")]
+impl IntoArrayBuffer for half::f16 {
+  unsafe fn into_array_buffer(texels: &[Self]) -> js_sys::Object {
+    let bits: &[u16] = std::slice::from_raw_parts(texels.as_ptr() as *const u16, texels.len());
+    js_sys::Uint16Array::view(bits).into()
+  }
+}
+
+#[cfg(feature = "half")]
+impl_aggregates_IntoArrayBuffer!(half::f16);
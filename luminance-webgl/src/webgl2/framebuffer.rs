@@ -21,7 +21,7 @@ where
   pub(crate) handle: Option<WebGlFramebuffer>,
   renderbuffer: Option<WebGlRenderbuffer>,
   pub(crate) size: D::Size,
-  state: Rc<RefCell<WebGL2State>>,
+  pub(crate) state: Rc<RefCell<WebGL2State>>,
 }
 
 impl<D> Drop for Framebuffer<D>
@@ -1,6 +1,9 @@
 //! Pipeline support for WebGL2.
 
-use luminance::backend::pipeline::{Pipeline as PipelineBackend, PipelineBase, PipelineTexture};
+use luminance::backend::pipeline::{
+  Pipeline as PipelineBackend, PipelineBase, PipelineDynTexture, PipelineTexture,
+  PipelineTextureSampler,
+};
 use luminance::backend::render_gate::RenderGate;
 use luminance::backend::shading_gate::ShadingGate;
 use luminance::backend::tess::Tess;
@@ -52,6 +55,23 @@ where
   }
 }
 
+pub struct BoundDynTexture {
+  pub(crate) unit: u32,
+  state: Rc<RefCell<WebGL2State>>,
+}
+
+impl Drop for BoundDynTexture {
+  fn drop(&mut self) {
+    // place the binding into the free list
+    self
+      .state
+      .borrow_mut()
+      .binding_stack_mut()
+      .free_texture_units
+      .push(self.unit);
+  }
+}
+
 unsafe impl PipelineBase for WebGL2 {
   type PipelineRepr = Pipeline;
 
@@ -142,17 +162,12 @@ where
     P: Pixel,
   {
     let mut state = pipeline.state.borrow_mut();
-    let bstack = state.binding_stack_mut();
-
-    let unit = bstack.free_texture_units.pop().unwrap_or_else(|| {
-      // no more free units; reserve one
-      let unit = bstack.next_texture_unit;
-      bstack.next_texture_unit += 1;
-      unit
-    });
+    let unit = reserve_texture_unit(&mut state);
 
     state.set_texture_unit(unit);
     state.bind_texture(texture.target, Some(texture.handle()));
+    // a sampler object might still be bound to that unit from a previous binding
+    state.bind_sampler_at(None, unit);
 
     Ok(BoundTexture {
       unit,
@@ -166,6 +181,74 @@ where
   }
 }
 
+unsafe impl<D, P> PipelineTextureSampler<D, P> for WebGL2
+where
+  D: Dimensionable,
+  P: Pixel,
+  P::Encoding: IntoArrayBuffer,
+  P::RawEncoding: IntoArrayBuffer,
+{
+  unsafe fn bind_texture_with_sampler(
+    pipeline: &Self::PipelineRepr,
+    texture: &Self::TextureRepr,
+    sampler: &Self::SamplerObjectRepr,
+  ) -> Result<Self::BoundTextureRepr, PipelineError> {
+    let mut state = pipeline.state.borrow_mut();
+    let unit = reserve_texture_unit(&mut state);
+
+    state.set_texture_unit(unit);
+    state.bind_texture(texture.target, Some(texture.handle()));
+    state.bind_sampler_at(Some(&sampler.handle), unit);
+
+    Ok(BoundTexture {
+      unit,
+      state: pipeline.state.clone(),
+      _phantom: PhantomData,
+    })
+  }
+}
+
+/// Get a free texture unit, reserving a new one if none is available.
+fn reserve_texture_unit(state: &mut WebGL2State) -> u32 {
+  let bstack = state.binding_stack_mut();
+
+  bstack.free_texture_units.pop().unwrap_or_else(|| {
+    // no more free units; reserve one
+    let unit = bstack.next_texture_unit;
+    bstack.next_texture_unit += 1;
+    unit
+  })
+}
+
+unsafe impl<D> PipelineDynTexture<D> for WebGL2
+where
+  D: Dimensionable,
+{
+  type BoundDynTextureRepr = BoundDynTexture;
+
+  unsafe fn bind_dyn_texture(
+    pipeline: &Self::PipelineRepr,
+    texture: &Self::TextureRepr,
+  ) -> Result<Self::BoundDynTextureRepr, PipelineError> {
+    let mut state = pipeline.state.borrow_mut();
+    let unit = reserve_texture_unit(&mut state);
+
+    state.set_texture_unit(unit);
+    state.bind_texture(texture.target, Some(texture.handle()));
+    // a sampler object might still be bound to that unit from a previous binding
+    state.bind_sampler_at(None, unit);
+
+    Ok(BoundDynTexture {
+      unit,
+      state: pipeline.state.clone(),
+    })
+  }
+
+  unsafe fn dyn_texture_binding(bound: &Self::BoundDynTextureRepr) -> u32 {
+    bound.unit
+  }
+}
+
 unsafe impl<V, I, W> TessGate<V, I, W, Interleaved> for WebGL2
 where
   V: TessVertexData<Interleaved, Data = Vec<V>>,
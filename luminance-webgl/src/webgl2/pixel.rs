@@ -36,6 +36,11 @@ pub(crate) fn webgl_pixel_format(pf: PixelFormat) -> Option<(u32, u32, u32)> {
       WebGl2RenderingContext::R16UI,
       WebGl2RenderingContext::UNSIGNED_SHORT,
     )),
+    (Format::R(Size::Sixteen), Type::Floating) => Some((
+      WebGl2RenderingContext::RED,
+      WebGl2RenderingContext::R16F,
+      WebGl2RenderingContext::HALF_FLOAT,
+    )),
 
     (Format::R(Size::ThirtyTwo), Type::NormUnsigned) => Some((
       WebGl2RenderingContext::RED_INTEGER,
@@ -95,6 +100,11 @@ pub(crate) fn webgl_pixel_format(pf: PixelFormat) -> Option<(u32, u32, u32)> {
       WebGl2RenderingContext::RG16UI,
       WebGl2RenderingContext::UNSIGNED_SHORT,
     )),
+    (Format::RG(Size::Sixteen, Size::Sixteen), Type::Floating) => Some((
+      WebGl2RenderingContext::RG,
+      WebGl2RenderingContext::RG16F,
+      WebGl2RenderingContext::HALF_FLOAT,
+    )),
 
     (Format::RG(Size::ThirtyTwo, Size::ThirtyTwo), Type::NormUnsigned) => Some((
       WebGl2RenderingContext::RG,
@@ -154,6 +164,11 @@ pub(crate) fn webgl_pixel_format(pf: PixelFormat) -> Option<(u32, u32, u32)> {
       WebGl2RenderingContext::RGB16UI,
       WebGl2RenderingContext::UNSIGNED_SHORT,
     )),
+    (Format::RGB(Size::Sixteen, Size::Sixteen, Size::Sixteen), Type::Floating) => Some((
+      WebGl2RenderingContext::RGB,
+      WebGl2RenderingContext::RGB16F,
+      WebGl2RenderingContext::HALF_FLOAT,
+    )),
 
     (Format::RGB(Size::Eleven, Size::Eleven, Size::Ten), Type::Floating) => Some((
       WebGl2RenderingContext::RGB,
@@ -227,6 +242,13 @@ pub(crate) fn webgl_pixel_format(pf: PixelFormat) -> Option<(u32, u32, u32)> {
         WebGl2RenderingContext::UNSIGNED_SHORT,
       ))
     }
+    (Format::RGBA(Size::Sixteen, Size::Sixteen, Size::Sixteen, Size::Sixteen), Type::Floating) => {
+      Some((
+        WebGl2RenderingContext::RGBA,
+        WebGl2RenderingContext::RGBA16F,
+        WebGl2RenderingContext::HALF_FLOAT,
+      ))
+    }
 
     (
       Format::RGBA(Size::ThirtyTwo, Size::ThirtyTwo, Size::ThirtyTwo, Size::ThirtyTwo),
@@ -269,6 +291,23 @@ pub(crate) fn webgl_pixel_format(pf: PixelFormat) -> Option<(u32, u32, u32)> {
       WebGl2RenderingContext::FLOAT,
     )),
 
+    // packed formats
+    (Format::RGBA(Size::Ten, Size::Ten, Size::Ten, Size::Two), Type::NormUnsigned) => Some((
+      WebGl2RenderingContext::RGBA,
+      WebGl2RenderingContext::RGB10_A2,
+      WebGl2RenderingContext::UNSIGNED_INT_2_10_10_10_REV,
+    )),
+    (Format::RGBA(Size::Ten, Size::Ten, Size::Ten, Size::Two), Type::Unsigned) => Some((
+      WebGl2RenderingContext::RGBA_INTEGER,
+      WebGl2RenderingContext::RGB10_A2UI,
+      WebGl2RenderingContext::UNSIGNED_INT_2_10_10_10_REV,
+    )),
+    (Format::RGBE(Size::Nine, Size::Nine, Size::Nine, Size::Five), Type::Floating) => Some((
+      WebGl2RenderingContext::RGB,
+      WebGl2RenderingContext::RGB9_E5,
+      WebGl2RenderingContext::UNSIGNED_INT_5_9_9_9_REV,
+    )),
+
     // sRGB
     (Format::SRGB(Size::Eight, Size::Eight, Size::Eight), Type::NormUnsigned) => Some((
       WebGl2RenderingContext::RGB,
@@ -295,6 +334,16 @@ pub(crate) fn webgl_pixel_format(pf: PixelFormat) -> Option<(u32, u32, u32)> {
       ))
     }
 
+    (Format::Depth(Size::Sixteen), Type::NormUnsigned) => Some((
+      WebGl2RenderingContext::DEPTH_COMPONENT,
+      WebGl2RenderingContext::DEPTH_COMPONENT16,
+      WebGl2RenderingContext::UNSIGNED_SHORT,
+    )),
+    (Format::Depth(Size::TwentyFour), Type::NormUnsigned) => Some((
+      WebGl2RenderingContext::DEPTH_COMPONENT,
+      WebGl2RenderingContext::DEPTH_COMPONENT24,
+      WebGl2RenderingContext::UNSIGNED_INT,
+    )),
     (Format::Depth(Size::ThirtyTwo), Type::Floating) => Some((
       WebGl2RenderingContext::DEPTH_COMPONENT,
       WebGl2RenderingContext::DEPTH_COMPONENT32F,
@@ -0,0 +1,260 @@
+//! Asynchronous texel readback support for WebGL2.
+//!
+//! Texels are read into a pixel buffer object (PBO) and a fence sync is inserted right after, so
+//! that we know when the GPU is done with the transfer without stalling it.
+
+use luminance::backend::texture::{
+  FramebufferReadback, PendingReadback as PendingReadbackBackend, PendingReadbackBase,
+  TextureReadback,
+};
+use luminance::pixel::Pixel;
+use luminance::texture::{Dimensionable, TextureError};
+use std::cell::RefCell;
+use std::mem;
+use std::rc::Rc;
+use std::slice;
+use web_sys::{WebGl2RenderingContext, WebGlBuffer, WebGlSync};
+
+use crate::webgl2::array_buffer::IntoArrayBuffer;
+use crate::webgl2::pixel::webgl_pixel_format;
+use crate::webgl2::state::WebGL2State;
+use crate::webgl2::texture::{
+  read_pixels, read_texels_part, set_pack_alignment, texels_part_len, ReadPixelsDst,
+};
+use crate::webgl2::WebGL2;
+
+pub struct PendingReadback {
+  buffer: WebGlBuffer,
+  sync: WebGlSync,
+  bytes_len: usize,
+  state: Rc<RefCell<WebGL2State>>,
+}
+
+impl Drop for PendingReadback {
+  fn drop(&mut self) {
+    let state = self.state.borrow();
+    state.ctx.delete_sync(Some(&self.sync));
+    state.ctx.delete_buffer(Some(&self.buffer));
+  }
+}
+
+impl PendingReadback {
+  /// Create a PBO of `bytes_len` bytes, bind it as pixel pack buffer and run `read` to start the
+  /// transfer.
+  fn new<F>(
+    state: &Rc<RefCell<WebGL2State>>,
+    bytes_len: usize,
+    read: F,
+  ) -> Result<Self, TextureError>
+  where
+    F: FnOnce(&mut WebGL2State) -> Result<(), TextureError>,
+  {
+    let mut gfx_state = state.borrow_mut();
+
+    let buffer = gfx_state
+      .create_buffer()
+      .ok_or_else(|| TextureError::cannot_retrieve_texels("cannot create the readback buffer"))?;
+    gfx_state
+      .ctx
+      .bind_buffer(WebGl2RenderingContext::PIXEL_PACK_BUFFER, Some(&buffer));
+    gfx_state.ctx.buffer_data_with_i32(
+      WebGl2RenderingContext::PIXEL_PACK_BUFFER,
+      bytes_len as i32,
+      WebGl2RenderingContext::STREAM_READ,
+    );
+
+    let r = read(&mut gfx_state);
+
+    // the rest of the backend expects reads to go to client memory
+    gfx_state
+      .ctx
+      .bind_buffer(WebGl2RenderingContext::PIXEL_PACK_BUFFER, None);
+
+    let sync = r.and_then(|_| {
+      gfx_state
+        .ctx
+        .fence_sync(WebGl2RenderingContext::SYNC_GPU_COMMANDS_COMPLETE, 0)
+        .ok_or_else(|| TextureError::cannot_retrieve_texels("cannot create the readback fence"))
+    });
+
+    let sync = match sync {
+      Ok(sync) => sync,
+      Err(e) => {
+        gfx_state.ctx.delete_buffer(Some(&buffer));
+        return Err(e);
+      }
+    };
+
+    // make sure the fence eventually gets signaled, even if nothing else is submitted
+    gfx_state.ctx.flush();
+
+    Ok(PendingReadback {
+      buffer,
+      sync,
+      bytes_len,
+      state: state.clone(),
+    })
+  }
+
+  /// Check whether the transfer is complete, without blocking.
+  ///
+  /// WebGL2 doesn’t allow blocking on fences; they only get signaled once the control has been
+  /// given back to the browser.
+  fn is_complete(&self) -> Result<bool, TextureError> {
+    let status = self.state.borrow().ctx.client_wait_sync_with_u32(
+      &self.sync,
+      WebGl2RenderingContext::SYNC_FLUSH_COMMANDS_BIT,
+      0,
+    );
+
+    match status {
+      WebGl2RenderingContext::ALREADY_SIGNALED | WebGl2RenderingContext::CONDITION_SATISFIED => {
+        Ok(true)
+      }
+      WebGl2RenderingContext::TIMEOUT_EXPIRED => Ok(false),
+      _ => Err(TextureError::cannot_retrieve_texels(
+        "failed to wait for the readback fence",
+      )),
+    }
+  }
+
+  unsafe fn texels<T>(&self) -> Vec<T>
+  where
+    T: Copy + Default,
+  {
+    let mut texels = vec![T::default(); self.bytes_len / mem::size_of::<T>()];
+    let bytes = slice::from_raw_parts_mut(texels.as_mut_ptr() as *mut u8, self.bytes_len);
+
+    let state = self.state.borrow();
+    state
+      .ctx
+      .bind_buffer(WebGl2RenderingContext::COPY_READ_BUFFER, Some(&self.buffer));
+    state.ctx.get_buffer_sub_data_with_i32_and_u8_array(
+      WebGl2RenderingContext::COPY_READ_BUFFER,
+      0,
+      bytes,
+    );
+    state
+      .ctx
+      .bind_buffer(WebGl2RenderingContext::COPY_READ_BUFFER, None);
+
+    texels
+  }
+}
+
+unsafe impl PendingReadbackBase for WebGL2 {
+  type PendingReadbackRepr = PendingReadback;
+}
+
+unsafe impl<P> PendingReadbackBackend<P> for WebGL2
+where
+  P: Pixel,
+{
+  unsafe fn try_get_readback(
+    pending: &mut Self::PendingReadbackRepr,
+  ) -> Result<Option<Vec<P::RawEncoding>>, TextureError>
+  where
+    P::RawEncoding: Copy + Default,
+  {
+    if pending.is_complete()? {
+      Ok(Some(pending.texels()))
+    } else {
+      Ok(None)
+    }
+  }
+
+  unsafe fn wait_readback(
+    pending: &mut Self::PendingReadbackRepr,
+  ) -> Result<Vec<P::RawEncoding>, TextureError>
+  where
+    P::RawEncoding: Copy + Default,
+  {
+    // we cannot block on the fence; reading the buffer right away makes the browser wait for the
+    // transfer to complete instead
+    Ok(pending.texels())
+  }
+}
+
+unsafe impl<D, P> TextureReadback<D, P> for WebGL2
+where
+  D: Dimensionable,
+  P: Pixel,
+  P::Encoding: IntoArrayBuffer,
+  P::RawEncoding: IntoArrayBuffer,
+{
+  unsafe fn start_texture_readback(
+    texture: &Self::TextureRepr,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+  ) -> Result<Self::PendingReadbackRepr, TextureError> {
+    let pf = P::pixel_format();
+    let texel_bytes = pf.raw_len() * mem::size_of::<P::RawEncoding>();
+    let bytes_len = texels_part_len::<D>(size) * texel_bytes;
+
+    PendingReadback::new(&texture.state, bytes_len, |state| {
+      read_texels_part::<D, P>(
+        state,
+        texture,
+        level,
+        offset,
+        size,
+        ReadPixelsDst::PackBuffer,
+      )
+    })
+  }
+}
+
+unsafe impl<FD, P> FramebufferReadback<FD, P> for WebGL2
+where
+  FD: Dimensionable,
+  P: Pixel,
+{
+  unsafe fn start_framebuffer_readback(
+    framebuffer: &Self::FramebufferRepr,
+    attachment_index: usize,
+    offset: [u32; 2],
+    size: [u32; 2],
+  ) -> Result<Self::PendingReadbackRepr, TextureError> {
+    let pf = P::pixel_format();
+
+    if pf.is_depth_pixel() {
+      return Err(TextureError::cannot_retrieve_texels(
+        "only color attachments can be read back",
+      ));
+    }
+
+    let (format, _, ty) = webgl_pixel_format(pf).ok_or(TextureError::UnsupportedPixelFormat(pf))?;
+
+    let read_buffer = match framebuffer.handle {
+      Some(_) => WebGl2RenderingContext::COLOR_ATTACHMENT0 + attachment_index as u32,
+
+      None if attachment_index == 0 => WebGl2RenderingContext::BACK,
+
+      None => {
+        return Err(TextureError::cannot_retrieve_texels(
+          "back buffers only have a single color attachment",
+        ));
+      }
+    };
+
+    let [w, h] = size;
+    let texel_bytes = pf.raw_len() * mem::size_of::<P::RawEncoding>();
+    let bytes_len = (w * h) as usize * texel_bytes;
+
+    PendingReadback::new(&framebuffer.state, bytes_len, |state| {
+      state.bind_read_framebuffer(framebuffer.handle.as_ref());
+      state.ctx.read_buffer(read_buffer);
+
+      set_pack_alignment(state, (pf.format.bytes_len() * w as usize) % 8);
+      read_pixels(
+        state,
+        [offset[0] as i32, offset[1] as i32, w as i32, h as i32],
+        format,
+        ty,
+        &mut ReadPixelsDst::PackBuffer,
+        0,
+      )
+    })
+  }
+}
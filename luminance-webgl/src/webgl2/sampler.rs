@@ -0,0 +1,53 @@
+//! Standalone sampler objects support for WebGL2.
+
+use luminance::backend::texture::SamplerObject as SamplerObjectBackend;
+use luminance::texture::{Sampler, TextureError};
+use std::cell::RefCell;
+use std::rc::Rc;
+use web_sys::WebGlSampler;
+
+use crate::webgl2::state::WebGL2State;
+use crate::webgl2::texture::{apply_sampler, SamplerTarget};
+use crate::webgl2::WebGL2;
+
+pub struct SamplerObject {
+  pub(crate) handle: WebGlSampler,
+  state: Rc<RefCell<WebGL2State>>,
+}
+
+impl Drop for SamplerObject {
+  fn drop(&mut self) {
+    let mut state = self.state.borrow_mut();
+    state.unbind_sampler(&self.handle);
+    state.ctx.delete_sampler(Some(&self.handle));
+  }
+}
+
+unsafe impl SamplerObjectBackend for WebGL2 {
+  type SamplerObjectRepr = SamplerObject;
+
+  unsafe fn new_sampler_object(
+    &mut self,
+    sampler: Sampler,
+  ) -> Result<Self::SamplerObjectRepr, TextureError> {
+    if !sampler.swizzle.is_identity() {
+      log::warn!("swizzling is part of the texture state; ignoring swizzle for sampler object");
+    }
+
+    let mut state = self.state.borrow_mut();
+
+    let handle = state
+      .ctx
+      .create_sampler()
+      .ok_or_else(|| TextureError::sampler_creation_failed("cannot create sampler object"))?;
+
+    apply_sampler(&mut state, SamplerTarget::SamplerObject(&handle), sampler);
+
+    let sampler = SamplerObject {
+      handle,
+      state: self.state.clone(),
+    };
+
+    Ok(sampler)
+  }
+}
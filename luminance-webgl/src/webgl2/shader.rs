@@ -1,17 +1,25 @@
 //! Shader support for WebGL2.
 
-use luminance::backend::shader::{Shader, Uniformable};
+use luminance::backend::shader::{
+  Shader, ShaderBinary, ShaderReflection, ShaderSpirv, ShaderUniformCache, Uniformable,
+};
 use luminance::pipeline::{BufferBinding, TextureBinding};
 use luminance::pixel::{SamplerType, Type as PixelType};
+use luminance::shader::preprocessor::with_prelude;
+use luminance::shader::reflection::{
+  ActiveAttribute, ActiveUniform, ActiveUniformBlock, UniformBlockMember,
+};
 use luminance::shader::{
-  ProgramError, StageError, StageType, TessellationStages, Uniform, UniformType, UniformWarning,
-  VertexAttribWarning,
+  GlslTarget, ProgramError, StageError, StageType, TessellationStages, Uniform, UniformCache,
+  UniformCacheStats, UniformType, UniformWarning, VertexAttribWarning,
 };
-use luminance::texture::{Dim, Dimensionable};
+use luminance::texture::{Dim, SampledWith};
 use luminance::vertex::Semantics;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::slice;
+use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{WebGl2RenderingContext, WebGlProgram, WebGlShader, WebGlUniformLocation};
 
 use crate::webgl2::state::WebGL2State;
@@ -81,10 +89,20 @@ impl Stage {
 /// mappings in the associated [`Program`].
 type LocationMap = HashMap<i32, WebGlUniformLocation>;
 
+/// Uniform a value is cached for.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum UniformKey {
+  /// Uniform location.
+  Location(i32),
+  /// Uniform block index.
+  Block(i32),
+}
+
 #[derive(Debug)]
 pub struct Program {
   pub(crate) handle: WebGlProgram,
   location_map: Rc<RefCell<LocationMap>>,
+  uniform_cache: Option<UniformCache<UniformKey>>,
   state: Rc<RefCell<WebGL2State>>,
 }
 
@@ -130,6 +148,7 @@ impl Program {
     let program = Program {
       handle,
       location_map,
+      uniform_cache: None,
       state,
     };
 
@@ -159,6 +178,17 @@ impl Program {
     }
   }
 
+  /// Check whether `value` was the last value uploaded to the uniform identified by `key`, and
+  /// remember it otherwise.
+  ///
+  /// `T` must not contain any padding byte.
+  unsafe fn is_uniform_cached<T>(&mut self, key: UniformKey, value: &[T]) -> bool {
+    match self.uniform_cache {
+      Some(ref mut cache) => cache.is_cached(key, value),
+      None => false,
+    }
+  }
+
   fn handle(&self) -> &WebGlProgram {
     &self.handle
   }
@@ -292,6 +322,197 @@ unsafe impl Shader for WebGL2 {
   }
 }
 
+// WebGL2 cannot consume SPIR-V modules; they can still be cross-compiled to GLSL ES 300
+unsafe impl ShaderSpirv for WebGL2 {
+  unsafe fn supports_spirv(&mut self) -> bool {
+    false
+  }
+
+  unsafe fn new_stage_from_spirv(
+    &mut self,
+    ty: StageType,
+    _: &[u8],
+    _: &str,
+  ) -> Result<Self::StageRepr, StageError> {
+    Err(StageError::unsupported_spirv(ty))
+  }
+
+  unsafe fn glsl_target(&mut self) -> GlslTarget {
+    GlslTarget::GlslEs300
+  }
+}
+
+unsafe impl ShaderUniformCache for WebGL2 {
+  unsafe fn set_uniform_cache(program: &mut Self::ProgramRepr, enabled: bool) {
+    if !enabled {
+      program.uniform_cache = None;
+    } else if program.uniform_cache.is_none() {
+      program.uniform_cache = Some(UniformCache::default());
+    }
+  }
+
+  unsafe fn uniform_cache_stats(program: &Self::ProgramRepr) -> Option<UniformCacheStats> {
+    program.uniform_cache.as_ref().map(UniformCache::stats)
+  }
+
+  unsafe fn reset_uniform_cache_stats(program: &mut Self::ProgramRepr) {
+    if let Some(ref mut cache) = program.uniform_cache {
+      cache.reset_stats();
+    }
+  }
+}
+
+// WebGL doesn’t expose uniform locations as integers, so active uniforms are reported with a -1
+// location
+unsafe impl ShaderReflection for WebGL2 {
+  unsafe fn active_uniforms(program: &Self::ProgramRepr) -> Vec<ActiveUniform> {
+    let state = program.state.borrow();
+    let count = program_parameter(&state, program, WebGl2RenderingContext::ACTIVE_UNIFORMS);
+
+    (0..count)
+      .filter_map(|index| {
+        let info = state.ctx.get_active_uniform(program.handle(), index)?;
+
+        // uniform block members are reported with their block
+        let indices = js_sys::Array::of1(&index.into());
+        let block = state
+          .ctx
+          .get_active_uniforms(
+            program.handle(),
+            indices.as_ref(),
+            WebGl2RenderingContext::UNIFORM_BLOCK_INDEX,
+          )
+          .dyn_into::<js_sys::Array>()
+          .ok()?
+          .get(0)
+          .as_f64()?;
+
+        if block != -1. {
+          return None;
+        }
+
+        Some(ActiveUniform {
+          name: info.name(),
+          ty: uniform_type(info.type_()),
+          array_size: info.size() as usize,
+          location: -1,
+        })
+      })
+      .collect()
+  }
+
+  unsafe fn active_uniform_blocks(program: &Self::ProgramRepr) -> Vec<ActiveUniformBlock> {
+    let state = program.state.borrow();
+    let count = program_parameter(
+      &state,
+      program,
+      WebGl2RenderingContext::ACTIVE_UNIFORM_BLOCKS,
+    );
+
+    (0..count)
+      .filter_map(|index| {
+        let name = state
+          .ctx
+          .get_active_uniform_block_name(program.handle(), index)?;
+        let block_parameter = |pname| {
+          state
+            .ctx
+            .get_active_uniform_block_parameter(program.handle(), index, pname)
+            .ok()
+        };
+
+        let size = block_parameter(WebGl2RenderingContext::UNIFORM_BLOCK_DATA_SIZE)?.as_f64()?;
+        let member_indices: Vec<u32> =
+          block_parameter(WebGl2RenderingContext::UNIFORM_BLOCK_ACTIVE_UNIFORM_INDICES)
+            .map(|indices| js_sys::Uint32Array::new(&indices).to_vec())
+            .unwrap_or_default();
+
+        // query all the offsets at once
+        let indices = member_indices
+          .iter()
+          .map(|&index| JsValue::from(index))
+          .collect::<js_sys::Array>();
+        let offsets = state
+          .ctx
+          .get_active_uniforms(
+            program.handle(),
+            indices.as_ref(),
+            WebGl2RenderingContext::UNIFORM_OFFSET,
+          )
+          .dyn_into::<js_sys::Array>()
+          .ok()?;
+
+        let members = member_indices
+          .iter()
+          .zip(offsets.iter())
+          .filter_map(|(&member, offset)| {
+            let info = state.ctx.get_active_uniform(program.handle(), member)?;
+
+            Some(UniformBlockMember {
+              name: info.name(),
+              ty: uniform_type(info.type_()),
+              array_size: info.size() as usize,
+              offset: offset.as_f64()? as usize,
+            })
+          })
+          .collect();
+
+        Some(ActiveUniformBlock {
+          name,
+          index,
+          size: size as usize,
+          members,
+        })
+      })
+      .collect()
+  }
+
+  unsafe fn active_attributes(program: &Self::ProgramRepr) -> Vec<ActiveAttribute> {
+    let state = program.state.borrow();
+    let count = program_parameter(&state, program, WebGl2RenderingContext::ACTIVE_ATTRIBUTES);
+
+    (0..count)
+      .filter_map(|index| {
+        let info = state.ctx.get_active_attrib(program.handle(), index)?;
+        let name = info.name();
+        let location = state.ctx.get_attrib_location(program.handle(), &name);
+
+        Some(ActiveAttribute {
+          name,
+          ty: uniform_type(info.type_()),
+          location,
+        })
+      })
+      .collect()
+  }
+}
+
+// get an integer parameter of a program, such as the number of active uniforms
+fn program_parameter(state: &WebGL2State, program: &Program, pname: u32) -> u32 {
+  state
+    .ctx
+    .get_program_parameter(program.handle(), pname)
+    .as_f64()
+    .map_or(0, |n| n as u32)
+}
+
+// WebGL doesn’t support program binaries, so programs are always compiled from their sources
+unsafe impl ShaderBinary for WebGL2 {
+  fn source_prelude() -> String {
+    GLSL_PRAGMA.to_owned()
+  }
+
+  unsafe fn set_binary_retrievable(_: &mut Self::ProgramRepr) {}
+
+  unsafe fn program_binary(_: &Self::ProgramRepr) -> Option<(u32, Vec<u8>)> {
+    None
+  }
+
+  unsafe fn new_program_from_binary(&mut self, _: u32, _: &[u8]) -> Option<Self::ProgramRepr> {
+    None
+  }
+}
+
 fn webgl_shader_type(ty: StageType) -> Option<u32> {
   match ty {
     StageType::VertexShader => Some(WebGl2RenderingContext::VERTEX_SHADER),
@@ -305,9 +526,7 @@ const GLSL_PRAGMA: &str = "#version 300 es\n\
                            precision highp int;\n";
 
 fn patch_shader_src(src: &str) -> String {
-  let mut pragma = String::from(GLSL_PRAGMA);
-  pragma.push_str(src);
-  pragma
+  with_prelude(GLSL_PRAGMA, src)
 }
 
 fn uniform_type_match(
@@ -347,65 +566,107 @@ fn uniform_type_match(
   check_types_match(name, ty, info.type_())
 }
 
-#[allow(clippy::cognitive_complexity)]
+// GLSL types luminance supports, along with their WebGL type
+const UNIFORM_TYPES: &[(UniformType, u32)] = &[
+  // scalars
+  (UniformType::Int, WebGl2RenderingContext::INT),
+  (UniformType::UInt, WebGl2RenderingContext::UNSIGNED_INT),
+  (UniformType::Float, WebGl2RenderingContext::FLOAT),
+  (UniformType::Bool, WebGl2RenderingContext::BOOL),
+  // vectors
+  (UniformType::IVec2, WebGl2RenderingContext::INT_VEC2),
+  (UniformType::IVec3, WebGl2RenderingContext::INT_VEC3),
+  (UniformType::IVec4, WebGl2RenderingContext::INT_VEC4),
+  (
+    UniformType::UIVec2,
+    WebGl2RenderingContext::UNSIGNED_INT_VEC2,
+  ),
+  (
+    UniformType::UIVec3,
+    WebGl2RenderingContext::UNSIGNED_INT_VEC3,
+  ),
+  (
+    UniformType::UIVec4,
+    WebGl2RenderingContext::UNSIGNED_INT_VEC4,
+  ),
+  (UniformType::Vec2, WebGl2RenderingContext::FLOAT_VEC2),
+  (UniformType::Vec3, WebGl2RenderingContext::FLOAT_VEC3),
+  (UniformType::Vec4, WebGl2RenderingContext::FLOAT_VEC4),
+  (UniformType::BVec2, WebGl2RenderingContext::BOOL_VEC2),
+  (UniformType::BVec3, WebGl2RenderingContext::BOOL_VEC3),
+  (UniformType::BVec4, WebGl2RenderingContext::BOOL_VEC4),
+  // matrices
+  (UniformType::M22, WebGl2RenderingContext::FLOAT_MAT2),
+  (UniformType::M33, WebGl2RenderingContext::FLOAT_MAT3),
+  (UniformType::M44, WebGl2RenderingContext::FLOAT_MAT4),
+  // textures
+  (
+    UniformType::ISampler2D,
+    WebGl2RenderingContext::INT_SAMPLER_2D,
+  ),
+  (
+    UniformType::ISampler3D,
+    WebGl2RenderingContext::INT_SAMPLER_3D,
+  ),
+  (
+    UniformType::ISampler2DArray,
+    WebGl2RenderingContext::INT_SAMPLER_2D_ARRAY,
+  ),
+  (
+    UniformType::UISampler2D,
+    WebGl2RenderingContext::UNSIGNED_INT_SAMPLER_2D,
+  ),
+  (
+    UniformType::UISampler3D,
+    WebGl2RenderingContext::UNSIGNED_INT_SAMPLER_3D,
+  ),
+  (
+    UniformType::UISampler2DArray,
+    WebGl2RenderingContext::UNSIGNED_INT_SAMPLER_2D_ARRAY,
+  ),
+  (UniformType::Sampler2D, WebGl2RenderingContext::SAMPLER_2D),
+  (UniformType::Sampler3D, WebGl2RenderingContext::SAMPLER_3D),
+  (
+    UniformType::Sampler2DArray,
+    WebGl2RenderingContext::SAMPLER_2D_ARRAY,
+  ),
+  (
+    UniformType::ICubemap,
+    WebGl2RenderingContext::INT_SAMPLER_CUBE,
+  ),
+  (
+    UniformType::UICubemap,
+    WebGl2RenderingContext::UNSIGNED_INT_SAMPLER_CUBE,
+  ),
+  (UniformType::Cubemap, WebGl2RenderingContext::SAMPLER_CUBE),
+  (
+    UniformType::Sampler2DShadow,
+    WebGl2RenderingContext::SAMPLER_2D_SHADOW,
+  ),
+  (
+    UniformType::Sampler2DArrayShadow,
+    WebGl2RenderingContext::SAMPLER_2D_ARRAY_SHADOW,
+  ),
+  (
+    UniformType::CubemapShadow,
+    WebGl2RenderingContext::SAMPLER_CUBE_SHADOW,
+  ),
+];
+
 fn check_types_match(name: &str, ty: UniformType, glty: u32) -> Result<(), UniformWarning> {
-  // helper macro to check type mismatch for each variant
-  macro_rules! milkcheck {
-    ($ty:expr, $( ( $v:tt, $t:tt ) ),*) => {
-      match $ty {
-        $(
-          UniformType::$v => {
-            if glty == WebGl2RenderingContext::$t {
-              Ok(())
-            } else {
-              Err(UniformWarning::type_mismatch(name, ty))
-            }
-          }
-        )*
-
-        _ => Err(UniformWarning::unsupported_type(name, ty))
-      }
-    }
+  match UNIFORM_TYPES.iter().find(|(t, _)| *t == ty) {
+    Some((_, t)) if *t == glty => Ok(()),
+    Some(_) => Err(UniformWarning::type_mismatch(name, ty)),
+    None => Err(UniformWarning::unsupported_type(name, ty)),
   }
+}
 
-  milkcheck!(
-    ty,
-    // scalars
-    (Int, INT),
-    (UInt, UNSIGNED_INT),
-    (Float, FLOAT),
-    (Bool, BOOL),
-    // vectors
-    (IVec2, INT_VEC2),
-    (IVec3, INT_VEC3),
-    (IVec4, INT_VEC4),
-    (UIVec2, UNSIGNED_INT_VEC2),
-    (UIVec3, UNSIGNED_INT_VEC3),
-    (UIVec4, UNSIGNED_INT_VEC4),
-    (Vec2, FLOAT_VEC2),
-    (Vec3, FLOAT_VEC3),
-    (Vec4, FLOAT_VEC4),
-    (BVec2, BOOL_VEC2),
-    (BVec3, BOOL_VEC3),
-    (BVec4, BOOL_VEC4),
-    // matrices
-    (M22, FLOAT_MAT2),
-    (M33, FLOAT_MAT3),
-    (M44, FLOAT_MAT4),
-    // textures
-    (ISampler2D, INT_SAMPLER_2D),
-    (ISampler3D, INT_SAMPLER_3D),
-    (ISampler2DArray, INT_SAMPLER_2D_ARRAY),
-    (UISampler2D, UNSIGNED_INT_SAMPLER_2D),
-    (UISampler3D, UNSIGNED_INT_SAMPLER_3D),
-    (UISampler2DArray, UNSIGNED_INT_SAMPLER_2D_ARRAY),
-    (Sampler2D, SAMPLER_2D),
-    (Sampler3D, SAMPLER_3D),
-    (Sampler2DArray, SAMPLER_2D_ARRAY),
-    (ICubemap, INT_SAMPLER_CUBE),
-    (UICubemap, UNSIGNED_INT_SAMPLER_CUBE),
-    (Cubemap, SAMPLER_CUBE)
-  )
+// get the UniformType of a WebGL type, if supported
+fn uniform_type(glty: u32) -> Option<UniformType> {
+  UNIFORM_TYPES
+    .iter()
+    .find(|(_, t)| *t == glty)
+    .map(|(ty, _)| *ty)
 }
 
 fn bind_vertex_attribs_locations<Sem>(
@@ -467,6 +728,10 @@ macro_rules! impl_Uniformable {
       }
 
       unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+        if program.is_uniform_cached(UniformKey::Location(uniform.index()), self) {
+          return;
+        }
+
         let len = self.len();
         let data = flatten_slice!(self: $t, len = $dim * self.len());
 
@@ -487,6 +752,10 @@ macro_rules! impl_Uniformable {
       }
 
       unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+        if program.is_uniform_cached(UniformKey::Location(uniform.index()), self) {
+          return;
+        }
+
         program
           .state
           .borrow()
@@ -503,6 +772,10 @@ macro_rules! impl_Uniformable {
       }
 
       unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+        if program.is_uniform_cached(UniformKey::Location(uniform.index()), &self[..]) {
+          return;
+        }
+
         program
           .state
           .borrow()
@@ -519,6 +792,10 @@ macro_rules! impl_Uniformable {
       }
 
       unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+        if program.is_uniform_cached(UniformKey::Location(uniform.index()), slice::from_ref(&self)) {
+          return;
+        }
+
         program
           .state
           .borrow()
@@ -536,6 +813,10 @@ macro_rules! impl_Uniformable {
       }
 
       unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+        if program.is_uniform_cached(UniformKey::Location(uniform.index()), self) {
+          return;
+        }
+
         let data = flatten_slice!(self: $t, len = $dim * $dim * self.len());
 
         program.state.borrow().ctx.$f(
@@ -556,6 +837,10 @@ macro_rules! impl_Uniformable {
       }
 
       unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+        if program.is_uniform_cached(UniformKey::Location(uniform.index()), slice::from_ref(&self)) {
+          return;
+        }
+
         let data = flatten_slice!(self: $t, len = $dim * $dim);
 
         program.state.borrow().ctx.$f(
@@ -654,10 +939,17 @@ unsafe impl Uniformable<WebGL2> for bool {
   }
 
   unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
-    program.state.borrow().ctx.uniform1ui(
-      program.location_map.borrow().get(&uniform.index()),
-      self as u32,
-    );
+    let v = self as u32;
+
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), slice::from_ref(&v)) {
+      return;
+    }
+
+    program
+      .state
+      .borrow()
+      .ctx
+      .uniform1ui(program.location_map.borrow().get(&uniform.index()), v);
   }
 }
 
@@ -669,6 +961,10 @@ unsafe impl Uniformable<WebGL2> for [bool; 2] {
   unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
     let v = [self[0] as u32, self[1] as u32];
 
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     program
       .state
       .borrow()
@@ -685,6 +981,10 @@ unsafe impl Uniformable<WebGL2> for [bool; 3] {
   unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
     let v = [self[0] as u32, self[1] as u32, self[2] as u32];
 
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     program
       .state
       .borrow()
@@ -706,6 +1006,10 @@ unsafe impl Uniformable<WebGL2> for [bool; 4] {
       self[3] as u32,
     ];
 
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     program
       .state
       .borrow()
@@ -722,6 +1026,10 @@ unsafe impl<'a> Uniformable<WebGL2> for &'a [bool] {
   unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
     let v: Vec<_> = self.iter().map(|x| *x as u32).collect();
 
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     program
       .state
       .borrow()
@@ -737,6 +1045,11 @@ unsafe impl<'a> Uniformable<WebGL2> for &'a [[bool; 2]] {
 
   unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
     let v: Vec<_> = self.iter().map(|x| [x[0] as u32, x[1] as u32]).collect();
+
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     let data = flatten_slice!(v: u32, len = 2 * v.len());
 
     program
@@ -757,6 +1070,11 @@ unsafe impl<'a> Uniformable<WebGL2> for &'a [[bool; 3]] {
       .iter()
       .map(|x| [x[0] as u32, x[1] as u32, x[2] as u32])
       .collect();
+
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     let data = flatten_slice!(v: u32, len = 3 * v.len());
 
     program
@@ -777,6 +1095,11 @@ unsafe impl<'a> Uniformable<WebGL2> for &'a [[bool; 4]] {
       .iter()
       .map(|x| [x[0] as u32, x[1] as u32, x[2] as u32, x[3] as u32])
       .collect();
+
+    if program.is_uniform_cached(UniformKey::Location(uniform.index()), &v) {
+      return;
+    }
+
     let data = flatten_slice!(v: u32, len = 4 * v.len());
 
     program
@@ -793,20 +1116,42 @@ unsafe impl<T> Uniformable<WebGL2> for BufferBinding<T> {
   }
 
   unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
+    let binding = self.binding();
+
+    if program.is_uniform_cached(
+      UniformKey::Block(uniform.index()),
+      slice::from_ref(&binding),
+    ) {
+      return;
+    }
+
     program.state.borrow().ctx.uniform_block_binding(
       &program.handle,
       uniform.index() as u32,
-      self.binding(),
+      binding,
     );
   }
 }
 
 unsafe impl<D, S> Uniformable<WebGL2> for TextureBinding<D, S>
 where
-  D: Dimensionable,
+  D: SampledWith<S>,
   S: SamplerType,
 {
   unsafe fn ty() -> UniformType {
+    if S::depth_comparison() {
+      return match D::dim() {
+        Dim::Dim1 => UniformType::Sampler1DShadow,
+        Dim::Dim2 => UniformType::Sampler2DShadow,
+        // 3D textures don’t implement SampledWith<Shadow>
+        Dim::Dim3 => unreachable!("3D textures have no shadow sampler"),
+        Dim::Cubemap => UniformType::CubemapShadow,
+        Dim::Dim1Array => UniformType::Sampler1DArrayShadow,
+        Dim::Dim2Array => UniformType::Sampler2DArrayShadow,
+        Dim::CubemapArray => UniformType::CubemapArrayShadow,
+      };
+    }
+
     match (S::sample_type(), D::dim()) {
       (PixelType::NormIntegral, Dim::Dim1) => UniformType::Sampler1D,
       (PixelType::NormUnsigned, Dim::Dim1) => UniformType::Sampler1D,
@@ -843,13 +1188,29 @@ where
       (PixelType::Integral, Dim::Dim2Array) => UniformType::ISampler2DArray,
       (PixelType::Unsigned, Dim::Dim2Array) => UniformType::UISampler2DArray,
       (PixelType::Floating, Dim::Dim2Array) => UniformType::Sampler2DArray,
+
+      (PixelType::NormIntegral, Dim::CubemapArray) => UniformType::CubemapArray,
+      (PixelType::NormUnsigned, Dim::CubemapArray) => UniformType::CubemapArray,
+      (PixelType::Integral, Dim::CubemapArray) => UniformType::ICubemapArray,
+      (PixelType::Unsigned, Dim::CubemapArray) => UniformType::UICubemapArray,
+      (PixelType::Floating, Dim::CubemapArray) => UniformType::CubemapArray,
     }
   }
 
   unsafe fn update(self, program: &mut Program, uniform: &Uniform<Self>) {
-    program.state.borrow().ctx.uniform1i(
-      program.location_map.borrow().get(&uniform.index()),
-      self.binding() as i32,
-    );
+    let binding = self.binding() as i32;
+
+    if program.is_uniform_cached(
+      UniformKey::Location(uniform.index()),
+      slice::from_ref(&binding),
+    ) {
+      return;
+    }
+
+    program
+      .state
+      .borrow()
+      .ctx
+      .uniform1i(program.location_map.borrow().get(&uniform.index()), binding);
   }
 }
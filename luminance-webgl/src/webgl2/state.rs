@@ -9,10 +9,14 @@ use luminance::{
 };
 use std::{fmt, marker::PhantomData};
 use web_sys::{
-  WebGl2RenderingContext, WebGlBuffer, WebGlFramebuffer, WebGlProgram, WebGlTexture,
+  WebGl2RenderingContext, WebGlBuffer, WebGlFramebuffer, WebGlProgram, WebGlSampler, WebGlTexture,
   WebGlVertexArrayObject,
 };
 
+// EXT_texture_filter_anisotropic symbols, which are not exposed by WebGl2RenderingContext.
+pub(crate) const TEXTURE_MAX_ANISOTROPY_EXT: u32 = 0x84FE;
+const MAX_TEXTURE_MAX_ANISOTROPY_EXT: u32 = 0x84FF;
+
 #[derive(Debug)]
 pub(crate) struct BindingStack {
   pub(crate) next_texture_unit: u32,
@@ -80,6 +84,9 @@ pub struct WebGL2State {
   current_texture_unit: u32,
   bound_textures: Vec<(u32, Option<WebGlTexture>)>,
 
+  // sampler objects bound to texture units; None means the texture’s own parameters are used
+  bound_samplers: Vec<Option<WebGlSampler>>,
+
   // texture buffer used to optimize texture creation; regular textures typically will never ask
   // for fetching from this set but framebuffers, who often generate several textures, might use
   // this opportunity to get N textures (color, depth and stencil) at once, in a single CPU / GPU
@@ -125,6 +132,9 @@ pub struct WebGL2State {
 
   /// Maximum number of elements a texture array can hold.
   max_texture_array_elements: Option<usize>,
+
+  /// Maximum anisotropy, if `EXT_texture_filter_anisotropic` is available.
+  max_texture_max_anisotropy: Option<Option<f32>>,
 }
 
 impl WebGL2State {
@@ -158,6 +168,7 @@ impl WebGL2State {
 
     let current_texture_unit = 0;
     let bound_textures = vec![(WebGl2RenderingContext::TEXTURE0, None); 48]; // 48 is the platform minimal requirement
+    let bound_samplers = vec![None; 48]; // same as bound_textures
     let texture_swimming_pool = Vec::new();
     let bound_uniform_buffers = vec![None; 36]; // 36 is the platform minimal requirement
     let bound_array_buffer = None;
@@ -173,6 +184,7 @@ impl WebGL2State {
     let gl_version = None;
     let glsl_version = None;
     let max_texture_array_elements = None;
+    let max_texture_max_anisotropy = None;
 
     Ok(WebGL2State {
       _phantom: PhantomData,
@@ -193,6 +205,7 @@ impl WebGL2State {
       scissor_region,
       current_texture_unit,
       bound_textures,
+      bound_samplers,
       texture_swimming_pool,
       bound_uniform_buffers,
       bound_array_buffer,
@@ -207,6 +220,7 @@ impl WebGL2State {
       webgl_version: gl_version,
       glsl_version,
       max_texture_array_elements,
+      max_texture_max_anisotropy,
     })
   }
 
@@ -320,6 +334,33 @@ impl WebGL2State {
     }
   }
 
+  /// Bind a sampler object at the provided texture unit. Binding `None` reverts to the bound
+  /// texture’s own sampling parameters.
+  pub(crate) fn bind_sampler_at(&mut self, handle: Option<&WebGlSampler>, unit: u32) {
+    let unit = unit as usize;
+
+    if unit >= self.bound_samplers.len() {
+      // not enough available texture units; let’s grow a bit more
+      self.bound_samplers.resize(unit + 1, None);
+    }
+
+    if self.bound_samplers[unit].as_ref() != handle {
+      self.ctx.bind_sampler(unit as u32, handle);
+      self.bound_samplers[unit] = handle.cloned();
+    }
+  }
+
+  /// Forget about a sampler object that is about to be deleted.
+  ///
+  /// Deleted sampler objects are unbound from all texture units, so the cache must reflect it.
+  pub(crate) fn unbind_sampler(&mut self, handle: &WebGlSampler) {
+    for bound in &mut self.bound_samplers {
+      if bound.as_ref() == Some(handle) {
+        *bound = None;
+      }
+    }
+  }
+
   pub(crate) fn create_framebuffer(&mut self) -> Option<WebGlFramebuffer> {
     self.ctx.create_framebuffer()
   }
@@ -332,12 +373,16 @@ impl WebGL2State {
     })
   }
 
+  // binding to FRAMEBUFFER replaces both the draw and read bindings, so both are cached
   pub(crate) fn bind_draw_framebuffer(&mut self, handle: Option<&WebGlFramebuffer>) {
-    if self.bound_draw_framebuffer.as_ref() != handle {
+    if self.bound_draw_framebuffer.as_ref() != handle
+      || self.bound_read_framebuffer.as_ref() != handle
+    {
       self
         .ctx
         .bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, handle);
       self.bound_draw_framebuffer = handle.cloned();
+      self.bound_read_framebuffer = handle.cloned();
     }
   }
 
@@ -606,6 +651,25 @@ impl WebGL2State {
       max
     })
   }
+
+  /// Get the maximum anisotropy supported for texture filtering, if the
+  /// `EXT_texture_filter_anisotropic` extension is available.
+  ///
+  /// Enable the extension and cache the number on the first call and then re-use it for later
+  /// calls.
+  pub fn get_max_texture_max_anisotropy(&mut self) -> Option<f32> {
+    if let Some(max) = self.max_texture_max_anisotropy {
+      return max;
+    }
+
+    let max = match self.ctx.get_extension("EXT_texture_filter_anisotropic") {
+      Ok(Some(_)) => self.ctx.get_webgl_param(MAX_TEXTURE_MAX_ANISOTROPY_EXT),
+      _ => None,
+    };
+
+    self.max_texture_max_anisotropy = Some(max);
+    max
+  }
 }
 
 impl Drop for WebGL2State {
@@ -1096,7 +1160,7 @@ macro_rules! impl_GetWebGLParam_integer {
   }
 }
 
-impl_GetWebGLParam_integer!(u32, usize);
+impl_GetWebGLParam_integer!(u32, usize, f32);
 
 macro_rules! impl_GetWebGLParam_array {
   ($($arr_ty:ty),*) => {
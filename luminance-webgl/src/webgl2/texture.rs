@@ -1,25 +1,28 @@
-use luminance::backend::texture::{Texture as TextureBackend, TextureBase};
+use luminance::backend::texture::{
+  DynTexture, Texture as TextureBackend, TextureBase, TextureCopy, TextureCopyFramebuffer,
+};
 use luminance::depth_test::DepthComparison;
 use luminance::pixel::{Pixel, PixelFormat};
 use luminance::texture::{
   Dim, Dimensionable, GenMipmaps, MagFilter, MinFilter, Sampler, TextureError, Wrap,
 };
 use std::cell::RefCell;
+use std::convert::TryInto;
 use std::mem;
 use std::rc::Rc;
 use std::slice;
-use web_sys::{WebGl2RenderingContext, WebGlTexture};
+use web_sys::{WebGl2RenderingContext, WebGlSampler, WebGlTexture};
 
 use crate::webgl2::array_buffer::IntoArrayBuffer;
 use crate::webgl2::pixel::webgl_pixel_format;
-use crate::webgl2::state::WebGL2State;
+use crate::webgl2::state::{WebGL2State, TEXTURE_MAX_ANISOTROPY_EXT};
 use crate::webgl2::WebGL2;
 
 pub struct Texture {
   pub(crate) handle: WebGlTexture,
   pub(crate) target: u32, // “type” of the texture; used for bindings
   mipmaps: usize,
-  state: Rc<RefCell<WebGL2State>>,
+  pub(crate) state: Rc<RefCell<WebGL2State>>,
 }
 
 impl Texture {
@@ -55,36 +58,7 @@ where
     mipmaps: usize,
     sampler: Sampler,
   ) -> Result<Self::TextureRepr, TextureError> {
-    let mipmaps = mipmaps + 1; // + 1 prevent having 0 mipmaps
-    let dim = D::dim();
-    let target = opengl_target(dim).ok_or_else(|| {
-      TextureError::TextureStorageCreationFailed(format!("incompatible texture dim: {}", dim))
-    })?;
-
-    let mut state = self.state.borrow_mut();
-
-    let handle = state.create_texture().ok_or_else(|| {
-      TextureError::TextureStorageCreationFailed("cannot create texture".to_owned())
-    })?;
-    state.bind_texture(target, Some(&handle));
-
-    setup_texture::<D>(
-      &mut state,
-      target,
-      size,
-      mipmaps,
-      P::pixel_format(),
-      sampler,
-    )?;
-
-    let texture = Texture {
-      handle,
-      target,
-      mipmaps,
-      state: self.state.clone(),
-    };
-
-    Ok(texture)
+    new_texture::<D>(self, size, mipmaps, sampler, P::pixel_format())
   }
 
   unsafe fn mipmaps(texture: &Self::TextureRepr) -> usize {
@@ -127,7 +101,15 @@ where
 
     gfx_state.bind_texture(texture.target, Some(&texture.handle));
 
-    upload_texels::<D, P, P::Encoding>(&mut gfx_state, texture.target, offset, size, texels)?;
+    upload_texels::<D, P::Encoding>(
+      &mut gfx_state,
+      texture.target,
+      0,
+      offset,
+      size,
+      P::pixel_format(),
+      texels,
+    )?;
 
     if gen_mipmaps == GenMipmaps::Yes {
       gfx_state.ctx.generate_mipmap(texture.target);
@@ -156,7 +138,15 @@ where
 
     gfx_state.bind_texture(texture.target, Some(&texture.handle));
 
-    upload_texels::<D, P, P::RawEncoding>(&mut gfx_state, texture.target, offset, size, texels)?;
+    upload_texels::<D, P::RawEncoding>(
+      &mut gfx_state,
+      texture.target,
+      0,
+      offset,
+      size,
+      P::pixel_format(),
+      texels,
+    )?;
 
     if gen_mipmaps == GenMipmaps::Yes {
       gfx_state.ctx.generate_mipmap(texture.target);
@@ -180,6 +170,54 @@ where
     )
   }
 
+  unsafe fn upload_part_level(
+    texture: &mut Self::TextureRepr,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+    texels: &[P::Encoding],
+  ) -> Result<(), TextureError> {
+    check_mipmap_level(texture, level, TextureError::CannotUploadTexels)?;
+
+    let mut gfx_state = texture.state.borrow_mut();
+
+    gfx_state.bind_texture(texture.target, Some(&texture.handle));
+
+    upload_texels::<D, P::Encoding>(
+      &mut gfx_state,
+      texture.target,
+      level as i32,
+      offset,
+      size,
+      P::pixel_format(),
+      texels,
+    )
+  }
+
+  unsafe fn upload_part_level_raw(
+    texture: &mut Self::TextureRepr,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+    texels: &[P::RawEncoding],
+  ) -> Result<(), TextureError> {
+    check_mipmap_level(texture, level, TextureError::CannotUploadTexels)?;
+
+    let mut gfx_state = texture.state.borrow_mut();
+
+    gfx_state.bind_texture(texture.target, Some(&texture.handle));
+
+    upload_texels::<D, P::RawEncoding>(
+      &mut gfx_state,
+      texture.target,
+      level as i32,
+      offset,
+      size,
+      P::pixel_format(),
+      texels,
+    )
+  }
+
   unsafe fn get_raw_texels(
     texture: &Self::TextureRepr,
     size: D::Size,
@@ -209,7 +247,7 @@ where
     match gfx_state.create_or_get_readback_framebuffer() {
       Some(ref readback_fb) => {
         // Resize the vec to allocate enough space to host the returned texels.
-        let texels_nb = (w * h) as usize * pf.channels_len();
+        let texels_nb = (w * h) as usize * pf.raw_len();
         let mut texels = vec![Default::default(); texels_nb];
 
         // Attach the texture so that we can read from the framebuffer; careful here, since we are
@@ -259,6 +297,35 @@ where
     }
   }
 
+  unsafe fn get_raw_texels_part(
+    texture: &Self::TextureRepr,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+  ) -> Result<Vec<P::RawEncoding>, TextureError>
+  where
+    P::RawEncoding: Copy + Default,
+  {
+    let pf = P::pixel_format();
+
+    let mut texels = vec![Default::default(); texels_part_len::<D>(size) * pf.raw_len()];
+    let bytes = slice::from_raw_parts_mut(
+      texels.as_mut_ptr() as *mut u8,
+      texels.len() * mem::size_of::<P::RawEncoding>(),
+    );
+
+    read_texels_part::<D, P>(
+      &mut texture.state.borrow_mut(),
+      texture,
+      level,
+      offset,
+      size,
+      ReadPixelsDst::Slice(bytes),
+    )?;
+
+    Ok(texels)
+  }
+
   unsafe fn resize(
     texture: &mut Self::TextureRepr,
     size: D::Size,
@@ -266,7 +333,295 @@ where
   ) -> Result<(), TextureError> {
     let mipmaps = mipmaps + 1; // + 1 to prevent having 0 mipmaps
     let mut state = texture.state.borrow_mut();
-    create_texture_storage::<D>(&mut state, size, mipmaps, P::pixel_format())
+    create_texture_storage::<D>(&mut state, size, mipmaps, P::pixel_format())?;
+
+    texture.mipmaps = mipmaps;
+    Ok(())
+  }
+}
+
+unsafe impl<D> DynTexture<D> for WebGL2
+where
+  D: Dimensionable,
+{
+  unsafe fn new_dyn_texture(
+    &mut self,
+    size: D::Size,
+    mipmaps: usize,
+    sampler: Sampler,
+    pixel_format: PixelFormat,
+  ) -> Result<Self::TextureRepr, TextureError> {
+    new_texture::<D>(self, size, mipmaps, sampler, pixel_format)
+  }
+
+  unsafe fn dyn_mipmaps(texture: &Self::TextureRepr) -> usize {
+    texture.mipmaps
+  }
+
+  unsafe fn upload_dyn_part_raw(
+    texture: &mut Self::TextureRepr,
+    pixel_format: PixelFormat,
+    gen_mipmaps: GenMipmaps,
+    offset: D::Offset,
+    size: D::Size,
+    texels: &[u8],
+  ) -> Result<(), TextureError> {
+    let (_, _, ty) =
+      webgl_pixel_format(pixel_format).ok_or(TextureError::UnsupportedPixelFormat(pixel_format))?;
+
+    let mut gfx_state = texture.state.borrow_mut();
+
+    gfx_state.bind_texture(texture.target, Some(&texture.handle));
+
+    // WebGL requires the array buffer view to match the type of the texels, so the bytes are
+    // converted first
+    macro_rules! upload_as {
+      ($t:ty) => {
+        upload_texels::<D, $t>(
+          &mut gfx_state,
+          texture.target,
+          0,
+          offset,
+          size,
+          pixel_format,
+          &texels
+            .chunks_exact(mem::size_of::<$t>())
+            .map(|raw| <$t>::from_ne_bytes(raw.try_into().unwrap()))
+            .collect::<Vec<_>>(),
+        )
+      };
+    }
+
+    match ty {
+      WebGl2RenderingContext::BYTE => upload_as!(i8),
+      WebGl2RenderingContext::UNSIGNED_BYTE => upload_as!(u8),
+      WebGl2RenderingContext::SHORT => upload_as!(i16),
+      WebGl2RenderingContext::UNSIGNED_SHORT | WebGl2RenderingContext::HALF_FLOAT => {
+        upload_as!(u16)
+      }
+      WebGl2RenderingContext::INT => upload_as!(i32),
+      WebGl2RenderingContext::FLOAT => upload_as!(f32),
+      _ => upload_as!(u32),
+    }?;
+
+    if gen_mipmaps == GenMipmaps::Yes {
+      gfx_state.ctx.generate_mipmap(texture.target);
+    }
+
+    Ok(())
+  }
+}
+
+unsafe fn new_texture<D>(
+  webgl2: &mut WebGL2,
+  size: D::Size,
+  mipmaps: usize,
+  sampler: Sampler,
+  pf: PixelFormat,
+) -> Result<Texture, TextureError>
+where
+  D: Dimensionable,
+{
+  let mipmaps = mipmaps + 1; // + 1 prevent having 0 mipmaps
+  let dim = D::dim();
+  let target = opengl_target(dim).ok_or_else(|| {
+    TextureError::TextureStorageCreationFailed(format!("incompatible texture dim: {}", dim))
+  })?;
+
+  // WebGL2 has no texture swizzling; checked before creating anything so that nothing leaks
+  if !sampler.swizzle.is_identity() {
+    return Err(TextureError::unsupported_swizzle(sampler.swizzle));
+  }
+
+  let mut state = webgl2.state.borrow_mut();
+
+  let handle = state.create_texture().ok_or_else(|| {
+    TextureError::TextureStorageCreationFailed("cannot create texture".to_owned())
+  })?;
+  state.bind_texture(target, Some(&handle));
+
+  if let Err(e) = setup_texture::<D>(&mut state, target, size, mipmaps, pf, sampler) {
+    state.ctx.delete_texture(Some(&handle));
+    return Err(e);
+  }
+
+  let texture = Texture {
+    handle,
+    target,
+    mipmaps,
+    state: webgl2.state.clone(),
+  };
+
+  Ok(texture)
+}
+
+unsafe impl<D, P> TextureCopy<D, P> for WebGL2
+where
+  D: Dimensionable,
+  P: Pixel,
+  P::Encoding: IntoArrayBuffer,
+  P::RawEncoding: IntoArrayBuffer,
+{
+  unsafe fn copy_texture(
+    dst: &mut Self::TextureRepr,
+    src: &Self::TextureRepr,
+    src_level: usize,
+    src_offset: D::Offset,
+    dst_level: usize,
+    dst_offset: D::Offset,
+    size: D::Size,
+  ) -> Result<(), TextureError> {
+    check_mipmap_level(src, src_level, TextureError::CannotCopyTexels)?;
+    check_mipmap_level(dst, dst_level, TextureError::CannotCopyTexels)?;
+
+    // WebGL2 cannot copy from depth attachments
+    if P::pixel_format().is_depth_pixel() {
+      return Err(TextureError::cannot_copy_texels(
+        "copying depth textures is not supported by WebGL2",
+      ));
+    }
+
+    let mut state = dst.state.borrow_mut();
+    let readback_fb = state
+      .create_or_get_readback_framebuffer()
+      .ok_or_else(|| TextureError::cannot_copy_texels("cannot create the readback framebuffer"))?;
+
+    // WebGL2 has no glCopyImageSubData; attach each source layer to the readback framebuffer and
+    // copy from it
+    state.bind_read_framebuffer(Some(&readback_fb));
+    state.bind_texture(dst.target, Some(&dst.handle));
+    state
+      .ctx
+      .read_buffer(WebGl2RenderingContext::COLOR_ATTACHMENT0);
+
+    let [sx, sy, sz] = texture_offset::<D>(src_offset);
+    let [dx, dy, dz] = texture_offset::<D>(dst_offset);
+    let (w, h) = (D::width(size) as i32, D::height(size) as i32);
+    let src_level = src_level as i32;
+    let dst_level = dst_level as i32;
+
+    match D::dim() {
+      Dim::Dim2 => {
+        attach_read_layer::<D>(&state, src, src_level, 0);
+        state
+          .ctx
+          .copy_tex_sub_image_2d(dst.target, dst_level, dx, dy, sx, sy, w, h);
+      }
+
+      Dim::Cubemap => {
+        attach_read_layer::<D>(&state, src, src_level, sz);
+        state.ctx.copy_tex_sub_image_2d(
+          WebGl2RenderingContext::TEXTURE_CUBE_MAP_POSITIVE_X + dz as u32,
+          dst_level,
+          dx,
+          dy,
+          sx,
+          sy,
+          w,
+          h,
+        );
+      }
+
+      Dim::Dim3 | Dim::Dim2Array => {
+        for layer in 0..D::depth(size) as i32 {
+          attach_read_layer::<D>(&state, src, src_level, sz + layer);
+          state
+            .ctx
+            .copy_tex_sub_image_3d(dst.target, dst_level, dx, dy, dz + layer, sx, sy, w, h);
+        }
+      }
+
+      _ => {
+        return Err(TextureError::cannot_copy_texels(format!(
+          "unsupported texture dimension: {}",
+          D::dim()
+        )));
+      }
+    }
+
+    // detach the source texture so that it doesn’t stay referenced by the readback framebuffer
+    state.ctx.framebuffer_texture_2d(
+      WebGl2RenderingContext::READ_FRAMEBUFFER,
+      WebGl2RenderingContext::COLOR_ATTACHMENT0,
+      WebGl2RenderingContext::TEXTURE_2D,
+      None,
+      0,
+    );
+
+    Ok(())
+  }
+}
+
+unsafe impl<D, P, FD> TextureCopyFramebuffer<D, P, FD> for WebGL2
+where
+  D: Dimensionable,
+  P: Pixel,
+  P::Encoding: IntoArrayBuffer,
+  P::RawEncoding: IntoArrayBuffer,
+  FD: Dimensionable,
+{
+  unsafe fn copy_from_framebuffer(
+    dst: &mut Self::TextureRepr,
+    framebuffer: &Self::FramebufferRepr,
+    attachment_index: usize,
+    src_offset: [u32; 2],
+    dst_level: usize,
+    dst_offset: D::Offset,
+    size: [u32; 2],
+  ) -> Result<(), TextureError> {
+    check_mipmap_level(dst, dst_level, TextureError::CannotCopyTexels)?;
+
+    let read_buffer = match framebuffer.handle {
+      Some(_) => WebGl2RenderingContext::COLOR_ATTACHMENT0 + attachment_index as u32,
+
+      None if attachment_index == 0 => WebGl2RenderingContext::BACK,
+
+      None => {
+        return Err(TextureError::cannot_copy_texels(
+          "back buffers only have a single color attachment",
+        ));
+      }
+    };
+
+    let mut state = dst.state.borrow_mut();
+    state.bind_read_framebuffer(framebuffer.handle.as_ref());
+    state.bind_texture(dst.target, Some(&dst.handle));
+    state.ctx.read_buffer(read_buffer);
+
+    let [sx, sy] = [src_offset[0] as i32, src_offset[1] as i32];
+    let [dx, dy, dz] = texture_offset::<D>(dst_offset);
+    let [w, h] = [size[0] as i32, size[1] as i32];
+    let dst_level = dst_level as i32;
+
+    match D::dim() {
+      Dim::Dim2 => state
+        .ctx
+        .copy_tex_sub_image_2d(dst.target, dst_level, dx, dy, sx, sy, w, h),
+
+      Dim::Cubemap => state.ctx.copy_tex_sub_image_2d(
+        WebGl2RenderingContext::TEXTURE_CUBE_MAP_POSITIVE_X + dz as u32,
+        dst_level,
+        dx,
+        dy,
+        sx,
+        sy,
+        w,
+        h,
+      ),
+
+      Dim::Dim3 | Dim::Dim2Array => state
+        .ctx
+        .copy_tex_sub_image_3d(dst.target, dst_level, dx, dy, dz, sx, sy, w, h),
+
+      _ => {
+        return Err(TextureError::cannot_copy_texels(format!(
+          "unsupported texture dimension: {}",
+          D::dim()
+        )));
+      }
+    }
+
+    Ok(())
   }
 }
 
@@ -281,6 +636,8 @@ pub(crate) fn opengl_target(d: Dim) -> Option<u32> {
 }
 
 /// Set all the required internal state required for the texture to be valid.
+///
+/// The swizzle of `sampler` must be the identity, as WebGL2 has no texture swizzling.
 pub(crate) unsafe fn setup_texture<D>(
   state: &mut WebGL2State,
   target: u32,
@@ -293,7 +650,7 @@ where
   D: Dimensionable,
 {
   set_texture_levels(state, target, mipmaps);
-  apply_sampler_to_texture(state, target, sampler);
+  apply_sampler(state, SamplerTarget::Texture(target), sampler);
   create_texture_storage::<D>(state, size, mipmaps, pf)
 }
 
@@ -309,55 +666,112 @@ fn set_texture_levels(state: &mut WebGL2State, target: u32, mipmaps: usize) {
   );
 }
 
-fn apply_sampler_to_texture(state: &mut WebGL2State, target: u32, sampler: Sampler) {
-  state.ctx.tex_parameteri(
-    target,
+/// Object sampling parameters are applied to.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum SamplerTarget<'a> {
+  /// A texture, bound to the given target.
+  Texture(u32),
+  /// A standalone sampler object.
+  SamplerObject(&'a WebGlSampler),
+}
+
+impl<'a> SamplerTarget<'a> {
+  fn parameteri(self, ctx: &WebGl2RenderingContext, pname: u32, param: i32) {
+    match self {
+      SamplerTarget::Texture(target) => ctx.tex_parameteri(target, pname, param),
+      SamplerTarget::SamplerObject(handle) => ctx.sampler_parameteri(handle, pname, param),
+    }
+  }
+
+  fn parameterf(self, ctx: &WebGl2RenderingContext, pname: u32, param: f32) {
+    match self {
+      SamplerTarget::Texture(target) => ctx.tex_parameterf(target, pname, param),
+      SamplerTarget::SamplerObject(handle) => ctx.sampler_parameterf(handle, pname, param),
+    }
+  }
+}
+
+pub(crate) fn apply_sampler(state: &mut WebGL2State, target: SamplerTarget, sampler: Sampler) {
+  target.parameteri(
+    &state.ctx,
     WebGl2RenderingContext::TEXTURE_WRAP_R,
     webgl_wrap(sampler.wrap_r) as i32,
   );
-  state.ctx.tex_parameteri(
-    target,
+  target.parameteri(
+    &state.ctx,
     WebGl2RenderingContext::TEXTURE_WRAP_S,
     webgl_wrap(sampler.wrap_s) as i32,
   );
-  state.ctx.tex_parameteri(
-    target,
+  target.parameteri(
+    &state.ctx,
     WebGl2RenderingContext::TEXTURE_WRAP_T,
     webgl_wrap(sampler.wrap_t) as i32,
   );
-  state.ctx.tex_parameteri(
-    target,
+  target.parameteri(
+    &state.ctx,
     WebGl2RenderingContext::TEXTURE_MIN_FILTER,
     webgl_min_filter(sampler.min_filter) as i32,
   );
-  state.ctx.tex_parameteri(
-    target,
+  target.parameteri(
+    &state.ctx,
     WebGl2RenderingContext::TEXTURE_MAG_FILTER,
     webgl_mag_filter(sampler.mag_filter) as i32,
   );
 
   match sampler.depth_comparison {
     Some(fun) => {
-      state.ctx.tex_parameteri(
-        target,
+      target.parameteri(
+        &state.ctx,
         WebGl2RenderingContext::TEXTURE_COMPARE_FUNC,
         webgl_depth_comparison(fun) as i32,
       );
-      state.ctx.tex_parameteri(
-        target,
+      target.parameteri(
+        &state.ctx,
         WebGl2RenderingContext::TEXTURE_COMPARE_MODE,
         WebGl2RenderingContext::COMPARE_REF_TO_TEXTURE as i32,
       );
     }
 
     None => {
-      state.ctx.tex_parameteri(
-        target,
+      target.parameteri(
+        &state.ctx,
         WebGl2RenderingContext::TEXTURE_COMPARE_MODE,
         WebGl2RenderingContext::NONE as i32,
       );
     }
   }
+
+  target.parameterf(
+    &state.ctx,
+    WebGl2RenderingContext::TEXTURE_MIN_LOD,
+    sampler.min_lod,
+  );
+  target.parameterf(
+    &state.ctx,
+    WebGl2RenderingContext::TEXTURE_MAX_LOD,
+    sampler.max_lod,
+  );
+
+  // WebGL2 has neither LOD bias nor border colors as sampler state
+  if sampler.lod_bias != 0. {
+    log::warn!("LOD bias is not supported by WebGL2; ignoring lod_bias");
+  }
+
+  if let Some(anisotropy) = sampler.max_anisotropy {
+    match state.get_max_texture_max_anisotropy() {
+      Some(max) => {
+        target.parameterf(
+          &state.ctx,
+          TEXTURE_MAX_ANISOTROPY_EXT,
+          anisotropy.max(1.).min(max),
+        );
+      }
+
+      None => {
+        log::warn!("EXT_texture_filter_anisotropic is not available; ignoring max_anisotropy");
+      }
+    }
+  }
 }
 
 fn webgl_wrap(wrap: Wrap) -> u32 {
@@ -365,6 +779,18 @@ fn webgl_wrap(wrap: Wrap) -> u32 {
     Wrap::ClampToEdge => WebGl2RenderingContext::CLAMP_TO_EDGE,
     Wrap::Repeat => WebGl2RenderingContext::REPEAT,
     Wrap::MirroredRepeat => WebGl2RenderingContext::MIRRORED_REPEAT,
+    Wrap::ClampToBorder => {
+      log::warn!(
+        "clamp to border wrapping is not supported by WebGL2; using clamp to edge instead"
+      );
+      WebGl2RenderingContext::CLAMP_TO_EDGE
+    }
+    Wrap::MirrorClampToEdge => {
+      log::warn!(
+        "mirror clamp to edge wrapping is not supported by WebGL2; using clamp to edge instead"
+      );
+      WebGl2RenderingContext::CLAMP_TO_EDGE
+    }
   }
 }
 
@@ -523,7 +949,7 @@ fn set_unpack_alignment(state: &mut WebGL2State, skip_bytes: usize) {
 }
 
 // set the pack alignment for downloading aligned texels
-fn set_pack_alignment(state: &mut WebGL2State, skip_bytes: usize) {
+pub(crate) fn set_pack_alignment(state: &mut WebGL2State, skip_bytes: usize) {
   let pack_alignment = match skip_bytes {
     0 => 8,
     2 => 2,
@@ -536,21 +962,21 @@ fn set_pack_alignment(state: &mut WebGL2State, skip_bytes: usize) {
     .pixel_storei(WebGl2RenderingContext::PACK_ALIGNMENT, pack_alignment);
 }
 // Upload texels into the texture’s memory. Becareful of the type of texels you send down.
-fn upload_texels<D, P, T>(
+fn upload_texels<D, T>(
   state: &mut WebGL2State,
   target: u32,
+  level: i32,
   off: D::Offset,
   size: D::Size,
+  pf: PixelFormat,
   texels: &[T],
 ) -> Result<(), TextureError>
 where
   D: Dimensionable,
-  P: Pixel,
   T: IntoArrayBuffer,
 {
   // number of bytes in the input texels argument
   let input_bytes = texels.len() * mem::size_of::<T>();
-  let pf = P::pixel_format();
   let pf_size = pf.format.bytes_len();
   let expected_bytes = D::count(size) * pf_size;
 
@@ -579,7 +1005,7 @@ where
           .ctx
           .tex_sub_image_2d_with_i32_and_i32_and_u32_and_type_and_array_buffer_view_and_src_offset(
             target,
-            0,
+            level,
             D::x_offset(off) as i32,
             D::y_offset(off) as i32,
             D::width(size) as i32,
@@ -597,7 +1023,7 @@ where
           .ctx
           .tex_sub_image_3d_with_opt_array_buffer_view(
             target,
-            0,
+            level,
             D::x_offset(off) as i32,
             D::y_offset(off) as i32,
             D::z_offset(off) as i32,
@@ -616,7 +1042,7 @@ where
           .ctx
           .tex_sub_image_2d_with_i32_and_i32_and_u32_and_type_and_array_buffer_view_and_src_offset(
             WebGl2RenderingContext::TEXTURE_CUBE_MAP_POSITIVE_X + D::z_offset(off),
-            0,
+            level,
             D::x_offset(off) as i32,
             D::y_offset(off) as i32,
             D::width(size) as i32,
@@ -634,7 +1060,7 @@ where
           .ctx
           .tex_sub_image_3d_with_opt_array_buffer_view(
             target,
-            0,
+            level,
             D::x_offset(off) as i32,
             D::y_offset(off) as i32,
             D::z_offset(off) as i32,
@@ -665,6 +1091,208 @@ where
   D::width(size) * D::height(size) * D::depth(size)
 }
 
+/// Number of texels in a region of a texture.
+pub(crate) fn texels_part_len<D>(size: D::Size) -> usize
+where
+  D: Dimensionable,
+{
+  match D::dim() {
+    Dim::Dim3 | Dim::Dim2Array => {
+      D::width(size) as usize * D::height(size) as usize * D::depth(size) as usize
+    }
+    _ => D::width(size) as usize * D::height(size) as usize,
+  }
+}
+
+/// Destination of texels read back from the GPU.
+pub(crate) enum ReadPixelsDst<'a> {
+  /// Client memory.
+  Slice(&'a mut [u8]),
+  /// The buffer currently bound to `PIXEL_PACK_BUFFER`.
+  PackBuffer,
+}
+
+/// Read a rectangle of pixels from the current read framebuffer into `dst`, at `dst_offset` bytes.
+pub(crate) fn read_pixels(
+  state: &WebGL2State,
+  [x, y, w, h]: [i32; 4],
+  format: u32,
+  ty: u32,
+  dst: &mut ReadPixelsDst,
+  dst_offset: usize,
+) -> Result<(), TextureError> {
+  let r = match dst {
+    ReadPixelsDst::Slice(bytes) => state.ctx.read_pixels_with_u8_array_and_dst_offset(
+      x,
+      y,
+      w,
+      h,
+      format,
+      ty,
+      bytes,
+      dst_offset as u32,
+    ),
+    ReadPixelsDst::PackBuffer => {
+      state
+        .ctx
+        .read_pixels_with_i32(x, y, w, h, format, ty, dst_offset as i32)
+    }
+  };
+
+  r.map_err(|e| TextureError::CannotRetrieveTexels(format!("{:?}", e)))
+}
+
+/// Read a region of a texture’s mipmap `level` into `dst`.
+///
+/// `dst` must be large enough to hold [`texels_part_len`] texels of `texel_bytes` bytes each.
+pub(crate) fn read_texels_part<D, P>(
+  state: &mut WebGL2State,
+  texture: &Texture,
+  level: usize,
+  offset: D::Offset,
+  size: D::Size,
+  mut dst: ReadPixelsDst,
+) -> Result<(), TextureError>
+where
+  D: Dimensionable,
+  P: Pixel,
+{
+  check_mipmap_level(texture, level, TextureError::CannotRetrieveTexels)?;
+
+  let pf = P::pixel_format();
+  let texel_bytes = pf.raw_len() * mem::size_of::<P::RawEncoding>();
+
+  // WebGL2 cannot read from depth attachments
+  if pf.is_depth_pixel() {
+    return Err(TextureError::cannot_retrieve_texels(
+      "reading back depth textures is not supported by WebGL2",
+    ));
+  }
+
+  let (format, _, ty) = webgl_pixel_format(pf).ok_or(TextureError::UnsupportedPixelFormat(pf))?;
+
+  let [x, y, z] = texture_offset::<D>(offset);
+  let (w, h) = (D::width(size) as i32, D::height(size) as i32);
+  let level = level as i32;
+
+  // (number of layers, first layer)
+  let (layers, first_layer) = match D::dim() {
+    Dim::Dim2 => (1, 0),
+    Dim::Cubemap => (1, z),
+    Dim::Dim3 | Dim::Dim2Array => (D::depth(size) as i32, z),
+    _ => {
+      return Err(TextureError::cannot_retrieve_texels(format!(
+        "unsupported texture dimension: {}",
+        D::dim()
+      )));
+    }
+  };
+
+  // set the packing alignment based on the number of bytes to skip
+  let skip_bytes = (pf.format.bytes_len() * w as usize) % 8;
+  set_pack_alignment(state, skip_bytes);
+
+  let readback_fb = state
+    .create_or_get_readback_framebuffer()
+    .ok_or_else(|| TextureError::cannot_retrieve_texels("unavailable readback framebuffer"))?;
+  state.bind_read_framebuffer(Some(&readback_fb));
+  state
+    .ctx
+    .read_buffer(WebGl2RenderingContext::COLOR_ATTACHMENT0);
+
+  let layer_bytes = (w * h) as usize * texel_bytes;
+
+  for layer in 0..layers {
+    attach_read_layer::<D>(state, texture, level, first_layer + layer);
+    read_pixels(
+      state,
+      [x, y, w, h],
+      format,
+      ty,
+      &mut dst,
+      layer as usize * layer_bytes,
+    )?;
+  }
+
+  // detach the texture so that it doesn’t stay referenced by the readback framebuffer
+  state.ctx.framebuffer_texture_2d(
+    WebGl2RenderingContext::READ_FRAMEBUFFER,
+    WebGl2RenderingContext::COLOR_ATTACHMENT0,
+    WebGl2RenderingContext::TEXTURE_2D,
+    None,
+    0,
+  );
+
+  Ok(())
+}
+
+/// Attach a single layer of a texture’s mipmap `level` to the color attachment of the read
+/// framebuffer.
+///
+/// `layer` is ignored for 2D textures and is the face index for cubemaps.
+fn attach_read_layer<D>(state: &WebGL2State, texture: &Texture, level: i32, layer: i32)
+where
+  D: Dimensionable,
+{
+  match D::dim() {
+    Dim::Cubemap => state.ctx.framebuffer_texture_2d(
+      WebGl2RenderingContext::READ_FRAMEBUFFER,
+      WebGl2RenderingContext::COLOR_ATTACHMENT0,
+      WebGl2RenderingContext::TEXTURE_CUBE_MAP_POSITIVE_X + layer as u32,
+      Some(&texture.handle),
+      level,
+    ),
+
+    Dim::Dim3 | Dim::Dim2Array => state.ctx.framebuffer_texture_layer(
+      WebGl2RenderingContext::READ_FRAMEBUFFER,
+      WebGl2RenderingContext::COLOR_ATTACHMENT0,
+      Some(&texture.handle),
+      level,
+      layer,
+    ),
+
+    _ => state.ctx.framebuffer_texture_2d(
+      WebGl2RenderingContext::READ_FRAMEBUFFER,
+      WebGl2RenderingContext::COLOR_ATTACHMENT0,
+      texture.target,
+      Some(&texture.handle),
+      level,
+    ),
+  }
+}
+
+fn check_mipmap_level(
+  texture: &Texture,
+  level: usize,
+  error: fn(String) -> TextureError,
+) -> Result<(), TextureError> {
+  if level < texture.mipmaps {
+    Ok(())
+  } else {
+    Err(error(format!(
+      "mipmap level {} out of bounds (texture has {} levels)",
+      level, texture.mipmaps
+    )))
+  }
+}
+
+/// Texel offset as (x, y, z), where z might be a layer or a cubemap face, as expected by copy
+/// functions.
+fn texture_offset<D>(off: D::Offset) -> [i32; 3]
+where
+  D: Dimensionable,
+{
+  let x = D::x_offset(off) as i32;
+
+  match D::dim() {
+    Dim::Dim1 => [x, 0, 0],
+    Dim::Dim2 | Dim::Dim1Array => [x, D::y_offset(off) as i32, 0],
+    Dim::Dim3 | Dim::Cubemap | Dim::Dim2Array | Dim::CubemapArray => {
+      [x, D::y_offset(off) as i32, D::z_offset(off) as i32]
+    }
+  }
+}
+
 pub(crate) fn webgl_depth_comparison(dc: DepthComparison) -> u32 {
   match dc {
     DepthComparison::Never => WebGl2RenderingContext::NEVER,
@@ -0,0 +1,596 @@
+//! Texture atlases.
+//!
+//! A texture atlas packs many small images — glyphs, sprites, lightmaps, etc. — into a single
+//! [`Texture`], so that they can all be sampled without switching textures. An [`Atlas`] owns such a
+//! texture and allocates rectangles in it with a [`ShelfAllocator`]: each image is placed on a
+//! _shelf_, a horizontal band of the texture as high as the images it holds.
+//!
+//! Atlases can be made of a single [`Dim2`] texture or of a [`Dim2Array`] texture. In the latter
+//! case, the atlas grows by adding layers to its texture when no more room is available.
+//!
+//! Inserted images are identified by an [`AtlasId`] and their location in the atlas is given by
+//! an [`AtlasRegion`], which contains the normalized UV rectangle to use in shaders.
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+
+use crate::backend::texture::{Texture as TextureBackend, TextureCopy};
+use crate::context::GraphicsContext;
+use crate::pixel::Pixel;
+use crate::texture::{Dim2, Dim2Array, Dimensionable, GenMipmaps, Sampler, Texture, TextureError};
+
+/// Dimensions an [`Atlas`] can be made of.
+///
+/// An atlas texture is made of one or several 2D layers, in which images are packed.
+pub trait AtlasDimension: Dimensionable {
+  /// Whether more layers can be added to a texture of this dimension.
+  const GROWABLE: bool;
+
+  /// Size of a texture made of `layers` layers of `layer_size`.
+  fn atlas_size(layer_size: [u32; 2], layers: u32) -> Self::Size;
+
+  /// Offset of a texel in a given layer.
+  fn atlas_offset(offset: [u32; 2], layer: u32) -> Self::Offset;
+}
+
+impl AtlasDimension for Dim2 {
+  const GROWABLE: bool = false;
+
+  fn atlas_size(layer_size: [u32; 2], _: u32) -> Self::Size {
+    layer_size
+  }
+
+  fn atlas_offset(offset: [u32; 2], _: u32) -> Self::Offset {
+    offset
+  }
+}
+
+impl AtlasDimension for Dim2Array {
+  const GROWABLE: bool = true;
+
+  fn atlas_size(layer_size: [u32; 2], layers: u32) -> Self::Size {
+    (layer_size, layers)
+  }
+
+  fn atlas_offset(offset: [u32; 2], layer: u32) -> Self::Offset {
+    (offset, layer)
+  }
+}
+
+/// Possible errors that might occur while using an [`Atlas`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AtlasError {
+  /// An image is larger than a layer of the atlas.
+  TooLarge {
+    /// Size of the image.
+    size: [u32; 2],
+    /// Size of a layer of the atlas.
+    layer_size: [u32; 2],
+  },
+  /// An image has no texel, as one of its dimensions is zero.
+  EmptyImage {
+    /// Size of the image.
+    size: [u32; 2],
+  },
+  /// No more room is available in the atlas, and it cannot grow.
+  Full,
+  /// The texture of the atlas failed to be created or updated.
+  TextureError(TextureError),
+}
+
+impl AtlasError {
+  /// An image is larger than a layer of the atlas.
+  pub fn too_large(size: [u32; 2], layer_size: [u32; 2]) -> Self {
+    AtlasError::TooLarge { size, layer_size }
+  }
+
+  /// An image has no texel, as one of its dimensions is zero.
+  pub fn empty_image(size: [u32; 2]) -> Self {
+    AtlasError::EmptyImage { size }
+  }
+
+  /// No more room is available in the atlas, and it cannot grow.
+  pub fn full() -> Self {
+    AtlasError::Full
+  }
+}
+
+impl fmt::Display for AtlasError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      AtlasError::TooLarge { size, layer_size } => write!(
+        f,
+        "image too large for atlas: {}×{} (layers are {}×{})",
+        size[0], size[1], layer_size[0], layer_size[1]
+      ),
+
+      AtlasError::EmptyImage { size } => {
+        write!(
+          f,
+          "cannot insert empty image in atlas: {}×{}",
+          size[0], size[1]
+        )
+      }
+
+      AtlasError::Full => f.write_str("atlas is full"),
+
+      AtlasError::TextureError(ref e) => write!(f, "atlas texture error: {}", e),
+    }
+  }
+}
+
+impl error::Error for AtlasError {
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    match self {
+      AtlasError::TextureError(e) => Some(e),
+      _ => None,
+    }
+  }
+}
+
+impl From<TextureError> for AtlasError {
+  fn from(e: TextureError) -> Self {
+    AtlasError::TextureError(e)
+  }
+}
+
+/// Identifier of an image inserted in an [`Atlas`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AtlasId(u64);
+
+/// Location of an image in an [`Atlas`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AtlasRegion {
+  /// Layer of the atlas texture the image lies in. Always `0` for [`Dim2`] atlases.
+  pub layer: u32,
+  /// Offset, in texels, of the image in its layer.
+  pub offset: [u32; 2],
+  /// Size, in texels, of the image.
+  pub size: [u32; 2],
+  /// Normalized UV rectangle of the image, as `[u_min, v_min, u_max, v_max]`.
+  pub uv: [f32; 4],
+}
+
+impl AtlasRegion {
+  fn new(layer: u32, offset: [u32; 2], size: [u32; 2], layer_size: [u32; 2]) -> Self {
+    let [w, h] = [layer_size[0] as f32, layer_size[1] as f32];
+    let uv = [
+      offset[0] as f32 / w,
+      offset[1] as f32 / h,
+      (offset[0] + size[0]) as f32 / w,
+      (offset[1] + size[1]) as f32 / h,
+    ];
+
+    AtlasRegion {
+      layer,
+      offset,
+      size,
+      uv,
+    }
+  }
+}
+
+/// Rectangle allocator packing rectangles on shelves.
+///
+/// The allocated area is split in horizontal _shelves_, stacked from the top of the area. A
+/// rectangle is placed on the shelf wasting the least height, or on a new shelf — as high as the
+/// rectangle — if no existing shelf can hold it. Freed rectangles give their room back to their
+/// shelf, and empty shelves at the bottom of the area are removed.
+#[derive(Clone, Debug)]
+pub struct ShelfAllocator {
+  size: [u32; 2],
+  shelves: Vec<Shelf>,
+}
+
+#[derive(Clone, Debug)]
+struct Shelf {
+  y: u32,
+  height: u32,
+  // free horizontal spans, as (x, width), sorted by x and never adjacent
+  free: Vec<(u32, u32)>,
+}
+
+impl ShelfAllocator {
+  /// Create an allocator for an area of the given size.
+  pub fn new(size: [u32; 2]) -> Self {
+    ShelfAllocator {
+      size,
+      shelves: Vec::new(),
+    }
+  }
+
+  /// Size of the area.
+  pub fn size(&self) -> [u32; 2] {
+    self.size
+  }
+
+  /// Whether nothing is allocated.
+  pub fn is_empty(&self) -> bool {
+    self.shelves.is_empty()
+  }
+
+  /// Allocate a rectangle of the given size, returning its offset in the area if there is enough
+  /// room.
+  pub fn allocate(&mut self, size: [u32; 2]) -> Option<[u32; 2]> {
+    let [w, h] = size;
+
+    if w == 0 || h == 0 || w > self.size[0] || h > self.size[1] {
+      return None;
+    }
+
+    // best fit: the shelf wasting the least height
+    let best = self
+      .shelves
+      .iter()
+      .enumerate()
+      .filter(|(_, shelf)| shelf.height >= h)
+      .filter_map(|(i, shelf)| {
+        let span = shelf.free.iter().position(|&(_, span_w)| span_w >= w)?;
+        Some((shelf.height - h, i, span))
+      })
+      .min();
+
+    if let Some((_, i, span)) = best {
+      let shelf = &mut self.shelves[i];
+      let (x, span_w) = shelf.free[span];
+
+      if span_w == w {
+        shelf.free.remove(span);
+      } else {
+        shelf.free[span] = (x + w, span_w - w);
+      }
+
+      return Some([x, shelf.y]);
+    }
+
+    // open a new shelf
+    let y = self
+      .shelves
+      .last()
+      .map_or(0, |shelf| shelf.y + shelf.height);
+
+    if y + h > self.size[1] {
+      return None;
+    }
+
+    let free = if w < self.size[0] {
+      vec![(w, self.size[0] - w)]
+    } else {
+      Vec::new()
+    };
+
+    self.shelves.push(Shelf { y, height: h, free });
+
+    Some([0, y])
+  }
+
+  /// Free a rectangle previously returned by [`ShelfAllocator::allocate`].
+  ///
+  /// `size` must be the size the rectangle was allocated with.
+  pub fn deallocate(&mut self, offset: [u32; 2], size: [u32; 2]) {
+    let [x, y] = offset;
+    let w = size[0];
+
+    let shelf = match self.shelves.iter_mut().find(|shelf| shelf.y == y) {
+      Some(shelf) => shelf,
+      None => return,
+    };
+
+    // insert the span back and merge it with its neighbors
+    let i = shelf.free.partition_point(|&(span_x, _)| span_x < x);
+    shelf.free.insert(i, (x, w));
+
+    if i + 1 < shelf.free.len() && x + w == shelf.free[i + 1].0 {
+      shelf.free[i].1 += shelf.free[i + 1].1;
+      shelf.free.remove(i + 1);
+    }
+
+    if i > 0 && shelf.free[i - 1].0 + shelf.free[i - 1].1 == x {
+      shelf.free[i - 1].1 += shelf.free[i].1;
+      shelf.free.remove(i);
+    }
+
+    // drop the empty shelves at the bottom, so that their room can be reused by any height
+    let width = self.size[0];
+    while let Some(last) = self.shelves.last() {
+      if last.free == [(0, width)] {
+        self.shelves.pop();
+      } else {
+        break;
+      }
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Allocation {
+  layer: u32,
+  offset: [u32; 2],
+  size: [u32; 2],
+}
+
+/// A texture atlas.
+///
+/// # Parametricity
+///
+/// - `B` is the backend type. It must implement [`backend::texture::Texture`] and
+///   [`backend::texture::TextureCopy`].
+/// - `D` is the dimension of the atlas texture. It must implement [`AtlasDimension`].
+/// - `P` is the pixel type. It must implement [`Pixel`].
+///
+/// [`backend::texture::Texture`]: crate::backend::texture::Texture
+/// [`backend::texture::TextureCopy`]: crate::backend::texture::TextureCopy
+pub struct Atlas<B, D, P>
+where
+  B: ?Sized + TextureBackend<D, P>,
+  D: AtlasDimension,
+  P: Pixel,
+{
+  texture: Texture<B, D, P>,
+  sampler: Sampler,
+  layer_size: [u32; 2],
+  allocators: Vec<ShelfAllocator>,
+  allocations: HashMap<AtlasId, Allocation>,
+  next_id: u64,
+}
+
+impl<B, D, P> Atlas<B, D, P>
+where
+  B: ?Sized + TextureBackend<D, P> + TextureCopy<D, P>,
+  D: AtlasDimension,
+  P: Pixel,
+{
+  /// Create a new, empty [`Atlas`] of `layers` layers of `layer_size` texels.
+  ///
+  /// For [`Dim2`] atlases, `layers` is ignored.
+  pub fn new<C>(
+    ctx: &mut C,
+    layer_size: [u32; 2],
+    layers: u32,
+    sampler: Sampler,
+  ) -> Result<Self, AtlasError>
+  where
+    C: GraphicsContext<Backend = B>,
+  {
+    let layers = if D::GROWABLE { layers.max(1) } else { 1 };
+    let texture = Texture::new_no_texels(ctx, D::atlas_size(layer_size, layers), 0, sampler)?;
+
+    Ok(Atlas {
+      texture,
+      sampler,
+      layer_size,
+      allocators: (0..layers)
+        .map(|_| ShelfAllocator::new(layer_size))
+        .collect(),
+      allocations: HashMap::new(),
+      next_id: 0,
+    })
+  }
+
+  /// Access the texture of the atlas.
+  pub fn texture(&self) -> &Texture<B, D, P> {
+    &self.texture
+  }
+
+  /// Access the texture of the atlas mutably, to bind it in a pipeline for instance.
+  pub fn texture_mut(&mut self) -> &mut Texture<B, D, P> {
+    &mut self.texture
+  }
+
+  /// Size of a layer of the atlas.
+  pub fn layer_size(&self) -> [u32; 2] {
+    self.layer_size
+  }
+
+  /// Number of layers of the atlas.
+  pub fn layers(&self) -> u32 {
+    self.allocators.len() as u32
+  }
+
+  /// Number of images in the atlas.
+  pub fn len(&self) -> usize {
+    self.allocations.len()
+  }
+
+  /// Whether the atlas holds no image.
+  pub fn is_empty(&self) -> bool {
+    self.allocations.is_empty()
+  }
+
+  /// Insert an image of `size` texels into the atlas.
+  ///
+  /// If no room is available, [`Dim2Array`] atlases grow by a layer while [`Dim2`] atlases fail
+  /// with [`AtlasError::Full`]. Images with a zero dimension are rejected with
+  /// [`AtlasError::EmptyImage`], without growing the atlas.
+  pub fn insert<C>(
+    &mut self,
+    ctx: &mut C,
+    size: [u32; 2],
+    texels: &[P::Encoding],
+  ) -> Result<(AtlasId, AtlasRegion), AtlasError>
+  where
+    C: GraphicsContext<Backend = B>,
+  {
+    if size[0] == 0 || size[1] == 0 {
+      return Err(AtlasError::empty_image(size));
+    }
+
+    if size[0] > self.layer_size[0] || size[1] > self.layer_size[1] {
+      return Err(AtlasError::too_large(size, self.layer_size));
+    }
+
+    let (layer, offset) = match self.allocate(size) {
+      Some(allocation) => allocation,
+
+      None if D::GROWABLE => {
+        let layer = self.layers();
+        self.grow(ctx, 1)?;
+        let offset = self.allocators[layer as usize]
+          .allocate(size)
+          .ok_or_else(AtlasError::full)?;
+        (layer, offset)
+      }
+
+      None => return Err(AtlasError::full()),
+    };
+
+    let upload = self.texture.upload_part(
+      GenMipmaps::No,
+      D::atlas_offset(offset, layer),
+      D::atlas_size(size, 1),
+      texels,
+    );
+
+    if let Err(e) = upload {
+      self.allocators[layer as usize].deallocate(offset, size);
+      return Err(e.into());
+    }
+
+    let id = AtlasId(self.next_id);
+    self.next_id += 1;
+    self.allocations.insert(
+      id,
+      Allocation {
+        layer,
+        offset,
+        size,
+      },
+    );
+
+    Ok((id, AtlasRegion::new(layer, offset, size, self.layer_size)))
+  }
+
+  /// Get the location of an image in the atlas.
+  pub fn region(&self, id: AtlasId) -> Option<AtlasRegion> {
+    self
+      .allocations
+      .get(&id)
+      .map(|a| AtlasRegion::new(a.layer, a.offset, a.size, self.layer_size))
+  }
+
+  /// Remove an image from the atlas, returning the location it had.
+  ///
+  /// The texels of the image are left in the texture but its room can be reused by later
+  /// insertions.
+  pub fn remove(&mut self, id: AtlasId) -> Option<AtlasRegion> {
+    let a = self.allocations.remove(&id)?;
+    self.allocators[a.layer as usize].deallocate(a.offset, a.size);
+    Some(AtlasRegion::new(a.layer, a.offset, a.size, self.layer_size))
+  }
+
+  /// Add `layers` layers to the atlas.
+  ///
+  /// The atlas texture is re-created and its content copied on the GPU. [`Dim2`] atlases cannot
+  /// grow and fail with [`AtlasError::Full`].
+  pub fn grow<C>(&mut self, ctx: &mut C, layers: u32) -> Result<(), AtlasError>
+  where
+    C: GraphicsContext<Backend = B>,
+  {
+    if !D::GROWABLE {
+      return Err(AtlasError::full());
+    }
+
+    let old_layers = self.layers();
+    let mut texture = Texture::new_no_texels(
+      ctx,
+      D::atlas_size(self.layer_size, old_layers + layers),
+      0,
+      self.sampler,
+    )?;
+
+    texture.copy_from(
+      &self.texture,
+      0,
+      D::ZERO_OFFSET,
+      0,
+      D::ZERO_OFFSET,
+      D::atlas_size(self.layer_size, old_layers),
+    )?;
+
+    let layer_size = self.layer_size;
+    self.texture = texture;
+    self
+      .allocators
+      .extend((0..layers).map(|_| ShelfAllocator::new(layer_size)));
+
+    Ok(())
+  }
+
+  /// Repack all the images of the atlas to reclaim the room lost to removals.
+  ///
+  /// Images are sorted by decreasing height and packed again into a new texture, to which their
+  /// texels are copied on the GPU. Their [`AtlasId`]s remain valid, but their [`AtlasRegion`]s
+  /// change and must be fetched again with [`Atlas::region`]. If the images cannot be repacked
+  /// into the current layers, the atlas is left untouched.
+  pub fn defragment<C>(&mut self, ctx: &mut C) -> Result<(), AtlasError>
+  where
+    C: GraphicsContext<Backend = B>,
+  {
+    let mut ids = self.allocations.keys().copied().collect::<Vec<_>>();
+    ids.sort_by_key(|id| {
+      let size = self.allocations[id].size;
+      (std::cmp::Reverse(size[1]), std::cmp::Reverse(size[0]), id.0)
+    });
+
+    let mut allocators = vec![ShelfAllocator::new(self.layer_size); self.allocators.len()];
+    let mut moves = Vec::with_capacity(ids.len());
+
+    for id in ids {
+      let old = self.allocations[&id];
+      let new = allocators
+        .iter_mut()
+        .enumerate()
+        .find_map(|(layer, allocator)| {
+          allocator.allocate(old.size).map(|offset| Allocation {
+            layer: layer as u32,
+            offset,
+            size: old.size,
+          })
+        })
+        .ok_or_else(AtlasError::full)?;
+
+      moves.push((id, old, new));
+    }
+
+    let mut texture = Texture::new_no_texels(
+      ctx,
+      D::atlas_size(self.layer_size, self.layers()),
+      0,
+      self.sampler,
+    )?;
+
+    for &(_, old, new) in &moves {
+      texture.copy_from(
+        &self.texture,
+        0,
+        D::atlas_offset(old.offset, old.layer),
+        0,
+        D::atlas_offset(new.offset, new.layer),
+        D::atlas_size(old.size, 1),
+      )?;
+    }
+
+    self.texture = texture;
+    self.allocators = allocators;
+
+    for (id, _, new) in moves {
+      self.allocations.insert(id, new);
+    }
+
+    Ok(())
+  }
+
+  fn allocate(&mut self, size: [u32; 2]) -> Option<(u32, [u32; 2])> {
+    self
+      .allocators
+      .iter_mut()
+      .enumerate()
+      .find_map(|(layer, allocator)| {
+        allocator
+          .allocate(size)
+          .map(|offset| (layer as u32, offset))
+      })
+  }
+}
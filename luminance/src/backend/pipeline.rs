@@ -5,7 +5,7 @@
 use crate::backend::{
   framebuffer::Framebuffer as FramebufferBackend,
   shading_gate::ShadingGate as ShadingGateBackend,
-  texture::{Texture, TextureBase},
+  texture::{BufferTexture, DynTexture, SamplerObject, Texture, TextureBase},
 };
 use crate::pipeline::{PipelineError, PipelineState};
 use crate::pixel::Pixel;
@@ -45,3 +45,44 @@ where
 
   unsafe fn texture_binding(bound: &Self::BoundTextureRepr) -> u32;
 }
+
+pub unsafe trait PipelineTextureSampler<D, P>:
+  PipelineTexture<D, P> + SamplerObject
+where
+  D: Dimensionable,
+  P: Pixel,
+{
+  unsafe fn bind_texture_with_sampler(
+    pipeline: &Self::PipelineRepr,
+    texture: &Self::TextureRepr,
+    sampler: &Self::SamplerObjectRepr,
+  ) -> Result<Self::BoundTextureRepr, PipelineError>;
+}
+
+pub unsafe trait PipelineBufferTexture<P>: PipelineBase + BufferTexture<P>
+where
+  P: Pixel,
+{
+  type BoundBufferTextureRepr;
+
+  unsafe fn bind_buffer_texture(
+    pipeline: &Self::PipelineRepr,
+    texture: &Self::BufferTextureRepr,
+  ) -> Result<Self::BoundBufferTextureRepr, PipelineError>;
+
+  unsafe fn buffer_texture_binding(bound: &Self::BoundBufferTextureRepr) -> u32;
+}
+
+pub unsafe trait PipelineDynTexture<D>: PipelineBase + DynTexture<D>
+where
+  D: Dimensionable,
+{
+  type BoundDynTextureRepr;
+
+  unsafe fn bind_dyn_texture(
+    pipeline: &Self::PipelineRepr,
+    texture: &Self::TextureRepr,
+  ) -> Result<Self::BoundDynTextureRepr, PipelineError>;
+
+  unsafe fn dyn_texture_binding(bound: &Self::BoundDynTextureRepr) -> u32;
+}
@@ -2,9 +2,10 @@
 //!
 //! This interface defines the low-level API shaders must implement to be usable.
 
+use crate::shader::reflection::{ActiveAttribute, ActiveUniform, ActiveUniformBlock};
 use crate::shader::{
-  ProgramError, StageError, StageType, TessellationStages, Uniform, UniformType, UniformWarning,
-  VertexAttribWarning,
+  GlslTarget, ProgramError, StageError, StageType, TessellationStages, Uniform, UniformCacheStats,
+  UniformType, UniformWarning, VertexAttribWarning,
 };
 use crate::vertex::Semantics;
 
@@ -55,3 +56,86 @@ pub unsafe trait Shader {
   where
     T: Uniformable<Self>;
 }
+
+/// Shader program binaries.
+///
+/// Backends implementing this trait can retrieve the binary of a linked program and create a
+/// program back from such a binary, skipping compilation. Backends that cannot do it at runtime —
+/// because the driver doesn’t support any binary format, for instance — must return [`None`] from
+/// both [`ShaderBinary::program_binary`] and [`ShaderBinary::new_program_from_binary`].
+pub unsafe trait ShaderBinary: Shader {
+  /// Prelude prepended to the sources of stages — `#version`, extensions, etc.
+  ///
+  /// It is part of the hash identifying the sources of a cached binary, so that enabling an
+  /// extension invalidates the binaries built without it.
+  fn source_prelude() -> String;
+
+  /// Hint that the binary of a program will be retrieved.
+  ///
+  /// This is called right before [`Shader::apply_semantics`] links the program again.
+  unsafe fn set_binary_retrievable(program: &mut Self::ProgramRepr);
+
+  /// Retrieve the binary of a linked program, along with its backend-specific format.
+  unsafe fn program_binary(program: &Self::ProgramRepr) -> Option<(u32, Vec<u8>)>;
+
+  /// Create a program from a binary retrieved with [`ShaderBinary::program_binary`].
+  ///
+  /// The vertex semantics the binary was built with are already applied to the returned program.
+  /// Return [`None`] if the binary is rejected.
+  unsafe fn new_program_from_binary(
+    &mut self,
+    format: u32,
+    binary: &[u8],
+  ) -> Option<Self::ProgramRepr>;
+}
+
+/// Shader program reflection.
+pub unsafe trait ShaderReflection: Shader {
+  /// Active uniforms of a program, uniform block members excluded.
+  unsafe fn active_uniforms(program: &Self::ProgramRepr) -> Vec<ActiveUniform>;
+
+  /// Active uniform blocks of a program.
+  unsafe fn active_uniform_blocks(program: &Self::ProgramRepr) -> Vec<ActiveUniformBlock>;
+
+  /// Active vertex attributes of a program.
+  unsafe fn active_attributes(program: &Self::ProgramRepr) -> Vec<ActiveAttribute>;
+}
+
+/// Uniform value caching.
+///
+/// Backends implementing this trait can remember, per program, the last value uploaded to each
+/// uniform, and skip [`Uniformable::update`] uploads of a value the uniform already has.
+pub unsafe trait ShaderUniformCache: Shader {
+  /// Enable or disable the uniform value cache of a program.
+  ///
+  /// Disabling the cache drops the cached values and the statistics.
+  unsafe fn set_uniform_cache(program: &mut Self::ProgramRepr, enabled: bool);
+
+  /// Statistics of the uniform value cache of a program, or [`None`] if it’s disabled.
+  unsafe fn uniform_cache_stats(program: &Self::ProgramRepr) -> Option<UniformCacheStats>;
+
+  /// Reset the statistics of the uniform value cache of a program.
+  unsafe fn reset_uniform_cache_stats(program: &mut Self::ProgramRepr);
+}
+
+/// SPIR-V stages.
+///
+/// Backends implementing this trait can tell whether they consume SPIR-V modules directly and
+/// which GLSL flavor their stages are written in, so that SPIR-V modules can be cross-compiled for
+/// them otherwise.
+pub unsafe trait ShaderSpirv: Shader {
+  /// Whether stages can be created from SPIR-V modules with
+  /// [`ShaderSpirv::new_stage_from_spirv`].
+  unsafe fn supports_spirv(&mut self) -> bool;
+
+  /// Create a stage from a SPIR-V module, using the `entry_point` function as entry point.
+  unsafe fn new_stage_from_spirv(
+    &mut self,
+    ty: StageType,
+    module: &[u8],
+    entry_point: &str,
+  ) -> Result<Self::StageRepr, StageError>;
+
+  /// GLSL flavor the stages of the backend are written in.
+  unsafe fn glsl_target(&mut self) -> GlslTarget;
+}
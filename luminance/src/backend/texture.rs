@@ -2,7 +2,8 @@
 //!
 //! This interface defines the low-level API textures must implement to be usable.
 
-use crate::pixel::Pixel;
+use crate::backend::framebuffer::Framebuffer;
+use crate::pixel::{Pixel, PixelFormat};
 use crate::texture::{Dimensionable, GenMipmaps, Sampler, TextureError};
 
 /// The base texture trait.
@@ -69,6 +70,22 @@ where
     texels: &[P::RawEncoding],
   ) -> Result<(), TextureError>;
 
+  unsafe fn upload_part_level(
+    texture: &mut Self::TextureRepr,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+    texels: &[P::Encoding],
+  ) -> Result<(), TextureError>;
+
+  unsafe fn upload_part_level_raw(
+    texture: &mut Self::TextureRepr,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+    texels: &[P::RawEncoding],
+  ) -> Result<(), TextureError>;
+
   unsafe fn get_raw_texels(
     texture: &Self::TextureRepr,
     size: D::Size,
@@ -76,9 +93,199 @@ where
   where
     P::RawEncoding: Copy + Default;
 
+  unsafe fn get_raw_texels_part(
+    texture: &Self::TextureRepr,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+  ) -> Result<Vec<P::RawEncoding>, TextureError>
+  where
+    P::RawEncoding: Copy + Default;
+
   unsafe fn resize(
     texture: &mut Self::TextureRepr,
     size: D::Size,
     mipmaps: usize,
   ) -> Result<(), TextureError>;
 }
+
+/// Standalone sampler objects.
+///
+/// Such objects hold a [`Sampler`] independently from any texture and can be bound along with a
+/// texture to override its own sampling parameters.
+pub unsafe trait SamplerObject {
+  type SamplerObjectRepr;
+
+  unsafe fn new_sampler_object(
+    &mut self,
+    sampler: Sampler,
+  ) -> Result<Self::SamplerObjectRepr, TextureError>;
+}
+
+/// GPU-side copies between textures.
+pub unsafe trait TextureCopy<D, P>: Texture<D, P>
+where
+  D: Dimensionable,
+  P: Pixel,
+{
+  unsafe fn copy_texture(
+    dst: &mut Self::TextureRepr,
+    src: &Self::TextureRepr,
+    src_level: usize,
+    src_offset: D::Offset,
+    dst_level: usize,
+    dst_offset: D::Offset,
+    size: D::Size,
+  ) -> Result<(), TextureError>;
+}
+
+/// GPU-side copies from framebuffers’ color attachments to textures.
+pub unsafe trait TextureCopyFramebuffer<D, P, FD>: Texture<D, P> + Framebuffer<FD>
+where
+  D: Dimensionable,
+  P: Pixel,
+  FD: Dimensionable,
+{
+  unsafe fn copy_from_framebuffer(
+    dst: &mut Self::TextureRepr,
+    framebuffer: &Self::FramebufferRepr,
+    attachment_index: usize,
+    src_offset: [u32; 2],
+    dst_level: usize,
+    dst_offset: D::Offset,
+    size: [u32; 2],
+  ) -> Result<(), TextureError>;
+}
+
+/// The base pending readback trait.
+pub unsafe trait PendingReadbackBase {
+  type PendingReadbackRepr;
+}
+
+/// Asynchronous texel readbacks that have been started but not retrieved yet.
+pub unsafe trait PendingReadback<P>: PendingReadbackBase
+where
+  P: Pixel,
+{
+  unsafe fn try_get_readback(
+    pending: &mut Self::PendingReadbackRepr,
+  ) -> Result<Option<Vec<P::RawEncoding>>, TextureError>
+  where
+    P::RawEncoding: Copy + Default;
+
+  unsafe fn wait_readback(
+    pending: &mut Self::PendingReadbackRepr,
+  ) -> Result<Vec<P::RawEncoding>, TextureError>
+  where
+    P::RawEncoding: Copy + Default;
+}
+
+/// Asynchronous readback of textures’ texels.
+pub unsafe trait TextureReadback<D, P>: Texture<D, P> + PendingReadback<P>
+where
+  D: Dimensionable,
+  P: Pixel,
+{
+  unsafe fn start_texture_readback(
+    texture: &Self::TextureRepr,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+  ) -> Result<Self::PendingReadbackRepr, TextureError>;
+}
+
+/// Asynchronous readback of framebuffers’ color attachments.
+pub unsafe trait FramebufferReadback<FD, P>: Framebuffer<FD> + PendingReadback<P>
+where
+  FD: Dimensionable,
+  P: Pixel,
+{
+  unsafe fn start_framebuffer_readback(
+    framebuffer: &Self::FramebufferRepr,
+    attachment_index: usize,
+    offset: [u32; 2],
+    size: [u32; 2],
+  ) -> Result<Self::PendingReadbackRepr, TextureError>;
+}
+
+/// Bindless textures.
+///
+/// A texture made bindless is accessed in shaders via a 64-bit handle instead of a texture unit.
+/// The handle can be used in shaders only while it is _resident_.
+pub unsafe trait TextureBindless<D, P>: Texture<D, P>
+where
+  D: Dimensionable,
+  P: Pixel,
+{
+  /// Get the bindless handle of a texture.
+  ///
+  /// Once a handle is obtained, the sampling parameters of the texture cannot change anymore.
+  unsafe fn bindless_handle(texture: &mut Self::TextureRepr) -> Result<u64, TextureError>;
+
+  /// Make the bindless handle of a texture resident or non-resident.
+  unsafe fn set_bindless_residency(
+    texture: &mut Self::TextureRepr,
+    resident: bool,
+  ) -> Result<(), TextureError>;
+}
+
+/// Buffer textures.
+///
+/// Such textures are one-dimensional arrays of texels backed by a GPU buffer, which shaders access
+/// by index, without sampling.
+pub unsafe trait BufferTexture<P>
+where
+  P: Pixel,
+{
+  type BufferTextureRepr;
+
+  unsafe fn new_buffer_texture(
+    &mut self,
+    texels: &[P::Encoding],
+  ) -> Result<Self::BufferTextureRepr, TextureError>;
+
+  unsafe fn new_buffer_texture_raw(
+    &mut self,
+    texels: &[P::RawEncoding],
+  ) -> Result<Self::BufferTextureRepr, TextureError>;
+
+  unsafe fn upload_buffer_texture_part(
+    texture: &mut Self::BufferTextureRepr,
+    offset: usize,
+    texels: &[P::Encoding],
+  ) -> Result<(), TextureError>;
+
+  unsafe fn upload_buffer_texture_part_raw(
+    texture: &mut Self::BufferTextureRepr,
+    offset: usize,
+    texels: &[P::RawEncoding],
+  ) -> Result<(), TextureError>;
+}
+
+/// Textures which pixel format is only known at runtime.
+///
+/// Texels are passed as raw bytes, laid out as the [`Pixel::RawEncoding`] of the pixel type
+/// matching the pixel format.
+pub unsafe trait DynTexture<D>: TextureBase
+where
+  D: Dimensionable,
+{
+  unsafe fn new_dyn_texture(
+    &mut self,
+    size: D::Size,
+    mipmaps: usize,
+    sampler: Sampler,
+    pixel_format: PixelFormat,
+  ) -> Result<Self::TextureRepr, TextureError>;
+
+  unsafe fn dyn_mipmaps(texture: &Self::TextureRepr) -> usize;
+
+  unsafe fn upload_dyn_part_raw(
+    texture: &mut Self::TextureRepr,
+    pixel_format: PixelFormat,
+    gen_mipmaps: GenMipmaps,
+    offset: D::Offset,
+    size: D::Size,
+    texels: &[u8],
+  ) -> Result<(), TextureError>;
+}
@@ -44,17 +44,27 @@
 //! ```
 
 use crate::backend::{
-  color_slot::ColorSlot, depth_slot::DepthSlot, framebuffer::Framebuffer as FramebufferBackend,
-  query::Query as QueryBackend, shader::Shader, tess::Tess as TessBackend,
-  texture::Texture as TextureBackend,
+  color_slot::ColorSlot,
+  depth_slot::DepthSlot,
+  framebuffer::Framebuffer as FramebufferBackend,
+  query::Query as QueryBackend,
+  shader::Shader,
+  tess::Tess as TessBackend,
+  texture::{
+    BufferTexture as BufferTextureBackend, DynTexture as DynTextureBackend,
+    SamplerObject as SamplerObjectBackend, Texture as TextureBackend,
+  },
 };
 use crate::framebuffer::{Framebuffer, FramebufferError};
 use crate::pipeline::PipelineGate;
-use crate::pixel::Pixel;
+use crate::pixel::{Pixel, PixelFormat};
 use crate::query::Query;
 use crate::shader::{ProgramBuilder, Stage, StageError, StageType};
 use crate::tess::{Deinterleaved, Interleaved, TessBuilder, TessVertexData};
-use crate::texture::{Dimensionable, GenMipmaps, Sampler, Texture, TextureError};
+use crate::texture::{
+  BufferTexture, Dimensionable, DynTexture, GenMipmaps, Sampler, SamplerObject, Texture,
+  TextureError,
+};
 use crate::vertex::Semantics;
 
 /// Class of graphics context.
@@ -202,4 +212,88 @@ pub unsafe trait GraphicsContext: Sized {
   {
     Texture::new_raw(self, size, mipmaps, sampler, gen_mipmaps, texels)
   }
+
+  /// Create a new texture which pixel format is only known at runtime by reserving space for
+  /// texels.
+  ///
+  /// Feel free to have a look at the documentation of [`DynTexture::new`] for further details.
+  fn new_dyn_texture<D>(
+    &mut self,
+    size: D::Size,
+    mipmaps: usize,
+    sampler: Sampler,
+    pixel_format: PixelFormat,
+  ) -> Result<DynTexture<Self::Backend, D>, TextureError>
+  where
+    Self::Backend: DynTextureBackend<D>,
+    D: Dimensionable,
+  {
+    DynTexture::new(self, size, mipmaps, sampler, pixel_format)
+  }
+
+  /// Create a new texture which pixel format is only known at runtime from raw texels.
+  ///
+  /// Feel free to have a look at the documentation of [`DynTexture::new_raw`] for further details.
+  fn new_dyn_texture_raw<D>(
+    &mut self,
+    size: D::Size,
+    mipmaps: usize,
+    sampler: Sampler,
+    pixel_format: PixelFormat,
+    gen_mipmaps: GenMipmaps,
+    texels: &[u8],
+  ) -> Result<DynTexture<Self::Backend, D>, TextureError>
+  where
+    Self::Backend: DynTextureBackend<D>,
+    D: Dimensionable,
+  {
+    DynTexture::new_raw(
+      self,
+      size,
+      mipmaps,
+      sampler,
+      pixel_format,
+      gen_mipmaps,
+      texels,
+    )
+  }
+
+  /// Create a new standalone sampler object.
+  ///
+  /// Feel free to have a look at the documentation of [`SamplerObject::new`] for further details.
+  fn new_sampler(&mut self, sampler: Sampler) -> Result<SamplerObject<Self::Backend>, TextureError>
+  where
+    Self::Backend: SamplerObjectBackend,
+  {
+    SamplerObject::new(self, sampler)
+  }
+
+  /// Create a new buffer texture from texels.
+  ///
+  /// Feel free to have a look at the documentation of [`BufferTexture::new`] for further details.
+  fn new_buffer_texture<P>(
+    &mut self,
+    texels: &[P::Encoding],
+  ) -> Result<BufferTexture<Self::Backend, P>, TextureError>
+  where
+    Self::Backend: BufferTextureBackend<P>,
+    P: Pixel,
+  {
+    BufferTexture::new(self, texels)
+  }
+
+  /// Create a new buffer texture from raw texels.
+  ///
+  /// Feel free to have a look at the documentation of [`BufferTexture::new_raw`] for further
+  /// details.
+  fn new_buffer_texture_raw<P>(
+    &mut self,
+    texels: &[P::RawEncoding],
+  ) -> Result<BufferTexture<Self::Backend, P>, TextureError>
+  where
+    Self::Backend: BufferTextureBackend<P>,
+    P: Pixel,
+  {
+    BufferTexture::new_raw(self, texels)
+  }
 }
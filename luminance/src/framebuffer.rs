@@ -69,8 +69,10 @@ use std::fmt;
 use crate::backend::color_slot::ColorSlot;
 use crate::backend::depth_slot::DepthSlot;
 use crate::backend::framebuffer::{Framebuffer as FramebufferBackend, FramebufferBackBuffer};
+use crate::backend::texture::FramebufferReadback;
 use crate::context::GraphicsContext;
-use crate::texture::{Dim2, Dimensionable, Sampler, TextureError};
+use crate::pixel::Pixel;
+use crate::texture::{Dim2, Dimensionable, PendingReadback, Sampler, TextureError};
 
 /// Typed framebuffers.
 ///
@@ -159,6 +161,28 @@ where
     &mut self.depth_slot
   }
 
+  /// Start reading back a rectangular region of a color attachment, without waiting for the GPU.
+  ///
+  /// The region starting at `offset` and spanning `size` in the color attachment at
+  /// `attachment_index` is read back as pixels of type `P`, converting them if needed. For back
+  /// buffers, `attachment_index` must be `0`. The texels are retrieved later via the returned
+  /// [`PendingReadback`].
+  pub fn read_color_async<P>(
+    &self,
+    attachment_index: usize,
+    offset: [u32; 2],
+    size: [u32; 2],
+  ) -> Result<PendingReadback<B, P>, TextureError>
+  where
+    B: FramebufferReadback<D, P>,
+    P: Pixel,
+  {
+    unsafe {
+      B::start_framebuffer_readback(&self.repr, attachment_index, offset, size)
+        .map(PendingReadback::from_repr)
+    }
+  }
+
   /// Consume this framebuffer and return the carried slots.
   pub fn into_slots(self) -> (CS::ColorTextures, DS::DepthTexture) {
     (self.color_slot, self.depth_slot)
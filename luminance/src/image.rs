@@ -0,0 +1,627 @@
+//! [image] crate integration.
+//!
+//! This module, only available with the `image` feature, allows to create 2D [`Texture`]s directly
+//! from [`DynamicImage`]s and [`ImageBuffer`]s.
+//!
+//! The pixel type of the texture is picked by the caller and must implement [`ImagePixel`], which
+//! maps it to the [`ColorType`] of the images it can be created from. The color type of the image
+//! is then validated against it. Selecting between linear and sRGB textures is done by picking the
+//! pixel type: an `Rgb8` image can be used to create both [`NormRGB8UI`] and [`SRGB8UI`] textures,
+//! for instance.
+//!
+//! # Saving textures and framebuffers
+//!
+//! The other way around, the base level of 2D [`Texture`]s can be read back as [`DynamicImage`]s
+//! with [`Texture::to_image`], or saved to image files with [`Texture::save_image`]. The color
+//! attachments of framebuffers, including back buffers, can be dumped the same way with
+//! [`Framebuffer::color_to_image`] and [`Framebuffer::save_color`]. Texels read back otherwise,
+//! for instance asynchronously, can be converted with [`texels_to_image`].
+//!
+//! Texels are converted according to the [`PixelFormat`] of the texture:
+//!
+//! - Normalized 8-bit formats give 8-bit images and other normalized formats give 16-bit images.
+//!   Signed channels are remapped from `[-1; 1]` to `[0; 1]`.
+//! - Integral formats are normalized over the full range of their channel type — from its minimum
+//!   to its maximum for signed ones — so that dumps of different textures can be compared. 8-bit
+//!   formats give 8-bit images and other integral formats give 16-bit images.
+//! - Floating formats — including depth formats, visualized as grayscale — give floating images.
+//!   Single and dual channel floating formats are expanded to RGB.
+//!
+//! Rows are flipped so that the top-left corner of the image is the top-left corner of the texture.
+//! When saving, the file format is guessed from the extension of the path, and floating images are
+//! converted to 16-bit for file formats that cannot store them, such as PNG. Encoding PNG and EXR
+//! files requires the `png` and `exr` features, respectively.
+//!
+//! [image]: https://crates.io/crates/image
+//! [`DynamicImage`]: https://docs.rs/image/0.24/image/enum.DynamicImage.html
+//! [`ImageBuffer`]: https://docs.rs/image/0.24/image/struct.ImageBuffer.html
+//! [`ColorType`]: https://docs.rs/image/0.24/image/enum.ColorType.html
+
+use ::image::{ColorType, DynamicImage, ImageBuffer, ImageFormat, PixelWithColorType};
+use std::convert::TryInto;
+use std::error;
+use std::fmt;
+use std::mem;
+use std::ops::Deref;
+use std::path::Path;
+use std::slice;
+
+use crate::backend::color_slot::ColorSlot;
+use crate::backend::depth_slot::DepthSlot;
+use crate::backend::framebuffer::Framebuffer as FramebufferBackend;
+use crate::backend::texture::FramebufferReadback;
+use crate::backend::texture::Texture as TextureBackend;
+use crate::context::GraphicsContext;
+use crate::framebuffer::Framebuffer;
+use crate::pixel::{
+  Format, NormR16UI, NormR8UI, NormRG16UI, NormRG8UI, NormRGB16UI, NormRGB8UI, NormRGBA16UI,
+  NormRGBA8UI, Pixel, PixelFormat, Size, Type, RGB32F, RGBA32F, SRGB8UI, SRGBA8UI,
+};
+use crate::texture::{Dim2, GenMipmaps, Sampler, Texture, TextureError};
+
+/// Pixel types textures can be created from images with.
+///
+/// The texels of an image of [`ImagePixel::COLOR_TYPE`] are uploaded as-is to textures of this
+/// pixel type.
+///
+/// # Safety
+///
+/// The subpixels of images of [`ImagePixel::COLOR_TYPE`] are reinterpreted as a slice of
+/// [`Pixel::RawEncoding`] without any check. Implementors must guarantee that:
+///
+/// - [`Pixel::RawEncoding`] has the same size and alignment as the subpixel type of the color
+///   type — for instance `u8` for [`ColorType::Rgba8`] or `f32` for [`ColorType::Rgb32F`].
+/// - The number of channels of the pixel type is the number of channels of the color type, in the
+///   same order, so that the texels of an image map one-to-one to texels of the texture.
+/// - Every bit pattern of the subpixel type is a valid value of [`Pixel::RawEncoding`].
+pub unsafe trait ImagePixel: Pixel {
+  /// Color type of the images textures of this pixel type can be created from.
+  const COLOR_TYPE: ColorType;
+}
+
+macro_rules! impl_ImagePixel {
+  ($($t:ty => $color_type:ident),*) => {
+    $(
+      unsafe impl ImagePixel for $t {
+        const COLOR_TYPE: ColorType = ColorType::$color_type;
+      }
+    )*
+  };
+}
+
+impl_ImagePixel!(
+  NormR8UI => L8,
+  NormRG8UI => La8,
+  NormRGB8UI => Rgb8,
+  NormRGBA8UI => Rgba8,
+  SRGB8UI => Rgb8,
+  SRGBA8UI => Rgba8,
+  NormR16UI => L16,
+  NormRG16UI => La16,
+  NormRGB16UI => Rgb16,
+  NormRGBA16UI => Rgba16,
+  RGB32F => Rgb32F,
+  RGBA32F => Rgba32F
+);
+
+/// Options used to create a [`Texture`] from an image.
+#[derive(Clone, Copy, Debug)]
+pub struct ImageOptions {
+  /// Number of extra mipmaps to allocate with the texture.
+  pub mipmaps: usize,
+  /// Sampler of the texture.
+  pub sampler: Sampler,
+  /// Whether mipmaps should be generated.
+  pub gen_mipmaps: GenMipmaps,
+  /// Whether the rows of the image should be flipped.
+  ///
+  /// Images are stored from their top row to their bottom one while textures coordinates start
+  /// at the bottom. Flipping makes the image appear upright when sampled with `(0, 0)` being the
+  /// bottom-left corner.
+  pub flip_vertically: bool,
+}
+
+impl Default for ImageOptions {
+  /// Default [`ImageOptions`]:
+  ///
+  /// - `mipmaps`: `0`
+  /// - `sampler`: [`Sampler::default`]
+  /// - `gen_mipmaps`: [`GenMipmaps::No`]
+  /// - `flip_vertically`: `false`
+  fn default() -> Self {
+    ImageOptions {
+      mipmaps: 0,
+      sampler: Sampler::default(),
+      gen_mipmaps: GenMipmaps::No,
+      flip_vertically: false,
+    }
+  }
+}
+
+/// Errors that might happen when creating a [`Texture`] from an image.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImageTextureError {
+  /// The color type of the image doesn’t match the pixel type of the texture.
+  IncompatibleColorType {
+    /// Color type of the image.
+    color_type: ColorType,
+    /// Pixel format of the texture.
+    pixel_format: PixelFormat,
+  },
+  /// Texels of this pixel format cannot be converted to an image.
+  UnsupportedPixelFormat(PixelFormat),
+  /// The image failed to be saved.
+  CannotSave(String),
+  /// The texture failed to be created or read back.
+  TextureError(TextureError),
+}
+
+impl ImageTextureError {
+  /// The color type of the image doesn’t match the pixel type of the texture.
+  pub fn incompatible_color_type(color_type: ColorType, pixel_format: PixelFormat) -> Self {
+    ImageTextureError::IncompatibleColorType {
+      color_type,
+      pixel_format,
+    }
+  }
+
+  /// Texels of this pixel format cannot be converted to an image.
+  pub fn unsupported_pixel_format(pixel_format: PixelFormat) -> Self {
+    ImageTextureError::UnsupportedPixelFormat(pixel_format)
+  }
+
+  /// The image failed to be saved.
+  pub fn cannot_save(reason: impl Into<String>) -> Self {
+    ImageTextureError::CannotSave(reason.into())
+  }
+}
+
+impl fmt::Display for ImageTextureError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      ImageTextureError::IncompatibleColorType {
+        color_type,
+        pixel_format,
+      } => write!(
+        f,
+        "image color type {:?} incompatible with pixel format {:?}",
+        color_type, pixel_format
+      ),
+
+      ImageTextureError::UnsupportedPixelFormat(pixel_format) => write!(
+        f,
+        "cannot convert texels of pixel format {:?} to an image",
+        pixel_format
+      ),
+
+      ImageTextureError::CannotSave(ref reason) => write!(f, "cannot save image: {}", reason),
+
+      ImageTextureError::TextureError(ref e) => write!(f, "texture error: {}", e),
+    }
+  }
+}
+
+impl error::Error for ImageTextureError {
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    match self {
+      ImageTextureError::TextureError(e) => Some(e),
+      _ => None,
+    }
+  }
+}
+
+impl From<TextureError> for ImageTextureError {
+  fn from(e: TextureError) -> Self {
+    ImageTextureError::TextureError(e)
+  }
+}
+
+impl<B, P> Texture<B, Dim2, P>
+where
+  B: ?Sized + TextureBackend<Dim2, P>,
+  P: ImagePixel,
+{
+  /// Create a new [`Texture`] from an [`ImageBuffer`].
+  ///
+  /// The color type of the image must be [`ImagePixel::COLOR_TYPE`].
+  ///
+  /// [`ImageBuffer`]: https://docs.rs/image/0.24/image/struct.ImageBuffer.html
+  pub fn from_image_buffer<C, Px, Container>(
+    ctx: &mut C,
+    image: &ImageBuffer<Px, Container>,
+    options: ImageOptions,
+  ) -> Result<Self, ImageTextureError>
+  where
+    C: GraphicsContext<Backend = B>,
+    Px: PixelWithColorType,
+    Container: Deref<Target = [Px::Subpixel]>,
+  {
+    if Px::COLOR_TYPE != P::COLOR_TYPE {
+      return Err(ImageTextureError::incompatible_color_type(
+        Px::COLOR_TYPE,
+        P::pixel_format(),
+      ));
+    }
+
+    let (width, height) = image.dimensions();
+    let row_len = width as usize * Px::CHANNEL_COUNT as usize;
+
+    // the container might be longer than the image
+    let raw = &image.as_raw()[..row_len * height as usize];
+
+    let flipped;
+    let subpixels: &[Px::Subpixel] = if options.flip_vertically {
+      flipped = raw
+        .chunks(row_len.max(1))
+        .rev()
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>();
+      &flipped
+    } else {
+      raw
+    };
+
+    // the color types match, so subpixels have the same layout as the raw encoding of P
+    let texels = unsafe {
+      slice::from_raw_parts(
+        subpixels.as_ptr() as *const P::RawEncoding,
+        mem::size_of_val(subpixels) / mem::size_of::<P::RawEncoding>(),
+      )
+    };
+
+    let texture = Texture::new_raw(
+      ctx,
+      [width, height],
+      options.mipmaps,
+      options.sampler,
+      options.gen_mipmaps,
+      texels,
+    )?;
+
+    Ok(texture)
+  }
+
+  /// Create a new [`Texture`] from a [`DynamicImage`].
+  ///
+  /// The color type of the image must be [`ImagePixel::COLOR_TYPE`]; no conversion is performed.
+  ///
+  /// [`DynamicImage`]: https://docs.rs/image/0.24/image/enum.DynamicImage.html
+  pub fn from_image<C>(
+    ctx: &mut C,
+    image: &DynamicImage,
+    options: ImageOptions,
+  ) -> Result<Self, ImageTextureError>
+  where
+    C: GraphicsContext<Backend = B>,
+  {
+    match image {
+      DynamicImage::ImageLuma8(image) => Self::from_image_buffer(ctx, image, options),
+      DynamicImage::ImageLumaA8(image) => Self::from_image_buffer(ctx, image, options),
+      DynamicImage::ImageRgb8(image) => Self::from_image_buffer(ctx, image, options),
+      DynamicImage::ImageRgba8(image) => Self::from_image_buffer(ctx, image, options),
+      DynamicImage::ImageLuma16(image) => Self::from_image_buffer(ctx, image, options),
+      DynamicImage::ImageLumaA16(image) => Self::from_image_buffer(ctx, image, options),
+      DynamicImage::ImageRgb16(image) => Self::from_image_buffer(ctx, image, options),
+      DynamicImage::ImageRgba16(image) => Self::from_image_buffer(ctx, image, options),
+      DynamicImage::ImageRgb32F(image) => Self::from_image_buffer(ctx, image, options),
+      DynamicImage::ImageRgba32F(image) => Self::from_image_buffer(ctx, image, options),
+      _ => Err(ImageTextureError::incompatible_color_type(
+        image.color(),
+        P::pixel_format(),
+      )),
+    }
+  }
+}
+
+impl<B, P> Texture<B, Dim2, P>
+where
+  B: ?Sized + TextureBackend<Dim2, P>,
+  P: Pixel,
+  P::RawEncoding: Copy + Default,
+{
+  /// Read back the texels of the base level of the [`Texture`] as a [`DynamicImage`].
+  ///
+  /// See the [module documentation](crate::image#saving-textures-and-framebuffers) for how texels
+  /// are converted.
+  ///
+  /// [`DynamicImage`]: https://docs.rs/image/0.24/image/enum.DynamicImage.html
+  pub fn to_image(&self) -> Result<DynamicImage, ImageTextureError> {
+    let texels = self.get_raw_texels()?;
+    texels_to_image::<P>(self.size(), &texels)
+  }
+
+  /// Save the base level of the [`Texture`] to an image file.
+  ///
+  /// The file format is guessed from the extension of `path`.
+  pub fn save_image(&self, path: impl AsRef<Path>) -> Result<(), ImageTextureError> {
+    save_image(&self.to_image()?, path.as_ref())
+  }
+}
+
+impl<B, CS, DS> Framebuffer<B, Dim2, CS, DS>
+where
+  B: ?Sized + FramebufferBackend<Dim2>,
+  CS: ColorSlot<B, Dim2>,
+  DS: DepthSlot<B, Dim2>,
+{
+  /// Read back the color attachment at `attachment_index` as a [`DynamicImage`], as pixels of
+  /// type `P`.
+  ///
+  /// For back buffers, `attachment_index` must be `0`. This waits for the GPU to be done
+  /// rendering to the framebuffer.
+  ///
+  /// [`DynamicImage`]: https://docs.rs/image/0.24/image/enum.DynamicImage.html
+  pub fn color_to_image<P>(
+    &self,
+    attachment_index: usize,
+  ) -> Result<DynamicImage, ImageTextureError>
+  where
+    B: FramebufferReadback<Dim2, P>,
+    P: Pixel,
+    P::RawEncoding: Copy + Default,
+  {
+    let size = self.size();
+    let texels = self
+      .read_color_async::<P>(attachment_index, [0, 0], size)?
+      .wait()?;
+    texels_to_image::<P>(size, &texels)
+  }
+
+  /// Save the color attachment at `attachment_index` to an image file, as pixels of type `P`.
+  ///
+  /// The file format is guessed from the extension of `path`.
+  pub fn save_color<P>(
+    &self,
+    attachment_index: usize,
+    path: impl AsRef<Path>,
+  ) -> Result<(), ImageTextureError>
+  where
+    B: FramebufferReadback<Dim2, P>,
+    P: Pixel,
+    P::RawEncoding: Copy + Default,
+  {
+    save_image(&self.color_to_image::<P>(attachment_index)?, path.as_ref())
+  }
+}
+
+/// Bit depth of the channels of the images texels are converted to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Depth {
+  Eight,
+  Sixteen,
+  Float,
+}
+
+/// Convert raw texels of a 2D texture of size `[width, height]` to a [`DynamicImage`].
+///
+/// Texels are expected to be stored from the bottom row to the top one, as returned by
+/// [`Texture::get_raw_texels`] or [`PendingReadback::wait`].
+///
+/// [`DynamicImage`]: https://docs.rs/image/0.24/image/enum.DynamicImage.html
+/// [`PendingReadback::wait`]: crate::texture::PendingReadback::wait
+pub fn texels_to_image<P>(
+  [width, height]: [u32; 2],
+  texels: &[P::RawEncoding],
+) -> Result<DynamicImage, ImageTextureError>
+where
+  P: Pixel,
+{
+  let pf = P::pixel_format();
+  let raw_size = mem::size_of::<P::RawEncoding>();
+  let bytes =
+    unsafe { slice::from_raw_parts(texels.as_ptr() as *const u8, mem::size_of_val(texels)) };
+
+  let mut channels = decode_channels(pf, bytes, raw_size)
+    .ok_or_else(|| ImageTextureError::unsupported_pixel_format(pf))?;
+  normalize_channels(pf, &mut channels);
+
+  let depth =
+    if pf.encoding == Type::Floating || matches!(pf.format, Format::RGBE(..) | Format::Depth(_)) {
+      Depth::Float
+    } else if raw_size == 1 {
+      Depth::Eight
+    } else {
+      Depth::Sixteen
+    };
+
+  // textures start at the bottom row; images at the top one
+  let channels_len = pf.channels_len();
+  let row_len = width as usize * channels_len;
+  let channels = channels
+    .chunks(row_len.max(1))
+    .rev()
+    .flatten()
+    .copied()
+    .collect::<Vec<_>>();
+
+  let image = match depth {
+    Depth::Eight => {
+      let values = channels
+        .iter()
+        .map(|&x| (x.clamp(0., 1.) * 255.).round() as u8)
+        .collect();
+      match channels_len {
+        1 => ImageBuffer::from_raw(width, height, values).map(DynamicImage::ImageLuma8),
+        2 => ImageBuffer::from_raw(width, height, values).map(DynamicImage::ImageLumaA8),
+        3 => ImageBuffer::from_raw(width, height, values).map(DynamicImage::ImageRgb8),
+        _ => ImageBuffer::from_raw(width, height, values).map(DynamicImage::ImageRgba8),
+      }
+    }
+
+    Depth::Sixteen => {
+      let values = channels
+        .iter()
+        .map(|&x| (x.clamp(0., 1.) * 65535.).round() as u16)
+        .collect();
+      match channels_len {
+        1 => ImageBuffer::from_raw(width, height, values).map(DynamicImage::ImageLuma16),
+        2 => ImageBuffer::from_raw(width, height, values).map(DynamicImage::ImageLumaA16),
+        3 => ImageBuffer::from_raw(width, height, values).map(DynamicImage::ImageRgb16),
+        _ => ImageBuffer::from_raw(width, height, values).map(DynamicImage::ImageRgba16),
+      }
+    }
+
+    // there are no single and dual channel floating images, so expand them to RGB and RGBA
+    Depth::Float => match channels_len {
+      1 => {
+        let values = channels.iter().flat_map(|&x| [x, x, x]).collect();
+        ImageBuffer::from_raw(width, height, values).map(DynamicImage::ImageRgb32F)
+      }
+      2 => {
+        let values = channels
+          .chunks(2)
+          .flat_map(|rg| [rg[0], rg[1], 0.])
+          .collect();
+        ImageBuffer::from_raw(width, height, values).map(DynamicImage::ImageRgb32F)
+      }
+      3 => ImageBuffer::from_raw(width, height, channels).map(DynamicImage::ImageRgb32F),
+      _ => ImageBuffer::from_raw(width, height, channels).map(DynamicImage::ImageRgba32F),
+    },
+  };
+
+  image.ok_or_else(|| ImageTextureError::unsupported_pixel_format(pf))
+}
+
+// decode the channels of raw texels as floating values
+//
+// normalized channels are mapped to [0; 1] ([-1; 1] for signed ones, remapped afterwards);
+// integral channels are mapped to [0; 1] over the full range of their type
+fn decode_channels(pf: PixelFormat, bytes: &[u8], raw_size: usize) -> Option<Vec<f32>> {
+  let raws = bytes.chunks_exact(raw_size);
+
+  match pf.format {
+    Format::RGBA(Size::Ten, Size::Ten, Size::Ten, Size::Two) => {
+      let scale = [1023., 1023., 1023., 3.];
+
+      Some(
+        raws
+          .map(|raw| u32::from_ne_bytes(raw.try_into().unwrap()))
+          .flat_map(|v| {
+            [
+              (v & 0x3ff) as f32 / scale[0],
+              ((v >> 10) & 0x3ff) as f32 / scale[1],
+              ((v >> 20) & 0x3ff) as f32 / scale[2],
+              (v >> 30) as f32 / scale[3],
+            ]
+          })
+          .collect(),
+      )
+    }
+
+    Format::RGBE(Size::Nine, Size::Nine, Size::Nine, Size::Five) => Some(
+      raws
+        .map(|raw| u32::from_ne_bytes(raw.try_into().unwrap()))
+        .flat_map(|v| {
+          let scale = 2f32.powi((v >> 27) as i32 - 15 - 9);
+          [
+            (v & 0x1ff) as f32 * scale,
+            ((v >> 9) & 0x1ff) as f32 * scale,
+            ((v >> 18) & 0x1ff) as f32 * scale,
+          ]
+        })
+        .collect(),
+    ),
+
+    Format::RGBE(..) => None,
+
+    _ => {
+      let decode: fn(&[u8]) -> f32 = match (pf.encoding, raw_size) {
+        (Type::Floating, 4) => |raw| f32::from_ne_bytes(raw.try_into().unwrap()),
+        (Type::Floating, 2) => |raw| f16_to_f32(u16::from_ne_bytes(raw.try_into().unwrap())),
+        (Type::NormUnsigned, 1) => |raw| raw[0] as f32 / u8::MAX as f32,
+        (Type::NormUnsigned, 2) => {
+          |raw| u16::from_ne_bytes(raw.try_into().unwrap()) as f32 / u16::MAX as f32
+        }
+        (Type::NormUnsigned, 4) => {
+          |raw| u32::from_ne_bytes(raw.try_into().unwrap()) as f32 / u32::MAX as f32
+        }
+        (Type::NormIntegral, 1) => |raw| (raw[0] as i8 as f32 / i8::MAX as f32).max(-1.),
+        (Type::NormIntegral, 2) => {
+          |raw| (i16::from_ne_bytes(raw.try_into().unwrap()) as f32 / i16::MAX as f32).max(-1.)
+        }
+        (Type::NormIntegral, 4) => {
+          |raw| (i32::from_ne_bytes(raw.try_into().unwrap()) as f32 / i32::MAX as f32).max(-1.)
+        }
+        (Type::Unsigned, 1) => |raw| raw[0] as f32 / u8::MAX as f32,
+        (Type::Unsigned, 2) => {
+          |raw| u16::from_ne_bytes(raw.try_into().unwrap()) as f32 / u16::MAX as f32
+        }
+        (Type::Unsigned, 4) => {
+          |raw| u32::from_ne_bytes(raw.try_into().unwrap()) as f32 / u32::MAX as f32
+        }
+        (Type::Integral, 1) => |raw| (raw[0] as i8 as f32 - i8::MIN as f32) / u8::MAX as f32,
+        (Type::Integral, 2) => |raw| {
+          (i16::from_ne_bytes(raw.try_into().unwrap()) as f32 - i16::MIN as f32) / u16::MAX as f32
+        },
+        (Type::Integral, 4) => |raw| {
+          (i32::from_ne_bytes(raw.try_into().unwrap()) as f64 - i32::MIN as f64) as f32
+            / u32::MAX as f32
+        },
+        _ => return None,
+      };
+
+      Some(raws.map(decode).collect())
+    }
+  }
+}
+
+// bring decoded channels to [0; 1], so that they can be visualized
+fn normalize_channels(pf: PixelFormat, channels: &mut [f32]) {
+  if pf.encoding == Type::NormIntegral {
+    for x in channels {
+      *x = (*x + 1.) * 0.5;
+    }
+  }
+}
+
+// IEEE 754 half-precision to single-precision conversion
+fn f16_to_f32(h: u16) -> f32 {
+  let sign = ((h as u32) & 0x8000) << 16;
+  let exp = ((h >> 10) & 0x1f) as u32;
+  let mantissa = (h & 0x3ff) as u32;
+
+  let bits = match exp {
+    // zero and subnormals
+    0 => {
+      let x = mantissa as f32 * 2f32.powi(-24);
+      return if sign == 0 { x } else { -x };
+    }
+
+    // infinities and NaNs
+    0x1f => sign | 0x7f80_0000 | (mantissa << 13),
+
+    _ => sign | ((exp + 127 - 15) << 23) | (mantissa << 13),
+  };
+
+  f32::from_bits(bits)
+}
+
+// save an image, converting it to what the file format supports
+fn save_image(image: &DynamicImage, path: &Path) -> Result<(), ImageTextureError> {
+  let format =
+    ImageFormat::from_path(path).map_err(|e| ImageTextureError::cannot_save(e.to_string()))?;
+  let is_float = matches!(
+    image,
+    DynamicImage::ImageRgb32F(_) | DynamicImage::ImageRgba32F(_)
+  );
+  let has_alpha = image.color().has_alpha();
+
+  let converted = match format {
+    ImageFormat::OpenExr if !is_float && has_alpha => {
+      Some(DynamicImage::ImageRgba32F(image.to_rgba32f()))
+    }
+    ImageFormat::OpenExr if !is_float => Some(DynamicImage::ImageRgb32F(image.to_rgb32f())),
+    ImageFormat::OpenExr => None,
+    _ if is_float && has_alpha => Some(DynamicImage::ImageRgba16(image.to_rgba16())),
+    _ if is_float => Some(DynamicImage::ImageRgb16(image.to_rgb16())),
+    _ => None,
+  };
+
+  converted
+    .as_ref()
+    .unwrap_or(image)
+    .save_with_format(path, format)
+    .map_err(|e| ImageTextureError::cannot_save(e.to_string()))
+}
@@ -21,7 +21,13 @@
 //!
 //! # Feature flags
 //!
-//! None so far.
+//! - `derive`, enabled by default: re-export the procedural macros of [luminance-derive].
+//! - `half`: enable the half-precision floating-point pixel formats, such as `RGBA16F`, which are
+//!   encoded with the `f16` type of the [half] crate.
+//! - `image`: create 2D textures directly from the images of the [image] crate, and read textures
+//!   and framebuffers back as images; see the [`image`](crate::image) module.
+//! - `png` and `exr`: enable `image` along with the PNG and OpenEXR encoders, to save textures and
+//!   framebuffers to files of these formats.
 //!
 //! # What’s included?
 //!
@@ -312,10 +318,64 @@
 //! }
 //! ```
 //!
+//! GLSL structs and arrays of structs are mapped with the `#[uniform(flatten)]` attribute, on a
+//! field which type is another [`UniformInterface`] or an array of them. The nested interface is
+//! built with the name of the field as prefix, so that its fields are looked up as `light.color`,
+//! or `lights[3].color` for arrays:
+//!
+//! ```
+//! # use luminance::shader::Uniform;
+//! # use luminance_derive::UniformInterface;
+//!
+//! #[derive(Debug, UniformInterface)]
+//! struct Light {
+//!   color: Uniform<[f32; 4]>,
+//!   intensity: Uniform<f32>,
+//! }
+//!
+//! #[derive(Debug, UniformInterface)]
+//! struct MyIface {
+//!   #[uniform(flatten)]
+//!   sun: Light, // maps "sun.color" and "sun.intensity"
+//!   #[uniform(flatten)]
+//!   lights: [Light; 4], // maps "lights[0].color" to "lights[3].intensity"
+//! }
+//! ```
+//!
+//! Flattened fields cannot be `unbound`; mark the fields of the nested interface instead.
+//!
+//! ### GLSL declarations
+//!
+//! Both the [`Semantics`] and [`UniformInterface`] derive proc-macros also implement traits
+//! generating the matching GLSL declarations — [`SemanticsDeclarations`] and
+//! [`UniformDeclarations`] — based on the field types and on the `name` attributes. With the
+//! `MyIface` above, `uniform float time;` and `uniform vec4 res;` would be generated. Build your
+//! shader programs with [`ProgramBuilder::from_strings_with_declarations`] to have those
+//! declarations prepended to your stages instead of writing them by hand. Flattened fields are
+//! not declared, as the GLSL struct they map to must be declared in the sources.
+//!
+//! Slice uniforms, such as `Uniform<&'static [[f32; 4]]>`, are typed after their elements, so the
+//! length of the GLSL array they map to must be given with the `len` attribute to declare them.
+//! Slice uniforms without it are not declared, and must be declared in the sources:
+//!
+//! ```
+//! # use luminance::shader::Uniform;
+//! # use luminance_derive::UniformInterface;
+//!
+//! #[derive(Debug, UniformInterface)]
+//! struct MyIface {
+//!   #[uniform(len = 8)]
+//!   lights: Uniform<&'static [[f32; 4]]>, // declared as "uniform vec4 lights[8];"
+//! }
+//! ```
+//!
 //!
 //! [luminance]: https://crates.io/crates/luminance
 //! [luminance-gl]: https://crates.io/crates/luminance-gl
 //! [luminance-front]: https://crates.io/crates/luminance-front
+//! [luminance-derive]: https://crates.io/crates/luminance-derive
+//! [half]: https://crates.io/crates/half
+//! [image]: https://crates.io/crates/image
 //! [luminance::backend]: crate::backend
 //! [`Semantics`]: https://docs.rs/luminance/latest/luminance/vertex/trait.Semantics.html
 //! [`HasSemantics`]: https://docs.rs/luminance/latest/luminance/vertex/trait.HasSemantics.html
@@ -324,6 +384,9 @@
 //! [`VertexAttrib`]: https://docs.rs/luminance/latest/luminance/vertex/trait.VertexAttrib.html
 //! [`VertexInstancing`]: https://docs.rs/luminance/latest/luminance/vertex/enum.VertexInstancing.html
 //! [`UniformInterface`]: https://docs.rs/luminance/latest/luminance/shader/program/trait.UniformInterface.html
+//! [`SemanticsDeclarations`]: crate::vertex::SemanticsDeclarations
+//! [`UniformDeclarations`]: crate::shader::UniformDeclarations
+//! [`ProgramBuilder::from_strings_with_declarations`]: crate::shader::ProgramBuilder::from_strings_with_declarations
 
 #![doc(
   html_logo_url = "https://github.com/phaazon/luminance-rs/blob/master/docs/imgs/luminance_alt.svg"
@@ -333,12 +396,15 @@
 #[cfg(feature = "derive")]
 pub use luminance_derive::*;
 
+pub mod atlas;
 pub mod backend;
 pub mod blending;
 pub mod context;
 pub mod depth_test;
 pub mod face_culling;
 pub mod framebuffer;
+#[cfg(feature = "image")]
+pub mod image;
 pub mod pipeline;
 pub mod pixel;
 pub mod query;
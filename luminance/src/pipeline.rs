@@ -233,24 +233,71 @@ use crate::{
     color_slot::ColorSlot,
     depth_slot::DepthSlot,
     framebuffer::Framebuffer as FramebufferBackend,
-    pipeline::{Pipeline as PipelineBackend, PipelineBase, PipelineTexture},
+    pipeline::{
+      Pipeline as PipelineBackend, PipelineBase, PipelineBufferTexture, PipelineDynTexture,
+      PipelineTexture, PipelineTextureSampler,
+    },
   },
   context::GraphicsContext,
   framebuffer::Framebuffer,
-  pixel::Pixel,
+  pixel::{DepthPixel, Pixel, PixelFormat, SamplerType, Shadow, Type},
   scissor::ScissorRegion,
   shading_gate::ShadingGate,
-  texture::{Dimensionable, Texture},
+  texture::{BufferTexture, Dim, Dimensionable, DynTexture, SamplerObject, Texture},
 };
 
 /// Possible errors that might occur in a graphics [`Pipeline`].
 #[non_exhaustive]
 #[derive(Debug, Eq, PartialEq)]
-pub enum PipelineError {}
+pub enum PipelineError {
+  /// A [`DynTexture`] cannot be sampled with the requested sampler type.
+  IncompatibleSamplerType {
+    /// Pixel format of the texture.
+    pixel_format: PixelFormat,
+    /// Requested sampler type.
+    sampler_type: Type,
+  },
+  /// A texture cannot be sampled with depth comparison, as its sampler doesn’t have
+  /// [`Sampler::depth_comparison`] set.
+  ///
+  /// [`Sampler::depth_comparison`]: crate::texture::Sampler::depth_comparison
+  DepthComparisonDisabled,
+}
+
+impl PipelineError {
+  /// A [`DynTexture`] cannot be sampled with the requested sampler type.
+  pub fn incompatible_sampler_type(pixel_format: PixelFormat, sampler_type: Type) -> Self {
+    PipelineError::IncompatibleSamplerType {
+      pixel_format,
+      sampler_type,
+    }
+  }
+
+  /// A texture cannot be sampled with depth comparison, as its sampler doesn’t have
+  /// [`Sampler::depth_comparison`] set.
+  ///
+  /// [`Sampler::depth_comparison`]: crate::texture::Sampler::depth_comparison
+  pub fn depth_comparison_disabled() -> Self {
+    PipelineError::DepthComparisonDisabled
+  }
+}
 
 impl fmt::Display for PipelineError {
-  fn fmt(&self, _: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-    Ok(())
+  fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      PipelineError::IncompatibleSamplerType {
+        pixel_format,
+        sampler_type,
+      } => write!(
+        f,
+        "cannot sample texture of pixel format {:?} with a {:?} sampler",
+        pixel_format, sampler_type
+      ),
+
+      PipelineError::DepthComparisonDisabled => {
+        f.write_str("cannot sample texture with depth comparison, as its sampler has it disabled")
+      }
+    }
   }
 }
 
@@ -436,6 +483,77 @@ where
     unsafe {
       B::bind_texture(&self.repr, &texture.repr).map(|repr| BoundTexture {
         repr,
+        depth_comparison: texture.sampler().depth_comparison.is_some(),
+        _phantom: PhantomData,
+      })
+    }
+  }
+
+  /// Bind a texture along with a [`SamplerObject`].
+  ///
+  /// The texture will be sampled with the parameters of `sampler` instead of the ones it was
+  /// created with, for as long as the [`BoundTexture`] lives. The texture itself is left untouched.
+  ///
+  /// Once the texture is bound, the [`BoundTexture`] object has to be dropped / die in order to
+  /// bind the texture again.
+  pub fn bind_texture_with_sampler<D, P>(
+    &'a self,
+    texture: &'a mut Texture<B, D, P>,
+    sampler: &'a SamplerObject<B>,
+  ) -> Result<BoundTexture<'a, B, D, P>, PipelineError>
+  where
+    B: PipelineTextureSampler<D, P>,
+    D: Dimensionable,
+    P: Pixel,
+  {
+    unsafe {
+      B::bind_texture_with_sampler(&self.repr, &texture.repr, &sampler.repr).map(|repr| {
+        BoundTexture {
+          repr,
+          depth_comparison: sampler.sampler().depth_comparison.is_some(),
+          _phantom: PhantomData,
+        }
+      })
+    }
+  }
+
+  /// Bind a buffer texture.
+  ///
+  /// Once the buffer texture is bound, the [`BoundBufferTexture`] object has to be dropped / die in
+  /// order to bind the buffer texture again.
+  pub fn bind_buffer_texture<P>(
+    &'a self,
+    texture: &'a mut BufferTexture<B, P>,
+  ) -> Result<BoundBufferTexture<'a, B, P>, PipelineError>
+  where
+    B: PipelineBufferTexture<P>,
+    P: Pixel,
+  {
+    unsafe {
+      B::bind_buffer_texture(&self.repr, &texture.repr).map(|repr| BoundBufferTexture {
+        repr,
+        _phantom: PhantomData,
+      })
+    }
+  }
+
+  /// Bind a texture which pixel format is only known at runtime.
+  ///
+  /// Once the texture is bound, the [`BoundDynTexture`] object has to be dropped / die in order to
+  /// bind the texture again.
+  pub fn bind_dyn_texture<D>(
+    &'a self,
+    texture: &'a mut DynTexture<B, D>,
+  ) -> Result<BoundDynTexture<'a, B, D>, PipelineError>
+  where
+    B: PipelineDynTexture<D>,
+    D: Dimensionable,
+  {
+    unsafe {
+      B::bind_dyn_texture(&self.repr, &texture.repr).map(|repr| BoundDynTexture {
+        repr,
+        pixel_format: texture.pixel_format(),
+        depth_comparison: texture.sampler().depth_comparison.is_some(),
         _phantom: PhantomData,
       })
     }
@@ -664,6 +782,8 @@ where
   P: Pixel,
 {
   pub(crate) repr: B::BoundTextureRepr,
+  // whether the texture is sampled with depth comparison
+  depth_comparison: bool,
   _phantom: PhantomData<&'a ()>,
 }
 
@@ -687,4 +807,191 @@ where
       _phantom: PhantomData,
     }
   }
+
+  /// Obtain a [`TextureBinding`] object that can be used to refer to this bound depth texture in
+  /// shader stages through a shadow sampler, such as `sampler2DShadow`.
+  ///
+  /// Fail with [`PipelineError::DepthComparisonDisabled`] if the texture is not sampled with
+  /// [`Sampler::depth_comparison`] set, and with [`PipelineError::IncompatibleSamplerType`] for 3D
+  /// textures, which have no shadow sampler.
+  ///
+  /// [`Sampler::depth_comparison`]: crate::texture::Sampler::depth_comparison
+  pub fn shadow_binding(&self) -> Result<TextureBinding<D, Shadow>, PipelineError>
+  where
+    P: DepthPixel,
+  {
+    check_shadow_sampling::<D>(P::pixel_format(), self.depth_comparison)?;
+
+    let binding = unsafe { B::texture_binding(&self.repr) };
+    Ok(TextureBinding {
+      binding,
+      _phantom: PhantomData,
+    })
+  }
+}
+
+/// Opaque buffer texture binding.
+///
+/// This type represents a bound [`BufferTexture`] via [`BoundBufferTexture`]. It can be used along
+/// with a [`Uniform`] to customize a shader’s behavior.
+///
+/// # Parametricity
+///
+/// - `S` is the sampler type. It must implement [`SamplerType`] in most useful methods.
+///
+/// # Notes
+///
+/// You shouldn’t try to do store / cache or do anything special with that value. Consider it
+/// an opaque object.
+///
+/// [`Uniform`]: crate::shader::Uniform
+/// [`SamplerType`]: crate::pixel::SamplerType
+#[derive(Debug)]
+pub struct BufferTextureBinding<S> {
+  binding: u32,
+  _phantom: PhantomData<*const S>,
+}
+
+impl<S> BufferTextureBinding<S> {
+  /// Access the underlying binding value.
+  ///
+  /// # Notes
+  ///
+  /// That value shouldn’t be read nor store, as it’s only meaningful for backend implementations.
+  pub fn binding(self) -> u32 {
+    self.binding
+  }
+}
+
+/// A _bound_ [`BufferTexture`].
+///
+/// # Parametricity
+///
+/// - `B` is the backend type. It must implement [`PipelineBufferTexture`].
+/// - `P` is the pixel type. It must implement [`Pixel`].
+///
+/// # Notes
+///
+/// As with [`BoundTexture`], pass the [`BufferTextureBinding`] obtained with
+/// [`BoundBufferTexture::binding`] to your shaders.
+pub struct BoundBufferTexture<'a, B, P>
+where
+  B: PipelineBufferTexture<P>,
+  P: Pixel,
+{
+  pub(crate) repr: B::BoundBufferTextureRepr,
+  _phantom: PhantomData<&'a ()>,
+}
+
+impl<'a, B, P> BoundBufferTexture<'a, B, P>
+where
+  B: PipelineBufferTexture<P>,
+  P: Pixel,
+{
+  /// Obtain a [`BufferTextureBinding`] object that can be used to refer to this bound buffer
+  /// texture in shader stages.
+  pub fn binding(&self) -> BufferTextureBinding<P::SamplerType> {
+    let binding = unsafe { B::buffer_texture_binding(&self.repr) };
+    BufferTextureBinding {
+      binding,
+      _phantom: PhantomData,
+    }
+  }
+}
+
+/// A _bound_ [`DynTexture`].
+///
+/// # Parametricity
+///
+/// - `B` is the backend type. It must implement [`PipelineDynTexture`].
+/// - `D` is the dimension. It must implement [`Dimensionable`].
+///
+/// # Notes
+///
+/// As the sampler type of the texture is not known at compile-time, it must be picked when asking
+/// for its [`TextureBinding`] with [`BoundDynTexture::binding`].
+pub struct BoundDynTexture<'a, B, D>
+where
+  B: PipelineDynTexture<D>,
+  D: Dimensionable,
+{
+  pub(crate) repr: B::BoundDynTextureRepr,
+  pixel_format: PixelFormat,
+  // whether the texture is sampled with depth comparison
+  depth_comparison: bool,
+  _phantom: PhantomData<&'a ()>,
+}
+
+impl<'a, B, D> BoundDynTexture<'a, B, D>
+where
+  B: PipelineDynTexture<D>,
+  D: Dimensionable,
+{
+  /// Obtain a [`TextureBinding`] object that can be used to refer to this bound texture in shader
+  /// stages, sampling it with the sampler type `S`.
+  ///
+  /// Fail with [`PipelineError::IncompatibleSamplerType`] if the pixel format of the texture
+  /// cannot be sampled with `S` — for instance, an integral texture with a floating sampler. Depth
+  /// comparison samplers, such as [`Shadow`], are subject to the same checks as with
+  /// [`BoundTexture::shadow_binding`].
+  pub fn binding<S>(&self) -> Result<TextureBinding<D, S>, PipelineError>
+  where
+    S: SamplerType,
+  {
+    let sampler_type = S::sample_type();
+
+    if !is_sampler_compatible(self.pixel_format.encoding, sampler_type) {
+      return Err(PipelineError::incompatible_sampler_type(
+        self.pixel_format,
+        sampler_type,
+      ));
+    }
+
+    if S::depth_comparison() {
+      check_shadow_sampling::<D>(self.pixel_format, self.depth_comparison)?;
+    }
+
+    let binding = unsafe { B::dyn_texture_binding(&self.repr) };
+    Ok(TextureBinding {
+      binding,
+      _phantom: PhantomData,
+    })
+  }
+}
+
+/// Check whether a texture of dimension `D` and of pixel format `pixel_format` can be sampled with
+/// a [`Shadow`] sampler; `depth_comparison` tells whether the sampler of the texture has
+/// [`Sampler::depth_comparison`] set.
+///
+/// This is the check performed by [`BoundTexture::shadow_binding`] and
+/// [`BoundDynTexture::binding`]. Only depth textures that are not 3D textures — there is no 3D
+/// shadow sampler — and that are sampled with depth comparison can be sampled with [`Shadow`].
+///
+/// [`Sampler::depth_comparison`]: crate::texture::Sampler::depth_comparison
+pub fn check_shadow_sampling<D>(
+  pixel_format: PixelFormat,
+  depth_comparison: bool,
+) -> Result<(), PipelineError>
+where
+  D: Dimensionable,
+{
+  if !pixel_format.is_depth_pixel() || D::dim() == Dim::Dim3 {
+    return Err(PipelineError::incompatible_sampler_type(
+      pixel_format,
+      Shadow::sample_type(),
+    ));
+  }
+
+  if !depth_comparison {
+    return Err(PipelineError::depth_comparison_disabled());
+  }
+
+  Ok(())
+}
+
+// normalized and floating textures are all sampled with floating samplers
+fn is_sampler_compatible(encoding: Type, sampler_type: Type) -> bool {
+  let is_floating = |ty| matches!(ty, Type::NormIntegral | Type::NormUnsigned | Type::Floating);
+
+  encoding == sampler_type || (is_floating(encoding) && is_floating(sampler_type))
 }
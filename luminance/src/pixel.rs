@@ -10,6 +10,14 @@
 //!   format on the GPU / in shaders.
 //! - [`Pixel::pixel_format`], a function returning the [`PixelFormat`], reified version of the
 //!   type at runtime.
+//!
+//! Half-precision floating-point pixel formats, such as `RGBA16F`, are only available with the
+//! `half` feature, as they are encoded with [`half::f16`].
+//!
+//! [`half::f16`]: https://docs.rs/half/2/half/struct.f16.html
+
+#[cfg(feature = "half")]
+use half::f16;
 
 /// Reify a static pixel format at runtime.
 pub unsafe trait Pixel {
@@ -44,6 +52,16 @@ pub unsafe trait RenderablePixel: Pixel {}
 pub unsafe trait SamplerType {
   /// Underlying type of the sampler.
   fn sample_type() -> Type;
+
+  /// Whether the sampler performs depth comparisons, as shadow samplers do.
+  ///
+  /// Depth comparison samplers must be used with depth textures created with
+  /// [`Sampler::depth_comparison`] set.
+  ///
+  /// [`Sampler::depth_comparison`]: crate::texture::Sampler::depth_comparison
+  fn depth_comparison() -> bool {
+    false
+  }
 }
 
 /// A `PixelFormat` gathers a `Type` along with a `Format`.
@@ -78,9 +96,55 @@ impl PixelFormat {
       Format::RGBA(_, _, _, _) => 4,
       Format::SRGB(_, _, _) => 3,
       Format::SRGBA(_, _, _, _) => 4,
+      Format::RGBE(_, _, _, _) => 3,
       Format::Depth(_) => 1,
     }
   }
+
+  /// Return the number of [`Pixel::RawEncoding`] values a single pixel is made of.
+  ///
+  /// This is the number of channels, except for packed formats — e.g. [`RGB10A2`] or [`RGB9E5`] —
+  /// which store all their channels in a single value.
+  pub fn raw_len(self) -> usize {
+    if self.format.is_packed() {
+      1
+    } else {
+      self.channels_len()
+    }
+  }
+
+  /// Return the size (in bytes) of the [`Pixel::RawEncoding`] values a single pixel is made of.
+  ///
+  /// This is the number of bytes a pixel takes in raw texels, which might be more than
+  /// [`Format::bytes_len`] for formats with channels that are not byte-aligned — e.g.
+  /// [`R11G11B10F`], which is encoded as three `f32`.
+  pub fn raw_bytes_len(self) -> usize {
+    if self.format.is_packed() {
+      return 4;
+    }
+
+    let channel_bits = match self.format {
+      Format::R(r) => r.bits_len(),
+      Format::RG(r, _) => r.bits_len(),
+      Format::RGB(r, _, _) => r.bits_len(),
+      Format::RGBA(r, _, _, _) => r.bits_len(),
+      Format::SRGB(r, _, _) => r.bits_len(),
+      Format::SRGBA(r, _, _, _) => r.bits_len(),
+      Format::RGBE(r, _, _, _) => r.bits_len(),
+      Format::Depth(d) => d.bits_len(),
+    };
+
+    let raw_bytes = match (self.encoding, channel_bits) {
+      (Type::Floating, 16) => 2,
+      // other floating channels, such as 11-bit ones, are encoded as 32-bit floats
+      (Type::Floating, _) => 4,
+      (_, 0..=8) => 1,
+      (_, 9..=16) => 2,
+      _ => 4,
+    };
+
+    raw_bytes * self.raw_len()
+  }
 }
 
 /// Pixel type.
@@ -124,6 +188,8 @@ pub enum Format {
   SRGB(Size, Size, Size),
   /// Holds a red, green and blue channels in sRGB colorspace, plus an alpha channel.
   SRGBA(Size, Size, Size, Size),
+  /// Holds red, green and blue channels sharing a common exponent, which size is the last one.
+  RGBE(Size, Size, Size, Size),
   /// Holds a depth channel.
   Depth(Size),
 }
@@ -138,24 +204,41 @@ impl Format {
       Format::RGBA(r, g, b, a) => r.bits_len() + g.bits_len() + b.bits_len() + a.bits_len(),
       Format::SRGB(r, g, b) => r.bits_len() + g.bits_len() + b.bits_len(),
       Format::SRGBA(r, g, b, a) => r.bits_len() + g.bits_len() + b.bits_len() + a.bits_len(),
+      Format::RGBE(r, g, b, e) => r.bits_len() + g.bits_len() + b.bits_len() + e.bits_len(),
       Format::Depth(d) => d.bits_len(),
     };
 
     bits / 8
   }
+
+  /// Whether all the channels of the format are packed in a single 32-bit value.
+  pub fn is_packed(self) -> bool {
+    matches!(
+      self,
+      Format::RGBA(Size::Ten, Size::Ten, Size::Ten, Size::Two) | Format::RGBE(_, _, _, _)
+    )
+  }
 }
 
 /// Size in bits a pixel channel can be.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Size {
+  /// 2-bit.
+  Two,
+  /// 5-bit.
+  Five,
   /// 8-bit.
   Eight,
+  /// 9-bit.
+  Nine,
   /// 10-bit.
   Ten,
   /// 11-bit.
   Eleven,
   /// 16-bit.
   Sixteen,
+  /// 24-bit.
+  TwentyFour,
   /// 32-bit.
   ThirtyTwo,
 }
@@ -164,10 +247,14 @@ impl Size {
   /// Size (in bits).
   pub fn bits_len(self) -> usize {
     match self {
+      Size::Two => 2,
+      Size::Five => 5,
       Size::Eight => 8,
+      Size::Nine => 9,
       Size::Ten => 10,
       Size::Eleven => 11,
       Size::Sixteen => 16,
+      Size::TwentyFour => 24,
       Size::ThirtyTwo => 32,
     }
   }
@@ -223,6 +310,24 @@ unsafe impl SamplerType for Floating {
   }
 }
 
+/// The depth comparison sampler type.
+///
+/// Depth textures sampled with that sampler type are accessed through shadow samplers, such as
+/// `sampler2DShadow`, which compare the sampled depth against a reference value instead of
+/// returning it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Shadow;
+
+unsafe impl SamplerType for Shadow {
+  fn sample_type() -> Type {
+    Type::Floating
+  }
+
+  fn depth_comparison() -> bool {
+    true
+  }
+}
+
 macro_rules! impl_Pixel {
   ($t:ty, $encoding:ty, $raw_encoding:ty, $encoding_ty:ident, $format:expr) => {
     unsafe impl Pixel for $t {
@@ -322,6 +427,18 @@ impl_Pixel!(NormR16UI, u16, u16, NormUnsigned, Format::R(Size::Sixteen));
 impl_ColorPixel!(NormR16UI);
 impl_RenderablePixel!(NormR16UI);
 
+/// A red 16-bit floating pixel format.
+#[cfg(feature = "half")]
+#[derive(Clone, Copy, Debug)]
+pub struct R16F;
+
+#[cfg(feature = "half")]
+impl_Pixel!(R16F, f16, f16, Floating, Format::R(Size::Sixteen));
+#[cfg(feature = "half")]
+impl_ColorPixel!(R16F);
+#[cfg(feature = "half")]
+impl_RenderablePixel!(R16F);
+
 /// A red 32-bit signed integral pixel format.
 #[derive(Clone, Copy, Debug)]
 pub struct R32I;
@@ -368,6 +485,24 @@ impl_Pixel!(R32F, f32, f32, Floating, Format::R(Size::ThirtyTwo));
 impl_ColorPixel!(R32F);
 impl_RenderablePixel!(R32F);
 
+/// A red and green 16-bit floating pixel format.
+#[cfg(feature = "half")]
+#[derive(Clone, Copy, Debug)]
+pub struct RG16F;
+
+#[cfg(feature = "half")]
+impl_Pixel!(
+  RG16F,
+  [f16; 2],
+  f16,
+  Floating,
+  Format::RG(Size::Sixteen, Size::Sixteen)
+);
+#[cfg(feature = "half")]
+impl_ColorPixel!(RG16F);
+#[cfg(feature = "half")]
+impl_RenderablePixel!(RG16F);
+
 /// A red and green 8-bit signed integral pixel format.
 #[derive(Clone, Copy, Debug)]
 pub struct RG8I;
@@ -666,6 +801,24 @@ impl_Pixel!(
 impl_ColorPixel!(NormRGB16UI);
 impl_RenderablePixel!(NormRGB16UI);
 
+/// A red, green and blue 16-bit floating pixel format.
+#[cfg(feature = "half")]
+#[derive(Clone, Copy, Debug)]
+pub struct RGB16F;
+
+#[cfg(feature = "half")]
+impl_Pixel!(
+  RGB16F,
+  [f16; 3],
+  f16,
+  Floating,
+  Format::RGB(Size::Sixteen, Size::Sixteen, Size::Sixteen)
+);
+#[cfg(feature = "half")]
+impl_ColorPixel!(RGB16F);
+#[cfg(feature = "half")]
+impl_RenderablePixel!(RGB16F);
+
 /// A red, green and blue 32-bit signed integral pixel format.
 #[derive(Clone, Copy, Debug)]
 pub struct RGB32I;
@@ -854,6 +1007,24 @@ impl_Pixel!(
 impl_ColorPixel!(NormRGBA16UI);
 impl_RenderablePixel!(NormRGBA16UI);
 
+/// A red, green, blue and alpha 16-bit floating pixel format.
+#[cfg(feature = "half")]
+#[derive(Clone, Copy, Debug)]
+pub struct RGBA16F;
+
+#[cfg(feature = "half")]
+impl_Pixel!(
+  RGBA16F,
+  [f16; 4],
+  f16,
+  Floating,
+  Format::RGBA(Size::Sixteen, Size::Sixteen, Size::Sixteen, Size::Sixteen)
+);
+#[cfg(feature = "half")]
+impl_ColorPixel!(RGBA16F);
+#[cfg(feature = "half")]
+impl_RenderablePixel!(RGBA16F);
+
 /// A red, green, blue and alpha 32-bit signed integral pixel format.
 #[derive(Clone, Copy, Debug)]
 pub struct RGBA32I;
@@ -969,6 +1140,61 @@ impl_Pixel!(
 impl_ColorPixel!(R11G11B10F);
 impl_RenderablePixel!(R11G11B10F);
 
+/// A red, green, blue and alpha pixel format, accessed as normalized floating pixels, in which:
+///
+///   - The red, green and blue channels are on 10 bits.
+///   - The alpha channel is on 2 bits.
+///
+/// All channels are packed in a single `u32`, red being in the least significant bits.
+#[derive(Clone, Copy, Debug)]
+pub struct RGB10A2;
+
+impl_Pixel!(
+  RGB10A2,
+  u32,
+  u32,
+  NormUnsigned,
+  Format::RGBA(Size::Ten, Size::Ten, Size::Ten, Size::Two)
+);
+impl_ColorPixel!(RGB10A2);
+impl_RenderablePixel!(RGB10A2);
+
+/// A red, green, blue and alpha unsigned integral pixel format in which:
+///
+///   - The red, green and blue channels are on 10 bits.
+///   - The alpha channel is on 2 bits.
+///
+/// All channels are packed in a single `u32`, red being in the least significant bits.
+#[derive(Clone, Copy, Debug)]
+pub struct RGB10A2UI;
+
+impl_Pixel!(
+  RGB10A2UI,
+  u32,
+  u32,
+  Unsigned,
+  Format::RGBA(Size::Ten, Size::Ten, Size::Ten, Size::Two)
+);
+impl_ColorPixel!(RGB10A2UI);
+impl_RenderablePixel!(RGB10A2UI);
+
+/// A red, green and blue floating pixel format in which each channel has a 9-bit mantissa, all
+/// channels sharing a 5-bit exponent.
+///
+/// All channels are packed in a single `u32`, red being in the least significant bits and the
+/// exponent in the most significant ones. That format cannot be rendered to.
+#[derive(Clone, Copy, Debug)]
+pub struct RGB9E5;
+
+impl_Pixel!(
+  RGB9E5,
+  u32,
+  u32,
+  Floating,
+  Format::RGBE(Size::Nine, Size::Nine, Size::Nine, Size::Five)
+);
+impl_ColorPixel!(RGB9E5);
+
 /// An 8-bit unsigned integral red, green and blue pixel format in sRGB colorspace.
 #[derive(Clone, Copy, Debug)]
 pub struct SRGB8UI;
@@ -997,6 +1223,35 @@ impl_Pixel!(
 impl_ColorPixel!(SRGBA8UI);
 impl_RenderablePixel!(SRGBA8UI);
 
+/// A depth 16-bit normalized unsigned integral pixel format.
+#[derive(Clone, Copy, Debug)]
+pub struct Depth16;
+
+impl_Pixel!(
+  Depth16,
+  u16,
+  u16,
+  NormUnsigned,
+  Format::Depth(Size::Sixteen)
+);
+impl_DepthPixel!(Depth16);
+
+/// A depth 24-bit normalized unsigned integral pixel format.
+///
+/// Texels are encoded as `u32`, normalized over the whole range of `u32`; only the 24 most
+/// significant bits are stored.
+#[derive(Clone, Copy, Debug)]
+pub struct Depth24;
+
+impl_Pixel!(
+  Depth24,
+  u32,
+  u32,
+  NormUnsigned,
+  Format::Depth(Size::TwentyFour)
+);
+impl_DepthPixel!(Depth24);
+
 /// A depth 32-bit floating pixel format.
 #[derive(Clone, Copy, Debug)]
 pub struct Depth32F;
@@ -37,6 +37,20 @@
 //! > that this changes in the future to be more flexible, but right now GLSL 150, for instance, is
 //! > not allowed.
 //!
+//! Sources can share code by including other sources with `#include "path"` directives, expanded
+//! by the [`preprocessor`] module. Stages created from preprocessed sources with
+//! [`Stage::new_preprocessed`] report compilation errors at their location in the included
+//! sources.
+//!
+//! Programs built from files can be rebuilt automatically when those files change with the
+//! [`watcher`] module.
+//!
+//! Linking many programs can slow down the startup of an application. Backends supporting it can
+//! skip compilation by reusing program binaries stored in a cache; see the [`cache`] module.
+//!
+//! The active uniforms, uniform blocks and vertex attributes of a linked program can be listed
+//! with the [`reflection`] API.
+//!
 //! # Shader program
 //!
 //! A shader program — [`Program`] is akin to a binary program, but runs on GPU. It is invoked when
@@ -141,13 +155,32 @@
 //! [`BoundBuffer`]: crate::pipeline::BoundBuffer
 //! [`BufferBinding`]: crate::pipeline::BufferBinding
 
+pub mod cache;
+#[cfg(feature = "naga")]
+pub mod cross;
+pub mod permutation;
+pub mod preprocessor;
+pub mod reflection;
+pub mod watcher;
+
+use std::collections::HashMap;
 use std::error;
 use std::fmt;
+use std::hash::Hash;
 use std::marker::PhantomData;
+use std::mem;
+use std::slice;
 
-use crate::backend::shader::{Shader, Uniformable};
+use crate::backend::query::Query;
+use crate::backend::shader::{
+  Shader, ShaderBinary, ShaderReflection, ShaderSpirv, ShaderUniformCache, Uniformable,
+};
 use crate::context::GraphicsContext;
-use crate::vertex::Semantics;
+use crate::shader::cache::{ProgramBinary, ProgramCache};
+use crate::shader::permutation::Defines;
+use crate::shader::preprocessor::{with_prelude, PreprocessedSource};
+use crate::shader::reflection::{ActiveAttribute, ActiveUniform, ActiveUniformBlock};
+use crate::vertex::{Semantics, SemanticsDeclarations};
 
 /// A shader stage type.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -176,6 +209,19 @@ impl fmt::Display for StageType {
   }
 }
 
+/// GLSL flavor the stages of a backend are written in.
+///
+/// See the [`cross`] module to translate stages from one flavor to another.
+///
+/// [`cross`]: crate::shader::cross
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum GlslTarget {
+  /// GLSL 330 core, as used by OpenGL 3.3.
+  Glsl330,
+  /// GLSL ES 300, as used by OpenGL ES 3.0 and WebGL2.
+  GlslEs300,
+}
+
 /// Errors that shader stages can emit.
 #[non_exhaustive]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -184,6 +230,9 @@ pub enum StageError {
   CompilationFailed(StageType, String),
   /// Occurs when you try to create a shader which type is not supported on the current hardware.
   UnsupportedType(StageType),
+  /// Occurs when you try to create a shader from a SPIR-V module that the backend cannot consume
+  /// nor cross-compile.
+  UnsupportedSpirv(StageType),
 }
 
 impl StageError {
@@ -196,6 +245,12 @@ impl StageError {
   pub fn unsupported_type(ty: StageType) -> Self {
     StageError::UnsupportedType(ty)
   }
+
+  /// Occurs when you try to create a shader from a SPIR-V module that the backend cannot consume
+  /// nor cross-compile.
+  pub fn unsupported_spirv(ty: StageType) -> Self {
+    StageError::UnsupportedSpirv(ty)
+  }
 }
 
 impl fmt::Display for StageError {
@@ -204,6 +259,8 @@ impl fmt::Display for StageError {
       StageError::CompilationFailed(ref ty, ref r) => write!(f, "{} compilation error: {}", ty, r),
 
       StageError::UnsupportedType(ty) => write!(f, "unsupported {}", ty),
+
+      StageError::UnsupportedSpirv(ty) => write!(f, "unsupported SPIR-V {}", ty),
     }
   }
 }
@@ -485,6 +542,104 @@ where
   }
 }
 
+/// Statistics of the uniform value cache of a [`Program`].
+///
+/// When the cache is enabled with [`Program::set_uniform_cache`], setting a uniform to the value
+/// it already has is a _hit_ and doesn’t reach the backend; any other update is a _miss_.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct UniformCacheStats {
+  /// Number of updates skipped because the uniform already had the value.
+  pub hits: u64,
+  /// Number of updates uploaded to the backend.
+  pub misses: u64,
+}
+
+/// Last values uploaded to the uniforms of a program.
+///
+/// This is a helper for backends implementing [`ShaderUniformCache`]: a backend keeps one per
+/// program, and checks it with [`UniformCache::is_cached`] before uploading a uniform value.
+///
+/// # Parametricity
+///
+/// - `K` is the type identifying uniforms, such as their location.
+#[derive(Clone, Debug)]
+pub struct UniformCache<K> {
+  values: HashMap<K, Vec<u8>>,
+  stats: UniformCacheStats,
+}
+
+impl<K> Default for UniformCache<K> {
+  fn default() -> Self {
+    UniformCache {
+      values: HashMap::new(),
+      stats: UniformCacheStats::default(),
+    }
+  }
+}
+
+impl<K> UniformCache<K>
+where
+  K: Eq + Hash,
+{
+  /// Create an empty cache.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Check whether `value` was the last value uploaded to the uniform identified by `key`, and
+  /// remember it otherwise.
+  ///
+  /// A check returning `true` counts as a hit; any other check counts as a miss.
+  ///
+  /// # Safety
+  ///
+  /// `T` must not contain any padding byte, as values are compared byte by byte.
+  pub unsafe fn is_cached<T>(&mut self, key: K, value: &[T]) -> bool {
+    let bytes = slice::from_raw_parts(value.as_ptr() as *const u8, mem::size_of_val(value));
+
+    match self.values.get_mut(&key) {
+      Some(cached) if cached.as_slice() == bytes => {
+        self.stats.hits += 1;
+        true
+      }
+
+      Some(cached) => {
+        cached.clear();
+        cached.extend_from_slice(bytes);
+        self.stats.misses += 1;
+        false
+      }
+
+      None => {
+        self.values.insert(key, bytes.to_owned());
+        self.stats.misses += 1;
+        false
+      }
+    }
+  }
+
+  /// Forget the values of the uniforms for which `f` returns `true`.
+  ///
+  /// Backends use it when an upload changes other uniforms than the one it’s cached for, such as
+  /// an array upload overwriting the elements of the array.
+  pub fn invalidate<F>(&mut self, mut f: F)
+  where
+    F: FnMut(&K) -> bool,
+  {
+    self.values.retain(|key, _| !f(key));
+  }
+
+  /// Statistics of the cache.
+  pub fn stats(&self) -> UniformCacheStats {
+    self.stats
+  }
+
+  /// Reset the statistics of the cache, keeping the cached values.
+  pub fn reset_stats(&mut self) {
+    self.stats = UniformCacheStats::default();
+  }
+}
+
 /// Type of a uniform.
 ///
 /// This is an exhaustive list of possible types of value you can send to a shader program.
@@ -587,6 +742,45 @@ pub enum UniformType {
   UICubemap,
   /// Floating-point cubemap sampler.
   Cubemap,
+  /// Signed cubemap array sampler.
+  ICubemapArray,
+  /// Unsigned cubemap array sampler.
+  UICubemapArray,
+  /// Floating-point cubemap array sampler.
+  CubemapArray,
+  /// Signed integral buffer texture sampler.
+  ISamplerBuffer,
+  /// Unsigned integral buffer texture sampler.
+  UISamplerBuffer,
+  /// Floating-point buffer texture sampler.
+  SamplerBuffer,
+  /// Depth comparison 1D texture sampler.
+  Sampler1DShadow,
+  /// Depth comparison 2D texture sampler.
+  Sampler2DShadow,
+  /// Depth comparison 1D array texture sampler.
+  Sampler1DArrayShadow,
+  /// Depth comparison 2D array texture sampler.
+  Sampler2DArrayShadow,
+  /// Depth comparison cubemap sampler.
+  CubemapShadow,
+  /// Depth comparison cubemap array sampler.
+  CubemapArrayShadow,
+  /// Signed integral 2D multisample texture sampler.
+  ///
+  /// Only reported by reflection: multisample textures cannot be created, so no texture binding has
+  /// this type.
+  ISampler2DMS,
+  /// Unsigned integral 2D multisample texture sampler.
+  ///
+  /// Only reported by reflection: multisample textures cannot be created, so no texture binding has
+  /// this type.
+  UISampler2DMS,
+  /// Floating-point 2D multisample texture sampler.
+  ///
+  /// Only reported by reflection: multisample textures cannot be created, so no texture binding has
+  /// this type.
+  Sampler2DMS,
 
   // buffer
   /// Buffer binding; used for UBOs.
@@ -640,6 +834,21 @@ impl fmt::Display for UniformType {
       UniformType::ICubemap => f.write_str("isamplerCube"),
       UniformType::UICubemap => f.write_str("usamplerCube"),
       UniformType::Cubemap => f.write_str("samplerCube"),
+      UniformType::ICubemapArray => f.write_str("isamplerCubeArray"),
+      UniformType::UICubemapArray => f.write_str("usamplerCubeArray"),
+      UniformType::CubemapArray => f.write_str("samplerCubeArray"),
+      UniformType::ISamplerBuffer => f.write_str("isamplerBuffer"),
+      UniformType::UISamplerBuffer => f.write_str("usamplerBuffer"),
+      UniformType::SamplerBuffer => f.write_str("samplerBuffer"),
+      UniformType::Sampler1DShadow => f.write_str("sampler1DShadow"),
+      UniformType::Sampler2DShadow => f.write_str("sampler2DShadow"),
+      UniformType::Sampler1DArrayShadow => f.write_str("sampler1DArrayShadow"),
+      UniformType::Sampler2DArrayShadow => f.write_str("sampler2DArrayShadow"),
+      UniformType::CubemapShadow => f.write_str("samplerCubeShadow"),
+      UniformType::CubemapArrayShadow => f.write_str("samplerCubeArrayShadow"),
+      UniformType::ISampler2DMS => f.write_str("isampler2DMS"),
+      UniformType::UISampler2DMS => f.write_str("usampler2DMS"),
+      UniformType::Sampler2DMS => f.write_str("sampler2DMS"),
       UniformType::BufferBinding => f.write_str("buffer binding"),
     }
   }
@@ -688,6 +897,90 @@ where
         .map(|repr| Stage { repr })
     }
   }
+
+  /// Create a new stage of type `ty` from a SPIR-V `module`.
+  ///
+  /// `entry_point` is the name of the function of the module to use as stage entry point. If the
+  /// backend cannot consume SPIR-V — see [`ShaderSpirv::supports_spirv`] — and the `naga` feature
+  /// is enabled, the module is cross-compiled to the [`GlslTarget`] of the backend; otherwise,
+  /// [`StageError::UnsupportedSpirv`] is returned.
+  ///
+  /// SPIR-V modules don’t have to keep the names of their globals, so uniforms should be looked up
+  /// with care. See the [`cross`] module for further details.
+  ///
+  /// [`cross`]: crate::shader::cross
+  pub fn new_spirv<C>(
+    ctx: &mut C,
+    ty: StageType,
+    module: &[u8],
+    entry_point: &str,
+  ) -> Result<Self, StageError>
+  where
+    C: GraphicsContext<Backend = B>,
+    B: ShaderSpirv,
+  {
+    unsafe {
+      if ctx.backend().supports_spirv() {
+        return ctx
+          .backend()
+          .new_stage_from_spirv(ty, module, entry_point)
+          .map(|repr| Stage { repr });
+      }
+    }
+
+    #[cfg(feature = "naga")]
+    {
+      let target = unsafe { ctx.backend().glsl_target() };
+      let stage = cross::spirv_to_glsl(module, ty, entry_point, target)
+        .map_err(|e| StageError::compilation_failed(ty, e.to_string()))?;
+
+      Self::new(ctx, ty, stage.code())
+    }
+
+    #[cfg(not(feature = "naga"))]
+    Err(StageError::unsupported_spirv(ty))
+  }
+
+  /// Create a new stage of type `ty` by cross-compiling GLSL 450 `src` to the [`GlslTarget`] of the
+  /// backend.
+  ///
+  /// See the [`cross`] module for further details.
+  ///
+  /// [`cross`]: crate::shader::cross
+  #[cfg(feature = "naga")]
+  pub fn new_glsl450<C>(ctx: &mut C, ty: StageType, src: &str) -> Result<Self, StageError>
+  where
+    C: GraphicsContext<Backend = B>,
+    B: ShaderSpirv,
+  {
+    let target = unsafe { ctx.backend().glsl_target() };
+    let stage = cross::glsl450_to_glsl(src, ty, target)
+      .map_err(|e| StageError::compilation_failed(ty, e.to_string()))?;
+
+    Self::new(ctx, ty, stage.code())
+  }
+
+  /// Create a new stage of type `ty` by compiling a [`PreprocessedSource`].
+  ///
+  /// If compilation fails, the locations in the log of [`StageError::CompilationFailed`] are
+  /// rewritten to point to the sources the faulty lines come from — see [`LineMap::remap_log`].
+  ///
+  /// [`LineMap::remap_log`]: crate::shader::preprocessor::LineMap::remap_log
+  pub fn new_preprocessed<C>(
+    ctx: &mut C,
+    ty: StageType,
+    src: &PreprocessedSource,
+  ) -> Result<Self, StageError>
+  where
+    C: GraphicsContext<Backend = B>,
+  {
+    Self::new(ctx, ty, src.code()).map_err(|e| match e {
+      StageError::CompilationFailed(ty, log) => {
+        StageError::compilation_failed(ty, src.line_map().remap_log(&log))
+      }
+      e => e,
+    })
+  }
 }
 
 /// A builder of [`Uniform`].
@@ -704,6 +997,7 @@ where
 {
   repr: B::UniformBuilderRepr,
   warnings: Vec<UniformWarning>,
+  prefix: String,
   _a: PhantomData<&'a mut ()>,
 }
 
@@ -712,12 +1006,20 @@ where
   B: ?Sized + Shader,
 {
   /// Ask the creation of a [`Uniform`], identified by its `name`.
+  ///
+  /// When building a nested [`UniformInterface`] with [`UniformBuilder::ask_nested`], `name` is
+  /// prefixed with the prefix of the nested interface.
   pub fn ask<T, N>(&mut self, name: N) -> Result<Uniform<T>, UniformWarning>
   where
     N: AsRef<str>,
     T: Uniformable<B>,
   {
-    unsafe { B::ask_uniform(&mut self.repr, name.as_ref()) }
+    if self.prefix.is_empty() {
+      unsafe { B::ask_uniform(&mut self.repr, name.as_ref()) }
+    } else {
+      let name = format!("{}{}", self.prefix, name.as_ref());
+      unsafe { B::ask_uniform(&mut self.repr, &name) }
+    }
   }
 
   /// Ask the creation of a [`Uniform`], identified by its `name`.
@@ -737,6 +1039,29 @@ where
       }
     }
   }
+
+  /// Build a nested [`UniformInterface`], which uniforms are looked up with `prefix` prepended to
+  /// their names.
+  ///
+  /// This is typically used to map GLSL structs and arrays of structs: with a `prefix` set to
+  /// `"lights[3]."`, the `color` uniform of the nested interface is looked up as `lights[3].color`.
+  /// Prefixes accumulate, so nested interfaces can nest other interfaces.
+  pub fn ask_nested<T, E>(
+    &mut self,
+    prefix: impl AsRef<str>,
+    env: &mut E,
+  ) -> Result<T, UniformWarning>
+  where
+    T: UniformInterface<B, E>,
+  {
+    let len = self.prefix.len();
+    self.prefix.push_str(prefix.as_ref());
+
+    let iface = T::uniform_interface(self, env);
+
+    self.prefix.truncate(len);
+    iface
+  }
 }
 
 /// [`Uniform`] interface.
@@ -789,6 +1114,33 @@ where
   }
 }
 
+/// Uniform interfaces that can declare their uniforms in GLSL.
+///
+/// Uniforms holding a [`BufferBinding`] are not declared, as the content of their uniform block
+/// is not known.
+///
+/// > Note: this trait is automatically implemented when deriving [`UniformInterface`] with
+/// > [luminance-derive].
+///
+/// [luminance-derive]: https://crates.io/crates/luminance-derive
+/// [`BufferBinding`]: crate::pipeline::BufferBinding
+pub trait UniformDeclarations<B>
+where
+  B: ?Sized + Shader,
+{
+  /// GLSL declarations of the uniforms, such as `uniform mat4 view;`, one per line.
+  fn uniform_declarations() -> String;
+}
+
+impl<B> UniformDeclarations<B> for ()
+where
+  B: ?Sized + Shader,
+{
+  fn uniform_declarations() -> String {
+    String::new()
+  }
+}
+
 /// A built program with potential warnings.
 ///
 /// The sole purpose of this type is to be destructured when a program is built.
@@ -887,10 +1239,35 @@ where
       B::new_uniform_builder(&mut self.program).map(|repr| UniformBuilder {
         repr,
         warnings: Vec::new(),
+        prefix: String::new(),
         _a: PhantomData,
       })
     }
   }
+
+  /// Active uniforms of the program, uniform block members excluded.
+  pub fn active_uniforms(&self) -> Vec<ActiveUniform>
+  where
+    B: ShaderReflection,
+  {
+    unsafe { B::active_uniforms(self.program) }
+  }
+
+  /// Active uniform blocks of the program.
+  pub fn active_uniform_blocks(&self) -> Vec<ActiveUniformBlock>
+  where
+    B: ShaderReflection,
+  {
+    unsafe { B::active_uniform_blocks(self.program) }
+  }
+
+  /// Active vertex attributes of the program.
+  pub fn active_attributes(&self) -> Vec<ActiveAttribute>
+  where
+    B: ShaderReflection,
+  {
+    unsafe { B::active_attributes(self.program) }
+  }
 }
 
 /// A [`Program`] builder.
@@ -960,25 +1337,37 @@ where
         .map(|w| ProgramError::Warning(w.into()))
         .collect();
 
-      let mut uniform_builder =
-        C::Backend::new_uniform_builder(&mut repr).map(|repr| UniformBuilder {
-          repr,
-          warnings: Vec::new(),
-          _a: PhantomData,
-        })?;
-
-      let uni =
-        Uni::uniform_interface(&mut uniform_builder, env).map_err(ProgramWarning::Uniform)?;
+      Self::with_uniform_interface(repr, warnings, env)
+    }
+  }
 
-      let program = Program {
+  // create the uniform interface of a linked program
+  unsafe fn with_uniform_interface<E>(
+    mut repr: <C::Backend as Shader>::ProgramRepr,
+    warnings: Vec<ProgramError>,
+    env: &mut E,
+  ) -> Result<BuiltProgram<C::Backend, Sem, Out, Uni>, ProgramError>
+  where
+    Uni: UniformInterface<C::Backend, E>,
+  {
+    let mut uniform_builder =
+      C::Backend::new_uniform_builder(&mut repr).map(|repr| UniformBuilder {
         repr,
-        uni,
-        _sem: PhantomData,
-        _out: PhantomData,
-      };
+        warnings: Vec::new(),
+        prefix: String::new(),
+        _a: PhantomData,
+      })?;
 
-      Ok(BuiltProgram { program, warnings })
-    }
+    let uni = Uni::uniform_interface(&mut uniform_builder, env).map_err(ProgramWarning::Uniform)?;
+
+    let program = Program {
+      repr,
+      uni,
+      _sem: PhantomData,
+      _out: PhantomData,
+    };
+
+    Ok(BuiltProgram { program, warnings })
   }
 
   /// Create a [`Program`] by linking [`Stage`]s.
@@ -1105,6 +1494,433 @@ where
   {
     Self::from_strings_env(self, vertex, tess, geometry, fragment, &mut ())
   }
+
+  /// Create a [`Program`] by linking [`&str`]s prepended with the GLSL declarations of the vertex
+  /// inputs and uniforms, and accessing a mutable environment variable.
+  ///
+  /// The vertex stage is prepended with the [`SemanticsDeclarations`] of `Sem` and all the stages
+  /// are prepended with the [`UniformDeclarations`] of `Uni`, so that those don’t have to be
+  /// written by hand — and cannot drift apart from their Rust counterparts. Sources must then not
+  /// declare them. The declarations are followed by a `#line 1` directive, so that compilation
+  /// logs report the lines of the sources.
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  /// - `T` is an [`Option`] containing a [`TessellationStages`] with [`&str`] inside.
+  /// - `G` is an [`Option`] containing a [`&str`] inside (geometry shader).
+  /// - `E` is the mutable environment variable.
+  ///
+  /// [`&str`]: str
+  pub fn from_strings_with_declarations_env<'b, T, G, E>(
+    &mut self,
+    vertex: &'b str,
+    tess: T,
+    geometry: G,
+    fragment: &'b str,
+    env: &mut E,
+  ) -> Result<BuiltProgram<C::Backend, Sem, Out, Uni>, ProgramError>
+  where
+    Sem: SemanticsDeclarations,
+    Uni: UniformInterface<C::Backend, E> + UniformDeclarations<C::Backend>,
+    T: Into<Option<TessellationStages<'b, str>>>,
+    G: Into<Option<&'b str>>,
+  {
+    // declarations are followed by a #line directive, so that compilation logs still report the
+    // lines of the sources
+    let uniforms = Uni::uniform_declarations();
+    let declare = |src: &str| with_prelude(&uniforms, src);
+
+    let vertex = with_prelude(
+      &format!("{}{}", Sem::input_declarations(), uniforms),
+      vertex,
+    );
+    let tess = tess
+      .into()
+      .map(|stages| (declare(stages.control), declare(stages.evaluation)));
+    let geometry = geometry.into().map(declare);
+    let fragment = declare(fragment);
+
+    Self::from_strings_env(
+      self,
+      &vertex,
+      tess
+        .as_ref()
+        .map(|(control, evaluation)| TessellationStages {
+          control: control.as_str(),
+          evaluation: evaluation.as_str(),
+        }),
+      geometry.as_deref(),
+      &fragment,
+      env,
+    )
+  }
+
+  /// Create a [`Program`] by linking [`&str`]s prepended with the GLSL declarations of the vertex
+  /// inputs and uniforms.
+  ///
+  /// See the documentation of [`ProgramBuilder::from_strings_with_declarations_env`] for further
+  /// details.
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  /// - `T` is an [`Option`] containing a [`TessellationStages`] with [`&str`] inside.
+  /// - `G` is an [`Option`] containing a [`&str`] inside (geometry shader).
+  ///
+  /// [`&str`]: str
+  pub fn from_strings_with_declarations<'b, T, G>(
+    &mut self,
+    vertex: &'b str,
+    tess: T,
+    geometry: G,
+    fragment: &'b str,
+  ) -> Result<BuiltProgram<C::Backend, Sem, Out, Uni>, ProgramError>
+  where
+    Sem: SemanticsDeclarations,
+    Uni: UniformInterface<C::Backend> + UniformDeclarations<C::Backend>,
+    T: Into<Option<TessellationStages<'b, str>>>,
+    G: Into<Option<&'b str>>,
+  {
+    Self::from_strings_with_declarations_env(self, vertex, tess, geometry, fragment, &mut ())
+  }
+
+  /// Create a [`Program`] by linking [`&str`]s with `#define` directives injected, and accessing a
+  /// mutable environment variable.
+  ///
+  /// The `defines` are injected into all the stages with [`Defines::inject`]. Use a
+  /// [`ProgramPermutations`] to build and keep several variants of the same program.
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  /// - `T` is an [`Option`] containing a [`TessellationStages`] with [`&str`] inside.
+  /// - `G` is an [`Option`] containing a [`&str`] inside (geometry shader).
+  /// - `E` is the mutable environment variable.
+  ///
+  /// [`&str`]: str
+  /// [`ProgramPermutations`]: crate::shader::permutation::ProgramPermutations
+  pub fn from_strings_with_defines_env<'b, T, G, E>(
+    &mut self,
+    defines: &Defines,
+    vertex: &'b str,
+    tess: T,
+    geometry: G,
+    fragment: &'b str,
+    env: &mut E,
+  ) -> Result<BuiltProgram<C::Backend, Sem, Out, Uni>, ProgramError>
+  where
+    Uni: UniformInterface<C::Backend, E>,
+    T: Into<Option<TessellationStages<'b, str>>>,
+    G: Into<Option<&'b str>>,
+  {
+    let vertex = defines.inject(vertex);
+    let tess = tess.into().map(|stages| {
+      (
+        defines.inject(stages.control),
+        defines.inject(stages.evaluation),
+      )
+    });
+    let geometry = geometry.into().map(|geometry| defines.inject(geometry));
+    let fragment = defines.inject(fragment);
+
+    Self::from_strings_env(
+      self,
+      &vertex,
+      tess
+        .as_ref()
+        .map(|(control, evaluation)| TessellationStages {
+          control: control.as_str(),
+          evaluation: evaluation.as_str(),
+        }),
+      geometry.as_deref(),
+      &fragment,
+      env,
+    )
+  }
+
+  /// Create a [`Program`] by linking [`&str`]s with `#define` directives injected.
+  ///
+  /// See the documentation of [`ProgramBuilder::from_strings_with_defines_env`] for further
+  /// details.
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  /// - `T` is an [`Option`] containing a [`TessellationStages`] with [`&str`] inside.
+  /// - `G` is an [`Option`] containing a [`&str`] inside (geometry shader).
+  ///
+  /// [`&str`]: str
+  pub fn from_strings_with_defines<'b, T, G>(
+    &mut self,
+    defines: &Defines,
+    vertex: &'b str,
+    tess: T,
+    geometry: G,
+    fragment: &'b str,
+  ) -> Result<BuiltProgram<C::Backend, Sem, Out, Uni>, ProgramError>
+  where
+    Uni: UniformInterface<C::Backend>,
+    T: Into<Option<TessellationStages<'b, str>>>,
+    G: Into<Option<&'b str>>,
+  {
+    Self::from_strings_with_defines_env(self, defines, vertex, tess, geometry, fragment, &mut ())
+  }
+
+  /// Create a [`Program`] by linking [`&str`]s, reusing a cached binary if possible, and accessing
+  /// a mutable environment variable.
+  ///
+  /// If `cache` holds a binary stored with `key` that was produced by the current driver from the
+  /// same sources, the program is created from it without compiling anything. Otherwise — or if
+  /// the driver rejects the binary — the program is compiled from its sources and its binary is
+  /// stored in `cache`. See the [`cache`] module for further details.
+  ///
+  /// Programs created from a binary don’t report [`VertexAttribWarning`]s, as their attributes are
+  /// not looked up.
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  /// - `PC` is the [`ProgramCache`].
+  /// - `T` is an [`Option`] containing a [`TessellationStages`] with [`&str`] inside.
+  /// - `G` is an [`Option`] containing a [`&str`] inside (geometry shader).
+  /// - `E` is the mutable environment variable.
+  ///
+  /// [`&str`]: str
+  #[allow(clippy::too_many_arguments)]
+  pub fn from_strings_cached_env<'b, PC, T, G, E>(
+    &mut self,
+    cache: &mut PC,
+    key: &str,
+    vertex: &'b str,
+    tess: T,
+    geometry: G,
+    fragment: &'b str,
+    env: &mut E,
+  ) -> Result<BuiltProgram<C::Backend, Sem, Out, Uni>, ProgramError>
+  where
+    C::Backend: ShaderBinary + Query,
+    Uni: UniformInterface<C::Backend, E>,
+    PC: ?Sized + ProgramCache,
+    T: Into<Option<TessellationStages<'b, str>>>,
+    G: Into<Option<&'b str>>,
+  {
+    let tess = tess.into();
+    let geometry = geometry.into();
+    let driver = cache::driver_id(self.ctx.backend());
+    let source_hash = cache::source_hash::<Sem>(
+      &C::Backend::source_prelude(),
+      vertex,
+      tess.as_ref(),
+      geometry,
+      fragment,
+    );
+
+    // try the cached binary first
+    if let Some(ref driver) = driver {
+      let binary = cache
+        .load(key)
+        .filter(|binary| binary.driver == *driver && binary.source_hash == source_hash);
+
+      if let Some(binary) = binary {
+        let repr = unsafe {
+          self
+            .ctx
+            .backend()
+            .new_program_from_binary(binary.format, &binary.data)
+        };
+
+        if let Some(repr) = repr {
+          return unsafe { Self::with_uniform_interface(repr, Vec::new(), env) };
+        }
+      }
+    }
+
+    let vs_stage = Stage::new(self.ctx, StageType::VertexShader, vertex)?;
+    let tess_stages = match tess {
+      Some(TessellationStages {
+        control,
+        evaluation,
+      }) => {
+        let control_stage = Stage::new(self.ctx, StageType::TessellationControlShader, control)?;
+        let evaluation_stage = Stage::new(
+          self.ctx,
+          StageType::TessellationEvaluationShader,
+          evaluation,
+        )?;
+        Some((control_stage, evaluation_stage))
+      }
+      None => None,
+    };
+    let gs_stage = match geometry {
+      Some(geometry) => Some(Stage::new(self.ctx, StageType::GeometryShader, geometry)?),
+      None => None,
+    };
+    let fs_stage = Stage::new(self.ctx, StageType::FragmentShader, fragment)?;
+
+    unsafe {
+      let mut repr = self.ctx.backend().new_program(
+        &vs_stage.repr,
+        tess_stages
+          .as_ref()
+          .map(|(control, evaluation)| TessellationStages {
+            control: &control.repr,
+            evaluation: &evaluation.repr,
+          }),
+        gs_stage.as_ref().map(|stage| &stage.repr),
+        &fs_stage.repr,
+      )?;
+
+      C::Backend::set_binary_retrievable(&mut repr);
+
+      let warnings = C::Backend::apply_semantics::<Sem>(&mut repr)?
+        .into_iter()
+        .map(|w| ProgramError::Warning(w.into()))
+        .collect();
+
+      if let Some(driver) = driver {
+        if let Some((format, data)) = C::Backend::program_binary(&repr) {
+          let binary = ProgramBinary {
+            driver,
+            source_hash,
+            format,
+            data,
+          };
+          cache.store(key, &binary);
+        }
+      }
+
+      Self::with_uniform_interface(repr, warnings, env)
+    }
+  }
+
+  /// Create a [`Program`] by linking [`&str`]s, reusing a cached binary if possible.
+  ///
+  /// See the documentation of [`ProgramBuilder::from_strings_cached_env`] for further details.
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  /// - `PC` is the [`ProgramCache`].
+  /// - `T` is an [`Option`] containing a [`TessellationStages`] with [`&str`] inside.
+  /// - `G` is an [`Option`] containing a [`&str`] inside (geometry shader).
+  ///
+  /// [`&str`]: str
+  pub fn from_strings_cached<'b, PC, T, G>(
+    &mut self,
+    cache: &mut PC,
+    key: &str,
+    vertex: &'b str,
+    tess: T,
+    geometry: G,
+    fragment: &'b str,
+  ) -> Result<BuiltProgram<C::Backend, Sem, Out, Uni>, ProgramError>
+  where
+    C::Backend: ShaderBinary + Query,
+    Uni: UniformInterface<C::Backend>,
+    PC: ?Sized + ProgramCache,
+    T: Into<Option<TessellationStages<'b, str>>>,
+    G: Into<Option<&'b str>>,
+  {
+    Self::from_strings_cached_env(self, cache, key, vertex, tess, geometry, fragment, &mut ())
+  }
+
+  /// Create a [`Program`] by linking [`PreprocessedSource`]s and accessing a mutable environment
+  /// variable.
+  ///
+  /// Stages are created with [`Stage::new_preprocessed`], so that compilation errors point to the
+  /// sources the faulty lines come from.
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  /// - `T` is an [`Option`] containing a [`TessellationStages`] with [`PreprocessedSource`] inside.
+  /// - `G` is an [`Option`] containing a [`PreprocessedSource`] inside (geometry shader).
+  /// - `E` is the mutable environment variable.
+  pub fn from_preprocessed_env<'b, T, G, E>(
+    &mut self,
+    vertex: &'b PreprocessedSource,
+    tess: T,
+    geometry: G,
+    fragment: &'b PreprocessedSource,
+    env: &mut E,
+  ) -> Result<BuiltProgram<C::Backend, Sem, Out, Uni>, ProgramError>
+  where
+    Uni: UniformInterface<C::Backend, E>,
+    T: Into<Option<TessellationStages<'b, PreprocessedSource>>>,
+    G: Into<Option<&'b PreprocessedSource>>,
+  {
+    let vs_stage = Stage::new_preprocessed(self.ctx, StageType::VertexShader, vertex)?;
+
+    let tess_stages = match tess.into() {
+      Some(TessellationStages {
+        control,
+        evaluation,
+      }) => {
+        let control_stage =
+          Stage::new_preprocessed(self.ctx, StageType::TessellationControlShader, control)?;
+        let evaluation_stage = Stage::new_preprocessed(
+          self.ctx,
+          StageType::TessellationEvaluationShader,
+          evaluation,
+        )?;
+        Some((control_stage, evaluation_stage))
+      }
+      None => None,
+    };
+    let tess_stages =
+      tess_stages
+        .as_ref()
+        .map(|(ref control, ref evaluation)| TessellationStages {
+          control,
+          evaluation,
+        });
+
+    let gs_stage = match geometry.into() {
+      Some(geometry) => Some(Stage::new_preprocessed(
+        self.ctx,
+        StageType::GeometryShader,
+        geometry,
+      )?),
+      None => None,
+    };
+
+    let fs_stage = Stage::new_preprocessed(self.ctx, StageType::FragmentShader, fragment)?;
+
+    Self::from_stages_env(
+      self,
+      &vs_stage,
+      tess_stages,
+      gs_stage.as_ref(),
+      &fs_stage,
+      env,
+    )
+  }
+
+  /// Create a [`Program`] by linking [`PreprocessedSource`]s.
+  ///
+  /// Stages are created with [`Stage::new_preprocessed`], so that compilation errors point to the
+  /// sources the faulty lines come from.
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  /// - `T` is an [`Option`] containing a [`TessellationStages`] with [`PreprocessedSource`] inside.
+  /// - `G` is an [`Option`] containing a [`PreprocessedSource`] inside (geometry shader).
+  pub fn from_preprocessed<'b, T, G>(
+    &mut self,
+    vertex: &'b PreprocessedSource,
+    tess: T,
+    geometry: G,
+    fragment: &'b PreprocessedSource,
+  ) -> Result<BuiltProgram<C::Backend, Sem, Out, Uni>, ProgramError>
+  where
+    Uni: UniformInterface<C::Backend>,
+    T: Into<Option<TessellationStages<'b, PreprocessedSource>>>,
+    G: Into<Option<&'b PreprocessedSource>>,
+  {
+    Self::from_preprocessed_env(self, vertex, tess, geometry, fragment, &mut ())
+  }
 }
 
 /// A shader program.
@@ -1159,33 +1975,11 @@ where
     Q: UniformInterface<B, E>,
   {
     // first, try to create the new uniform interface
-    let mut uniform_builder: UniformBuilder<B> =
-      match unsafe { B::new_uniform_builder(&mut self.repr) } {
-        Ok(repr) => UniformBuilder {
-          repr,
-          warnings: Vec::new(),
-          _a: PhantomData,
-        },
-
-        Err(e) => return Err(AdaptationFailure::new(self, e)),
-      };
-
-    let uni = match Q::uniform_interface(&mut uniform_builder, env) {
+    let (uni, warnings) = match Self::new_uniform_interface(&mut self.repr, env) {
       Ok(uni) => uni,
-      Err(e) => {
-        return Err(AdaptationFailure::new(
-          self,
-          ProgramWarning::Uniform(e).into(),
-        ))
-      }
+      Err(e) => return Err(AdaptationFailure::new(self, e)),
     };
 
-    let warnings = uniform_builder
-      .warnings
-      .into_iter()
-      .map(|w| ProgramError::Warning(w.into()))
-      .collect();
-
     let program = Program {
       repr: self.repr,
       uni,
@@ -1210,4 +2004,107 @@ where
   {
     self.adapt_env(env)
   }
+
+  /// Re-create the [`UniformInterface`] in place, by using a mutable environment variable.
+  ///
+  /// This is [`Program::readapt_env`] for when the [`Program`] cannot be moved; on failure, the
+  /// current [`UniformInterface`] is kept.
+  pub(crate) fn readapt_env_mut<E>(
+    &mut self,
+    env: &mut E,
+  ) -> Result<Vec<ProgramError>, ProgramError>
+  where
+    Uni: UniformInterface<B, E>,
+  {
+    let (uni, warnings) = Self::new_uniform_interface(&mut self.repr, env)?;
+    self.uni = uni;
+    Ok(warnings)
+  }
+
+  // create a new uniform interface for a program, along with the warnings emitted while doing so
+  fn new_uniform_interface<Q, E>(
+    repr: &mut B::ProgramRepr,
+    env: &mut E,
+  ) -> Result<(Q, Vec<ProgramError>), ProgramError>
+  where
+    Q: UniformInterface<B, E>,
+  {
+    let mut uniform_builder: UniformBuilder<B> = UniformBuilder {
+      repr: unsafe { B::new_uniform_builder(repr)? },
+      warnings: Vec::new(),
+      prefix: String::new(),
+      _a: PhantomData,
+    };
+
+    let uni = Q::uniform_interface(&mut uniform_builder, env)
+      .map_err(|e| ProgramError::from(ProgramWarning::Uniform(e)))?;
+
+    let warnings = uniform_builder
+      .warnings
+      .into_iter()
+      .map(|w| ProgramError::Warning(w.into()))
+      .collect();
+
+    Ok((uni, warnings))
+  }
+
+  /// Active uniforms of the program, uniform block members excluded.
+  ///
+  /// See the [`reflection`] module for further details.
+  pub fn active_uniforms(&self) -> Vec<ActiveUniform>
+  where
+    B: ShaderReflection,
+  {
+    unsafe { B::active_uniforms(&self.repr) }
+  }
+
+  /// Active uniform blocks of the program, along with their members.
+  ///
+  /// See the [`reflection`] module for further details.
+  pub fn active_uniform_blocks(&self) -> Vec<ActiveUniformBlock>
+  where
+    B: ShaderReflection,
+  {
+    unsafe { B::active_uniform_blocks(&self.repr) }
+  }
+
+  /// Active vertex attributes of the program.
+  ///
+  /// See the [`reflection`] module for further details.
+  pub fn active_attributes(&self) -> Vec<ActiveAttribute>
+  where
+    B: ShaderReflection,
+  {
+    unsafe { B::active_attributes(&self.repr) }
+  }
+
+  /// Enable or disable the uniform value cache of the program.
+  ///
+  /// When enabled, the last value set on each uniform is remembered and setting the same value
+  /// again is skipped, which saves backend calls when many draws share a program. Disabling the
+  /// cache forgets the values and the statistics.
+  ///
+  /// The cache is disabled when a program is created.
+  pub fn set_uniform_cache(&mut self, enabled: bool)
+  where
+    B: ShaderUniformCache,
+  {
+    unsafe { B::set_uniform_cache(&mut self.repr, enabled) }
+  }
+
+  /// Statistics of the uniform value cache, or [`None`] if the cache is disabled.
+  pub fn uniform_cache_stats(&self) -> Option<UniformCacheStats>
+  where
+    B: ShaderUniformCache,
+  {
+    unsafe { B::uniform_cache_stats(&self.repr) }
+  }
+
+  /// Reset the statistics of the uniform value cache, keeping the cached values.
+  pub fn reset_uniform_cache_stats(&mut self)
+  where
+    B: ShaderUniformCache,
+  {
+    unsafe { B::reset_uniform_cache_stats(&mut self.repr) }
+  }
 }
@@ -0,0 +1,228 @@
+//! Shader program binary caching.
+//!
+//! Compiling and linking shader programs can take a noticeable amount of time, especially when an
+//! application creates hundreds of them on startup. Some backends can retrieve the binary of a
+//! linked program and create a program back from it, skipping compilation altogether.
+//!
+//! Programs are cached with [`ProgramBuilder::from_strings_cached`], which takes a _cache key_ and
+//! a [`ProgramCache`] storing [`ProgramBinary`]s. Two stores are provided:
+//!
+//! - [`DirectoryCache`], storing one file per program in a directory.
+//! - `HashMap<String, ProgramBinary>`, keeping binaries in memory.
+//!
+//! A binary is only reused if it was produced by the same driver — as reported by the backend
+//! [`Query`] interface — from the same sources. The driver might still reject a binary, for
+//! instance after an update that didn’t change its version string; in that case, and whenever
+//! the backend doesn’t support program binaries, the program is compiled from its sources.
+//!
+//! [`ProgramBuilder::from_strings_cached`]: crate::shader::ProgramBuilder::from_strings_cached
+//! [`Query`]: crate::query::Query
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fmt::Write as _;
+use std::fs;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use crate::backend::query::Query;
+use crate::shader::TessellationStages;
+use crate::vertex::Semantics;
+
+// magic number starting encoded program binaries
+const MAGIC: &[u8; 8] = b"LUMPBIN1";
+
+/// Binary of a linked shader program.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramBinary {
+  /// Driver the binary was produced by.
+  pub driver: String,
+  /// Hash of the sources — backend prelude included — and vertex semantics the program was built
+  /// from.
+  pub source_hash: u64,
+  /// Backend-specific format of the binary.
+  pub format: u32,
+  /// Binary data.
+  pub data: Vec<u8>,
+}
+
+impl ProgramBinary {
+  /// Encode the binary into bytes, typically to store it somewhere.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(MAGIC.len() + 16 + self.driver.len() + self.data.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&(self.driver.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(self.driver.as_bytes());
+    bytes.extend_from_slice(&self.source_hash.to_le_bytes());
+    bytes.extend_from_slice(&self.format.to_le_bytes());
+    bytes.extend_from_slice(&self.data);
+    bytes
+  }
+
+  /// Decode a binary encoded with [`ProgramBinary::to_bytes`].
+  ///
+  /// Return [`None`] if `bytes` is not a valid encoded binary.
+  pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+    let bytes = bytes.strip_prefix(&MAGIC[..])?;
+
+    let (driver_len, bytes) = split_u32(bytes)?;
+    let driver_len = driver_len as usize;
+
+    if bytes.len() < driver_len {
+      return None;
+    }
+
+    let driver = String::from_utf8(bytes[..driver_len].to_owned()).ok()?;
+    let bytes = &bytes[driver_len..];
+
+    if bytes.len() < 8 {
+      return None;
+    }
+
+    let source_hash = u64::from_le_bytes(bytes[..8].try_into().ok()?);
+    let (format, data) = split_u32(&bytes[8..])?;
+
+    Some(ProgramBinary {
+      driver,
+      source_hash,
+      format,
+      data: data.to_owned(),
+    })
+  }
+}
+
+fn split_u32(bytes: &[u8]) -> Option<(u32, &[u8])> {
+  if bytes.len() < 4 {
+    return None;
+  }
+
+  let (n, rest) = bytes.split_at(4);
+  Some((u32::from_le_bytes(n.try_into().ok()?), rest))
+}
+
+/// Store of [`ProgramBinary`]s.
+pub trait ProgramCache {
+  /// Load the binary stored with `key`, if any.
+  fn load(&mut self, key: &str) -> Option<ProgramBinary>;
+
+  /// Store `binary` with `key`, replacing the previously stored binary, if any.
+  ///
+  /// Caching is an optimization: failing to store a binary is not an error and must not prevent
+  /// the program from being used.
+  fn store(&mut self, key: &str, binary: &ProgramBinary);
+}
+
+impl<S> ProgramCache for HashMap<String, ProgramBinary, S>
+where
+  S: BuildHasher,
+{
+  fn load(&mut self, key: &str) -> Option<ProgramBinary> {
+    self.get(key).cloned()
+  }
+
+  fn store(&mut self, key: &str, binary: &ProgramBinary) {
+    self.insert(key.to_owned(), binary.clone());
+  }
+}
+
+/// [`ProgramCache`] storing binaries in a directory.
+///
+/// Each binary is stored in its own `<key>.bin` file, right in the directory. Characters of keys
+/// other than ASCII letters, digits, `-` and `_` are percent-encoded in file names, so that keys
+/// containing path separators or `..` cannot refer to files outside of the directory.
+#[derive(Clone, Debug)]
+pub struct DirectoryCache {
+  dir: PathBuf,
+}
+
+impl DirectoryCache {
+  /// Create a [`DirectoryCache`] storing binaries in `dir`.
+  ///
+  /// The directory is created when the first binary is stored.
+  pub fn new(dir: impl Into<PathBuf>) -> Self {
+    DirectoryCache { dir: dir.into() }
+  }
+
+  /// Directory binaries are stored in.
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// Path of the file storing the binary with `key`.
+  pub fn path(&self, key: &str) -> PathBuf {
+    let mut file_name = String::with_capacity(key.len() + 4);
+
+    for byte in key.bytes() {
+      if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+        file_name.push(byte as char);
+      } else {
+        let _ = write!(file_name, "%{:02X}", byte);
+      }
+    }
+
+    file_name.push_str(".bin");
+    self.dir.join(file_name)
+  }
+}
+
+impl ProgramCache for DirectoryCache {
+  fn load(&mut self, key: &str) -> Option<ProgramBinary> {
+    let bytes = fs::read(self.path(key)).ok()?;
+    ProgramBinary::from_bytes(&bytes)
+  }
+
+  fn store(&mut self, key: &str, binary: &ProgramBinary) {
+    let _ = fs::create_dir_all(&self.dir);
+    let _ = fs::write(self.path(key), binary.to_bytes());
+  }
+}
+
+/// Identify the driver of a backend with its [`Query`] interface.
+///
+/// Return [`None`] if the driver cannot be identified, in which case binaries must not be cached.
+pub(crate) fn driver_id<B>(backend: &B) -> Option<String>
+where
+  B: ?Sized + Query,
+{
+  Some(format!(
+    "{}; {}; {}; {}",
+    backend.backend_author().ok()?,
+    backend.backend_name().ok()?,
+    backend.backend_version().ok()?,
+    backend.backend_shading_lang_version().ok()?
+  ))
+}
+
+/// Hash the sources and vertex semantics of a program, along with the prelude the backend prepends
+/// to them.
+///
+/// The hash is only used to detect stale binaries, so it doesn’t have to be stable across Rust
+/// releases: a different hash merely causes a recompilation.
+pub(crate) fn source_hash<Sem>(
+  prelude: &str,
+  vertex: &str,
+  tess: Option<&TessellationStages<str>>,
+  geometry: Option<&str>,
+  fragment: &str,
+) -> u64
+where
+  Sem: Semantics,
+{
+  let mut hasher = DefaultHasher::new();
+
+  prelude.hash(&mut hasher);
+  vertex.hash(&mut hasher);
+  tess
+    .map(|stages| (stages.control, stages.evaluation))
+    .hash(&mut hasher);
+  geometry.hash(&mut hasher);
+  fragment.hash(&mut hasher);
+
+  for desc in Sem::semantics_set() {
+    desc.index.hash(&mut hasher);
+    desc.name.hash(&mut hasher);
+  }
+
+  hasher.finish()
+}
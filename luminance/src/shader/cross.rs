@@ -0,0 +1,229 @@
+//! GLSL cross-compilation.
+//!
+//! Backends don’t all speak the same shading language: OpenGL 3.3 expects GLSL 330 while WebGL2
+//! expects GLSL ES 300, and only some OpenGL drivers can consume SPIR-V modules directly, through
+//! `GL_ARB_gl_spirv`. This module, available with the `naga` feature, translates SPIR-V modules and
+//! GLSL 450 sources into the [`GlslTarget`] of a backend with the pure-Rust [naga] translator, so
+//! that the same shader source can be used with all backends. [`Stage::new_spirv`] falls back to
+//! that translation when the backend cannot consume SPIR-V, and [`Stage::new_glsl450`] always
+//! uses it.
+//!
+//! Only vertex and fragment stages can be translated, and the translation doesn’t keep all the
+//! names of the input:
+//!
+//! - Uniform blocks and samplers are renamed after their set and binding; use
+//!   [`CrossCompiledStage::name`] to get the name a global of the input ends up with.
+//! - Vertex inputs are renamed but keep their `location`, so the indices of your vertex
+//!   [`Semantics`] must match those locations. Inactive attribute warnings are then expected, as
+//!   attributes cannot be found by name.
+//!
+//! [naga]: https://crates.io/crates/naga
+//! [`GlslTarget`]: crate::shader::GlslTarget
+//! [`Semantics`]: crate::vertex::Semantics
+//! [`Stage::new_spirv`]: crate::shader::Stage::new_spirv
+//! [`Stage::new_glsl450`]: crate::shader::Stage::new_glsl450
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+
+use crate::shader::{GlslTarget, StageType};
+
+/// Errors that can occur while cross-compiling a stage.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CrossCompileError {
+  /// The input cannot be parsed.
+  ParseFailed(String),
+  /// The input was parsed but is not valid.
+  ValidationFailed(String),
+  /// The stage type cannot be translated.
+  UnsupportedStage(StageType),
+  /// The input is valid but cannot be expressed in the target GLSL flavor.
+  TranslationFailed(String),
+}
+
+impl CrossCompileError {
+  /// The input cannot be parsed.
+  pub fn parse_failed(reason: impl Into<String>) -> Self {
+    CrossCompileError::ParseFailed(reason.into())
+  }
+
+  /// The input was parsed but is not valid.
+  pub fn validation_failed(reason: impl Into<String>) -> Self {
+    CrossCompileError::ValidationFailed(reason.into())
+  }
+
+  /// The stage type cannot be translated.
+  pub fn unsupported_stage(ty: StageType) -> Self {
+    CrossCompileError::UnsupportedStage(ty)
+  }
+
+  /// The input is valid but cannot be expressed in the target GLSL flavor.
+  pub fn translation_failed(reason: impl Into<String>) -> Self {
+    CrossCompileError::TranslationFailed(reason.into())
+  }
+}
+
+impl fmt::Display for CrossCompileError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      CrossCompileError::ParseFailed(ref reason) => write!(f, "cannot parse shader: {}", reason),
+
+      CrossCompileError::ValidationFailed(ref reason) => {
+        write!(f, "invalid shader: {}", reason)
+      }
+
+      CrossCompileError::UnsupportedStage(ty) => write!(f, "cannot cross-compile {}", ty),
+
+      CrossCompileError::TranslationFailed(ref reason) => {
+        write!(f, "cannot translate shader: {}", reason)
+      }
+    }
+  }
+}
+
+impl error::Error for CrossCompileError {}
+
+/// A stage cross-compiled to a [`GlslTarget`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CrossCompiledStage {
+  code: String,
+  names: HashMap<String, String>,
+}
+
+impl CrossCompiledStage {
+  /// GLSL code of the stage, without its `#version` line, which backends write themselves.
+  pub fn code(&self) -> &str {
+    &self.code
+  }
+
+  /// Name that the global named `name` in the input has in the generated code, if it was renamed.
+  pub fn name(&self, name: &str) -> Option<&str> {
+    self.names.get(name).map(String::as_str)
+  }
+
+  /// Iterate over the renamed globals, as pairs of input and generated names.
+  pub fn names(&self) -> impl Iterator<Item = (&str, &str)> {
+    self
+      .names
+      .iter()
+      .map(|(input, generated)| (input.as_str(), generated.as_str()))
+  }
+}
+
+/// Cross-compile a SPIR-V `module` to `target`.
+///
+/// `entry_point` is the name of the function of the module to use as stage entry point.
+pub fn spirv_to_glsl(
+  module: &[u8],
+  ty: StageType,
+  entry_point: &str,
+  target: GlslTarget,
+) -> Result<CrossCompiledStage, CrossCompileError> {
+  let stage = naga_stage(ty)?;
+  let options = naga::front::spv::Options::default();
+  let module = naga::front::spv::parse_u8_slice(module, &options)
+    .map_err(|e| CrossCompileError::parse_failed(e.to_string()))?;
+
+  translate(&module, stage, entry_point, target)
+}
+
+/// Cross-compile GLSL 450 `src` to `target`.
+///
+/// The entry point of the stage is its `main` function.
+pub fn glsl450_to_glsl(
+  src: &str,
+  ty: StageType,
+  target: GlslTarget,
+) -> Result<CrossCompiledStage, CrossCompileError> {
+  let stage = naga_stage(ty)?;
+  let options = naga::front::glsl::Options::from(stage);
+  let module = naga::front::glsl::Frontend::default()
+    .parse(&options, src)
+    .map_err(|e| CrossCompileError::parse_failed(e.emit_to_string(src)))?;
+
+  translate(&module, stage, "main", target)
+}
+
+fn naga_stage(ty: StageType) -> Result<naga::ShaderStage, CrossCompileError> {
+  match ty {
+    StageType::VertexShader => Ok(naga::ShaderStage::Vertex),
+    StageType::FragmentShader => Ok(naga::ShaderStage::Fragment),
+    _ => Err(CrossCompileError::unsupported_stage(ty)),
+  }
+}
+
+// validate a parsed module and write it as GLSL
+fn translate(
+  module: &naga::Module,
+  stage: naga::ShaderStage,
+  entry_point: &str,
+  target: GlslTarget,
+) -> Result<CrossCompiledStage, CrossCompileError> {
+  use naga::back::glsl;
+  use naga::valid::{Capabilities, ValidationFlags, Validator};
+
+  let info = Validator::new(ValidationFlags::all(), Capabilities::empty())
+    .validate(module)
+    .map_err(|e| CrossCompileError::validation_failed(e.into_inner().to_string()))?;
+
+  let version = match target {
+    GlslTarget::Glsl330 => glsl::Version::Desktop(330),
+    GlslTarget::GlslEs300 => glsl::Version::Embedded {
+      version: 300,
+      is_webgl: true,
+    },
+  };
+  let options = glsl::Options {
+    version,
+    // luminance works in the OpenGL coordinate space already
+    writer_flags: glsl::WriterFlags::empty(),
+    ..glsl::Options::default()
+  };
+  let pipeline_options = glsl::PipelineOptions {
+    shader_stage: stage,
+    entry_point: entry_point.to_owned(),
+    multiview: None,
+  };
+
+  let mut output = String::new();
+  let mut writer = glsl::Writer::new(
+    &mut output,
+    module,
+    &info,
+    &options,
+    &pipeline_options,
+    naga::proc::BoundsCheckPolicies::default(),
+  )
+  .map_err(|e| CrossCompileError::translation_failed(e.to_string()))?;
+  let reflection = writer
+    .write()
+    .map_err(|e| CrossCompileError::translation_failed(e.to_string()))?;
+
+  let mut names = HashMap::new();
+  let global_name =
+    |handle: naga::Handle<naga::GlobalVariable>| module.global_variables[handle].name.clone();
+
+  for (handle, generated) in reflection.uniforms {
+    if let Some(name) = global_name(handle) {
+      names.insert(name, generated);
+    }
+  }
+
+  for (generated, mapping) in reflection.texture_mapping {
+    if let Some(name) = global_name(mapping.texture) {
+      names.insert(name, generated);
+    }
+  }
+
+  names.retain(|name, generated| name != generated);
+
+  // backends write their own #version line
+  let code = match output.find('\n') {
+    Some(end) if output.starts_with("#version") => output[end + 1..].to_owned(),
+    _ => output,
+  };
+
+  Ok(CrossCompiledStage { code, names })
+}
@@ -0,0 +1,368 @@
+//! Compile-time defines and shader permutations.
+//!
+//! A single shader is often built in several variants — with or without normal mapping, with a
+//! different number of shadow cascades, etc. — selected with preprocessor `#define`s. This module
+//! provides [`Defines`], a set of `#define` directives injected into shader sources, and
+//! [`ProgramPermutations`], which builds a [`Program`] per set of [`Defines`] and returns the
+//! already built one when the same set is asked again.
+//!
+//! Defines are injected right after the `#version` line of each stage or, if a stage doesn’t have
+//! one, at its very beginning. The latter is what happens with backends injecting the `#version`
+//! line themselves, such as WebGL2, which keeps the injected `#version` line first.
+//!
+//! ```ignore
+//! let mut permutations = ProgramPermutations::<_, Semantics, (), ShaderInterface>::new(VS, FS);
+//! let defines = Defines::new()
+//!   .flag("NORMAL_MAP")
+//!   .define("SHADOW_CASCADES", 4);
+//!
+//! let program = permutations.program(&mut ctx, &defines)?;
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::iter::FromIterator;
+
+use crate::backend::shader::Shader;
+use crate::context::GraphicsContext;
+use crate::shader::preprocessor::with_prelude;
+use crate::shader::{
+  BuiltProgram, Program, ProgramBuilder, ProgramError, TessellationStages, UniformInterface,
+};
+use crate::vertex::Semantics;
+
+/// Set of `#define` directives.
+///
+/// Defines are sorted by name, so that two sets with the same defines are equal — and inject the
+/// same code — regardless of the order they were defined in. Names and values are injected
+/// verbatim.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Defines {
+  defines: BTreeMap<String, String>,
+}
+
+impl Defines {
+  /// Create an empty set of defines.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Define `name` to `value`, replacing the previous value of `name`, if any.
+  pub fn define(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+    self.set(name, value);
+    self
+  }
+
+  /// Define `name` without any value, as in `#define NORMAL_MAP`.
+  pub fn flag(self, name: impl Into<String>) -> Self {
+    self.define(name, "")
+  }
+
+  /// Define `name` to `value` in place, replacing the previous value of `name`, if any.
+  pub fn set(&mut self, name: impl Into<String>, value: impl ToString) {
+    self.defines.insert(name.into(), value.to_string());
+  }
+
+  /// Remove the define of `name` and return its value, if any.
+  pub fn remove(&mut self, name: &str) -> Option<String> {
+    self.defines.remove(name)
+  }
+
+  /// Value `name` is defined to, if defined.
+  pub fn get(&self, name: &str) -> Option<&str> {
+    self.defines.get(name).map(String::as_str)
+  }
+
+  /// Number of defines.
+  pub fn len(&self) -> usize {
+    self.defines.len()
+  }
+
+  /// Whether there is no define.
+  pub fn is_empty(&self) -> bool {
+    self.defines.is_empty()
+  }
+
+  /// Iterate over the names and values of the defines, sorted by name.
+  pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+    self
+      .defines
+      .iter()
+      .map(|(name, value)| (name.as_str(), value.as_str()))
+  }
+
+  /// Inject the defines into `src`.
+  ///
+  /// The `#define` directives are inserted right after the `#version` line if `src` starts with
+  /// one — blank lines and line comments aside — and at the beginning of `src` otherwise. They are
+  /// followed by a `#line` directive, so that compilation logs report the lines of `src`.
+  pub fn inject(&self, src: &str) -> String {
+    if self.is_empty() {
+      return src.to_owned();
+    }
+
+    let mut directives = String::new();
+
+    for (name, value) in self.iter() {
+      if value.is_empty() {
+        directives.push_str(&format!("#define {}\n", name));
+      } else {
+        directives.push_str(&format!("#define {} {}\n", name, value));
+      }
+    }
+
+    match version_line_end(src) {
+      Some(end) => {
+        let (version, rest) = src.split_at(end);
+        let mut injected = String::with_capacity(src.len() + directives.len() + 1);
+        injected.push_str(version);
+
+        if !version.ends_with('\n') {
+          injected.push('\n');
+        }
+
+        injected.push_str(&directives);
+        injected.push_str(&format!("#line {}\n", version.lines().count() + 1));
+        injected.push_str(rest);
+        injected
+      }
+
+      None => with_prelude(&directives, src),
+    }
+  }
+}
+
+impl<K, V> FromIterator<(K, V)> for Defines
+where
+  K: Into<String>,
+  V: ToString,
+{
+  fn from_iter<I>(iter: I) -> Self
+  where
+    I: IntoIterator<Item = (K, V)>,
+  {
+    let mut defines = Defines::new();
+
+    for (name, value) in iter {
+      defines.set(name, value);
+    }
+
+    defines
+  }
+}
+
+impl<K, V> Extend<(K, V)> for Defines
+where
+  K: Into<String>,
+  V: ToString,
+{
+  fn extend<I>(&mut self, iter: I)
+  where
+    I: IntoIterator<Item = (K, V)>,
+  {
+    for (name, value) in iter {
+      self.set(name, value);
+    }
+  }
+}
+
+// offset right after the #version line of a source, if any; only blank lines and line comments
+// can precede it
+fn version_line_end(src: &str) -> Option<usize> {
+  let mut offset = 0;
+
+  for line in src.split_inclusive('\n') {
+    let trimmed = line.trim();
+    offset += line.len();
+
+    if trimmed.is_empty() || trimmed.starts_with("//") {
+      continue;
+    }
+
+    if trimmed.starts_with("#version") {
+      return Some(offset);
+    }
+
+    break;
+  }
+
+  None
+}
+
+/// Programs built from the same sources with different [`Defines`].
+///
+/// # Parametricity
+///
+/// - `B` is the backend type.
+/// - `Sem` is the [`Semantics`] type.
+/// - `Out` is the render target type.
+/// - `Uni` is the [`UniformInterface`] type.
+pub struct ProgramPermutations<B, Sem, Out, Uni>
+where
+  B: ?Sized + Shader,
+{
+  vertex: String,
+  tess: Option<(String, String)>,
+  geometry: Option<String>,
+  fragment: String,
+  programs: HashMap<Defines, Permutation<B, Sem, Out, Uni>>,
+}
+
+// a built permutation, along with the warnings emitted while building it
+struct Permutation<B, Sem, Out, Uni>
+where
+  B: ?Sized + Shader,
+{
+  program: Program<B, Sem, Out, Uni>,
+  warnings: Vec<ProgramError>,
+}
+
+impl<B, Sem, Out, Uni> ProgramPermutations<B, Sem, Out, Uni>
+where
+  B: ?Sized + Shader,
+  Sem: Semantics,
+{
+  /// Create permutations of the program made of the `vertex` and `fragment` stages.
+  ///
+  /// No program is built until one is asked.
+  pub fn new(vertex: impl Into<String>, fragment: impl Into<String>) -> Self {
+    ProgramPermutations {
+      vertex: vertex.into(),
+      tess: None,
+      geometry: None,
+      fragment: fragment.into(),
+      programs: HashMap::new(),
+    }
+  }
+
+  /// Add tessellation stages to the program.
+  pub fn with_tess(mut self, control: impl Into<String>, evaluation: impl Into<String>) -> Self {
+    self.tess = Some((control.into(), evaluation.into()));
+    self
+  }
+
+  /// Add a geometry stage to the program.
+  pub fn with_geometry(mut self, geometry: impl Into<String>) -> Self {
+    self.geometry = Some(geometry.into());
+    self
+  }
+
+  /// Get the [`Program`] built with `defines`, building it if it was not already, by using a
+  /// mutable environment variable to create its [`UniformInterface`].
+  ///
+  /// If the build fails, the error is returned and nothing is cached, so the next call will try to
+  /// build the program again.
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  /// - `E` is the mutable environment variable.
+  pub fn program_env<C, E>(
+    &mut self,
+    ctx: &mut C,
+    defines: &Defines,
+    env: &mut E,
+  ) -> Result<&mut Program<B, Sem, Out, Uni>, ProgramError>
+  where
+    C: GraphicsContext<Backend = B>,
+    Uni: UniformInterface<B, E>,
+  {
+    if !self.programs.contains_key(defines) {
+      let BuiltProgram { program, warnings } = ProgramBuilder::new(ctx)
+        .from_strings_with_defines_env(
+          defines,
+          &self.vertex,
+          self
+            .tess
+            .as_ref()
+            .map(|(control, evaluation)| TessellationStages {
+              control: control.as_str(),
+              evaluation: evaluation.as_str(),
+            }),
+          self.geometry.as_deref(),
+          &self.fragment,
+          env,
+        )?;
+
+      self
+        .programs
+        .insert(defines.clone(), Permutation { program, warnings });
+    }
+
+    // the permutation is built at that point
+    Ok(&mut self.programs.get_mut(defines).unwrap().program)
+  }
+
+  /// Get the [`Program`] built with `defines`, building it if it was not already.
+  ///
+  /// See the documentation of [`ProgramPermutations::program_env`] for further details.
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  pub fn program<C>(
+    &mut self,
+    ctx: &mut C,
+    defines: &Defines,
+  ) -> Result<&mut Program<B, Sem, Out, Uni>, ProgramError>
+  where
+    C: GraphicsContext<Backend = B>,
+    Uni: UniformInterface<B>,
+  {
+    self.program_env(ctx, defines, &mut ())
+  }
+
+  /// Get the [`Program`] already built with `defines`, if any.
+  pub fn get(&self, defines: &Defines) -> Option<&Program<B, Sem, Out, Uni>> {
+    self
+      .programs
+      .get(defines)
+      .map(|permutation| &permutation.program)
+  }
+
+  /// Get the [`Program`] already built with `defines` mutably, if any.
+  pub fn get_mut(&mut self, defines: &Defines) -> Option<&mut Program<B, Sem, Out, Uni>> {
+    self
+      .programs
+      .get_mut(defines)
+      .map(|permutation| &mut permutation.program)
+  }
+
+  /// Warnings emitted when building the [`Program`] with `defines`, if it was built.
+  pub fn warnings(&self, defines: &Defines) -> Option<&[ProgramError]> {
+    self
+      .programs
+      .get(defines)
+      .map(|permutation| permutation.warnings.as_slice())
+  }
+
+  /// Remove the [`Program`] built with `defines` and return it, if any.
+  pub fn remove(&mut self, defines: &Defines) -> Option<Program<B, Sem, Out, Uni>> {
+    self
+      .programs
+      .remove(defines)
+      .map(|permutation| permutation.program)
+  }
+
+  /// Remove all the built programs.
+  pub fn clear(&mut self) {
+    self.programs.clear();
+  }
+
+  /// Number of built programs.
+  pub fn len(&self) -> usize {
+    self.programs.len()
+  }
+
+  /// Whether no program is built.
+  pub fn is_empty(&self) -> bool {
+    self.programs.is_empty()
+  }
+
+  /// Iterate over the built programs, along with the [`Defines`] they were built with.
+  pub fn iter(&self) -> impl Iterator<Item = (&Defines, &Program<B, Sem, Out, Uni>)> {
+    self
+      .programs
+      .iter()
+      .map(|(defines, permutation)| (defines, &permutation.program))
+  }
+}
@@ -0,0 +1,583 @@
+//! GLSL preprocessing.
+//!
+//! GLSL doesn’t have any way to share code between sources. This module provides a small
+//! preprocessor expanding `#include "path"` directives, so that code such as lighting or BRDF
+//! functions can be written once and included in several shader stages.
+//!
+//! Include paths are resolved with an [`IncludeResolver`]. Resolvers are provided for:
+//!
+//! - Maps, such as `HashMap<&str, &str>`, mapping paths to sources.
+//! - Tables of `(path, source)` pairs, typically built with [`include_str!`].
+//! - The file system, with [`FileSystemResolver`].
+//!
+//! A source is only included once if it contains a `#pragma once` directive or if it’s protected
+//! by an include guard — i.e. its code is wrapped in `#ifndef NAME`, `#define NAME` and `#endif`.
+//! Including a source from itself, directly or not, is otherwise an error. Includes cannot be
+//! nested deeper than [`MAX_INCLUDE_DEPTH`].
+//!
+//! Preprocessing a source gives a [`PreprocessedSource`], holding the expanded code along with a
+//! [`LineMap`] mapping each line back to the source and line it comes from. Stages created with
+//! [`Stage::new_preprocessed`] use it to report compilation errors with their original locations.
+//!
+//! ```
+//! use luminance::shader::preprocessor::preprocess;
+//!
+//! let includes = [("lighting.glsl", "float lambert(vec3 n, vec3 l) {\n  return max(0., dot(n, l));\n}")];
+//! let fs = "#include \"lighting.glsl\"\nvoid main() {}";
+//! let preprocessed = preprocess("main.frag", fs, &includes).unwrap();
+//!
+//! assert_eq!(preprocessed.line_map().origin(2), Some(("lighting.glsl", 2)));
+//! ```
+//!
+//! > Conditional directives are not evaluated by the preprocessor: `#include` directives are
+//! > expanded even inside a disabled `#if` block.
+//!
+//! As with any other luminance source, the sources must not have a `#version` line: backends
+//! prepend their own prelude with [`with_prelude`], which resets line numbers so that compilation
+//! logs refer to the lines of the sources.
+//!
+//! [`Stage::new_preprocessed`]: crate::shader::Stage::new_preprocessed
+
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+use std::error;
+use std::fmt;
+use std::fs;
+use std::hash::{BuildHasher, Hash};
+use std::iter;
+use std::path::{Path, PathBuf};
+
+/// A source resolved by an [`IncludeResolver`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolvedSource {
+  /// Name of the source.
+  ///
+  /// It is used to report errors and to identify the source: two paths resolving to the same name
+  /// refer to the same source.
+  pub name: String,
+  /// GLSL code of the source.
+  pub code: String,
+}
+
+/// Resolve `#include` paths to GLSL sources.
+pub trait IncludeResolver {
+  /// Resolve `path`, included from the source named `includer`.
+  ///
+  /// Return [`None`] if there is no source at `path`.
+  fn resolve(&self, path: &str, includer: &str) -> Option<ResolvedSource>;
+}
+
+impl<K, V, S> IncludeResolver for HashMap<K, V, S>
+where
+  K: Borrow<str> + Eq + Hash,
+  V: AsRef<str>,
+  S: BuildHasher,
+{
+  fn resolve(&self, path: &str, _: &str) -> Option<ResolvedSource> {
+    self.get(path).map(|code| ResolvedSource {
+      name: path.to_owned(),
+      code: code.as_ref().to_owned(),
+    })
+  }
+}
+
+impl<'a> IncludeResolver for [(&'a str, &'a str)] {
+  fn resolve(&self, path: &str, _: &str) -> Option<ResolvedSource> {
+    self
+      .iter()
+      .find(|(name, _)| *name == path)
+      .map(|(name, code)| ResolvedSource {
+        name: (*name).to_owned(),
+        code: (*code).to_owned(),
+      })
+  }
+}
+
+impl<'a, const N: usize> IncludeResolver for [(&'a str, &'a str); N] {
+  fn resolve(&self, path: &str, includer: &str) -> Option<ResolvedSource> {
+    self[..].resolve(path, includer)
+  }
+}
+
+/// Resolve `#include` paths from the file system.
+///
+/// Paths are first looked up relative to the directory of the including source, then relative to
+/// the root directory of the resolver. Resolved sources are named after their canonical path, so
+/// that the same file is always identified by the same name, whatever the path used to include it.
+#[derive(Clone, Debug)]
+pub struct FileSystemResolver {
+  root: PathBuf,
+}
+
+impl FileSystemResolver {
+  /// Create a [`FileSystemResolver`] looking up sources relative to `root`.
+  pub fn new(root: impl Into<PathBuf>) -> Self {
+    FileSystemResolver { root: root.into() }
+  }
+
+  /// Root directory of the resolver.
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  // paths the file `path`, included from the source named `includer`, is looked up at, in order
+  pub(crate) fn candidate_paths(
+    &self,
+    path: &str,
+    includer: &str,
+  ) -> impl Iterator<Item = PathBuf> {
+    let relative = Path::new(includer).parent().map(|dir| dir.join(path));
+    relative.into_iter().chain(iter::once(self.root.join(path)))
+  }
+
+  // canonical path of the file `path`, included from the source named `includer`, resolves to
+  pub(crate) fn resolve_path(&self, path: &str, includer: &str) -> Option<PathBuf> {
+    self
+      .candidate_paths(path, includer)
+      .filter_map(|path| fs::canonicalize(path).ok())
+      .find(|path| path.is_file())
+  }
+}
+
+impl IncludeResolver for FileSystemResolver {
+  fn resolve(&self, path: &str, includer: &str) -> Option<ResolvedSource> {
+    let path = self.resolve_path(path, includer)?;
+    let code = fs::read_to_string(&path).ok()?;
+    Some(ResolvedSource {
+      name: path.to_string_lossy().into_owned(),
+      code,
+    })
+  }
+}
+
+/// Errors that might happen while preprocessing a source.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PreprocessError {
+  /// An included path cannot be resolved.
+  IncludeNotFound {
+    /// Included path.
+    path: String,
+    /// Name of the source holding the `#include` directive.
+    file: String,
+    /// Line of the `#include` directive.
+    line: usize,
+  },
+  /// A source includes itself, directly or not, without being guarded.
+  IncludeCycle {
+    /// Included path.
+    path: String,
+    /// Name of the source holding the `#include` directive.
+    file: String,
+    /// Line of the `#include` directive.
+    line: usize,
+  },
+  /// An `#include` directive is not followed by a quoted path.
+  MalformedInclude {
+    /// Name of the source holding the `#include` directive.
+    file: String,
+    /// Line of the `#include` directive.
+    line: usize,
+  },
+  /// Includes are nested deeper than [`MAX_INCLUDE_DEPTH`].
+  IncludeTooDeep {
+    /// Included path.
+    path: String,
+    /// Name of the source holding the `#include` directive.
+    file: String,
+    /// Line of the `#include` directive.
+    line: usize,
+  },
+}
+
+impl PreprocessError {
+  /// An included path cannot be resolved.
+  pub fn include_not_found(path: impl Into<String>, file: impl Into<String>, line: usize) -> Self {
+    PreprocessError::IncludeNotFound {
+      path: path.into(),
+      file: file.into(),
+      line,
+    }
+  }
+
+  /// A source includes itself, directly or not, without being guarded.
+  pub fn include_cycle(path: impl Into<String>, file: impl Into<String>, line: usize) -> Self {
+    PreprocessError::IncludeCycle {
+      path: path.into(),
+      file: file.into(),
+      line,
+    }
+  }
+
+  /// An `#include` directive is not followed by a quoted path.
+  pub fn malformed_include(file: impl Into<String>, line: usize) -> Self {
+    PreprocessError::MalformedInclude {
+      file: file.into(),
+      line,
+    }
+  }
+
+  /// Includes are nested deeper than [`MAX_INCLUDE_DEPTH`].
+  pub fn include_too_deep(path: impl Into<String>, file: impl Into<String>, line: usize) -> Self {
+    PreprocessError::IncludeTooDeep {
+      path: path.into(),
+      file: file.into(),
+      line,
+    }
+  }
+}
+
+impl fmt::Display for PreprocessError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      PreprocessError::IncludeNotFound {
+        ref path,
+        ref file,
+        line,
+      } => write!(f, "{}:{}: cannot find included source {}", file, line, path),
+
+      PreprocessError::IncludeCycle {
+        ref path,
+        ref file,
+        line,
+      } => write!(f, "{}:{}: {} includes itself", file, line, path),
+
+      PreprocessError::MalformedInclude { ref file, line } => write!(
+        f,
+        "{}:{}: #include must be followed by a quoted path",
+        file, line
+      ),
+
+      PreprocessError::IncludeTooDeep {
+        ref path,
+        ref file,
+        line,
+      } => write!(
+        f,
+        "{}:{}: cannot include {}: includes are nested deeper than {}",
+        file, line, path, MAX_INCLUDE_DEPTH
+      ),
+    }
+  }
+}
+
+impl error::Error for PreprocessError {}
+
+/// Maximum number of nested includes.
+pub const MAX_INCLUDE_DEPTH: usize = 32;
+
+/// Mapping between the lines of a [`PreprocessedSource`] and the sources they come from.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LineMap {
+  files: Vec<String>,
+  // file index and line, for each line of the preprocessed source
+  lines: Vec<(usize, usize)>,
+}
+
+impl LineMap {
+  /// Names of the sources the preprocessed source is made of, starting with the root one.
+  pub fn files(&self) -> &[String] {
+    &self.files
+  }
+
+  /// Name of the source and line a line of the preprocessed source comes from.
+  ///
+  /// Lines start at `1`, as in compilation logs.
+  pub fn origin(&self, line: usize) -> Option<(&str, usize)> {
+    let (file, line) = *self.lines.get(line.checked_sub(1)?)?;
+    Some((&self.files[file], line))
+  }
+
+  /// Rewrite the locations found in a compilation log to point to the original sources.
+  ///
+  /// Locations are recognized in the most common formats used by drivers: `0:12`, as in `0:12(5):
+  /// error: …` or `ERROR: 0:12: …`, and `0(12)`, as in `0(12) : error …`. They are rewritten as
+  /// `file:line` and `file(line)`, respectively.
+  pub fn remap_log(&self, log: &str) -> String {
+    log
+      .split('\n')
+      .map(|line| self.remap_log_line(line))
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+
+  fn remap_log_line(&self, line: &str) -> String {
+    let bytes = line.as_bytes();
+    let starts = iter::once(0).chain(
+      bytes
+        .iter()
+        .enumerate()
+        .filter(|(_, b)| b.is_ascii_whitespace())
+        .map(|(i, _)| i + 1),
+    );
+
+    for start in starts {
+      if let Some((end, location, parenthesized)) = parse_log_location(&bytes[start..]) {
+        if let Some((file, original)) = self.origin(location) {
+          let remapped = if parenthesized {
+            format!("{}({})", file, original)
+          } else {
+            format!("{}:{}", file, original)
+          };
+
+          return format!("{}{}{}", &line[..start], remapped, &line[start + end..]);
+        }
+      }
+    }
+
+    line.to_owned()
+  }
+}
+
+// parse a 0:LINE or 0(LINE) location, returning its length, the line and whether it’s parenthesized
+fn parse_log_location(bytes: &[u8]) -> Option<(usize, usize, bool)> {
+  let digits = |from: usize| {
+    bytes[from..]
+      .iter()
+      .take_while(|b| b.is_ascii_digit())
+      .count()
+  };
+
+  // only a single source string is ever compiled
+  if bytes.first() != Some(&b'0') || digits(0) != 1 {
+    return None;
+  }
+
+  let parenthesized = match bytes.get(1) {
+    Some(b':') => false,
+    Some(b'(') => true,
+    _ => None?,
+  };
+
+  let len = digits(2);
+  if len == 0 {
+    return None;
+  }
+
+  let line = std::str::from_utf8(&bytes[2..2 + len]).ok()?.parse().ok()?;
+
+  if parenthesized {
+    if bytes.get(2 + len) != Some(&b')') {
+      return None;
+    }
+
+    Some((3 + len, line, true))
+  } else {
+    Some((2 + len, line, false))
+  }
+}
+
+/// Prepend a backend `prelude` — typically its `#version` line and `#extension` directives — to
+/// `src`.
+///
+/// A `#line 1` directive is inserted between the prelude and `src`, so that the line numbers
+/// reported in compilation logs are the ones of `src`, as expected by [`LineMap::remap_log`].
+/// `prelude` must end with a newline.
+pub fn with_prelude(prelude: &str, src: &str) -> String {
+  let mut code = String::with_capacity(prelude.len() + src.len() + 8);
+  code.push_str(prelude);
+  code.push_str("#line 1\n");
+  code.push_str(src);
+  code
+}
+
+/// A preprocessed source.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreprocessedSource {
+  code: String,
+  line_map: LineMap,
+}
+
+impl PreprocessedSource {
+  /// Expanded GLSL code.
+  pub fn code(&self) -> &str {
+    &self.code
+  }
+
+  /// Mapping between the lines of the expanded code and the sources they come from.
+  pub fn line_map(&self) -> &LineMap {
+    &self.line_map
+  }
+}
+
+/// Preprocess the GLSL `code` of the source named `name`, resolving its includes with `resolver`.
+pub fn preprocess<R>(
+  name: &str,
+  code: &str,
+  resolver: &R,
+) -> Result<PreprocessedSource, PreprocessError>
+where
+  R: ?Sized + IncludeResolver,
+{
+  let mut preprocessor = Preprocessor {
+    resolver,
+    code: String::new(),
+    line_map: LineMap::default(),
+    stack: Vec::new(),
+    once: HashSet::new(),
+    guards: HashSet::new(),
+  };
+
+  preprocessor.expand(name, code)?;
+
+  Ok(PreprocessedSource {
+    code: preprocessor.code,
+    line_map: preprocessor.line_map,
+  })
+}
+
+struct Preprocessor<'a, R>
+where
+  R: ?Sized,
+{
+  resolver: &'a R,
+  code: String,
+  line_map: LineMap,
+  // names of the sources being expanded
+  stack: Vec<String>,
+  // names of the sources with #pragma once
+  once: HashSet<String>,
+  // include guards already defined
+  guards: HashSet<String>,
+}
+
+impl<'a, R> Preprocessor<'a, R>
+where
+  R: ?Sized + IncludeResolver,
+{
+  fn expand(&mut self, name: &str, code: &str) -> Result<(), PreprocessError> {
+    if let Some(guard) = include_guard(code) {
+      if !self.guards.insert(guard.to_owned()) {
+        return Ok(());
+      }
+    }
+
+    let file = match self.line_map.files.iter().position(|file| file == name) {
+      Some(file) => file,
+      None => {
+        self.line_map.files.push(name.to_owned());
+        self.line_map.files.len() - 1
+      }
+    };
+
+    self.stack.push(name.to_owned());
+
+    let mut in_comment = false;
+    for (i, line) in code.lines().enumerate() {
+      let directive = if in_comment { None } else { directive(line) };
+      in_comment = ends_in_comment(line, in_comment);
+
+      match directive {
+        Some(("pragma", "once")) => {
+          self.once.insert(name.to_owned());
+        }
+
+        Some(("include", path)) => self.include(path, name, i + 1)?,
+
+        _ => {
+          self.code.push_str(line);
+          self.code.push('\n');
+          self.line_map.lines.push((file, i + 1));
+        }
+      }
+    }
+
+    self.stack.pop();
+    Ok(())
+  }
+
+  fn include(&mut self, path: &str, includer: &str, line: usize) -> Result<(), PreprocessError> {
+    let path =
+      quoted_path(path).ok_or_else(|| PreprocessError::malformed_include(includer, line))?;
+    let source = self
+      .resolver
+      .resolve(path, includer)
+      .ok_or_else(|| PreprocessError::include_not_found(path, includer, line))?;
+
+    if self.once.contains(&source.name) {
+      return Ok(());
+    }
+
+    if self.stack.contains(&source.name) && include_guard(&source.code).is_none() {
+      return Err(PreprocessError::include_cycle(path, includer, line));
+    }
+
+    // the root source is at the bottom of the stack
+    if self.stack.len() > MAX_INCLUDE_DEPTH {
+      return Err(PreprocessError::include_too_deep(path, includer, line));
+    }
+
+    self.expand(&source.name, &source.code)
+  }
+}
+
+// parse a directive line into its name and its arguments
+fn directive(line: &str) -> Option<(&str, &str)> {
+  let line = line.trim_start().strip_prefix('#')?.trim_start();
+  let name_len = line.find(|c: char| c.is_whitespace()).unwrap_or(line.len());
+  let (name, args) = line.split_at(name_len);
+
+  Some((name, args.trim()))
+}
+
+// extract the path of #include "path", optionally followed by a comment
+fn quoted_path(args: &str) -> Option<&str> {
+  let args = args.strip_prefix('"')?;
+  let end = args.find('"')?;
+  let rest = args[end + 1..].trim_start();
+
+  if rest.is_empty() || rest.starts_with("//") || rest.starts_with("/*") {
+    Some(&args[..end])
+  } else {
+    None
+  }
+}
+
+// whether a block comment is still open at the end of the line
+fn ends_in_comment(line: &str, mut in_comment: bool) -> bool {
+  let mut rest = line;
+
+  loop {
+    if in_comment {
+      match rest.find("*/") {
+        Some(end) => {
+          rest = &rest[end + 2..];
+          in_comment = false;
+        }
+        None => return true,
+      }
+    } else {
+      match (rest.find("//"), rest.find("/*")) {
+        (Some(line_comment), Some(start)) if line_comment < start => return false,
+        (_, Some(start)) => {
+          rest = &rest[start + 2..];
+          in_comment = true;
+        }
+        _ => return false,
+      }
+    }
+  }
+}
+
+// name of the include guard of a source, if its code is wrapped in #ifndef NAME / #define NAME / #endif
+fn include_guard(code: &str) -> Option<&str> {
+  let mut lines = code
+    .lines()
+    .map(str::trim)
+    .filter(|line| !line.is_empty() && !line.starts_with("//"));
+
+  let guard = match directive(lines.next()?)? {
+    ("ifndef", guard) => guard,
+    _ => return None,
+  };
+
+  match directive(lines.next()?)? {
+    ("define", name) if name == guard => (),
+    _ => return None,
+  }
+
+  match directive(lines.next_back()?)? {
+    ("endif", _) => Some(guard),
+    _ => None,
+  }
+}
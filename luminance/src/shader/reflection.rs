@@ -0,0 +1,69 @@
+//! Shader program reflection.
+//!
+//! Reflection lists what a linked [`Program`] expects: its active uniforms, uniform blocks and
+//! vertex attributes, as reported by the backend. It is typically used by tooling, such as
+//! debuggers or material editors, or to validate a [`UniformInterface`] against a program.
+//!
+//! Only _active_ items are listed: uniforms and attributes declared in the sources but optimized
+//! out by the compiler don’t appear.
+//!
+//! [`Program`]: crate::shader::Program
+//! [`UniformInterface`]: crate::shader::UniformInterface
+
+use crate::shader::UniformType;
+
+/// An active uniform of a program.
+///
+/// Uniforms declared inside uniform blocks are not listed as active uniforms but as members of
+/// their [`ActiveUniformBlock`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActiveUniform {
+  /// Name of the uniform, as reported by the backend.
+  ///
+  /// Arrays are named after their first element, such as `lights[0]`.
+  pub name: String,
+  /// Type of the uniform, or [`None`] if the type is not supported by luminance.
+  pub ty: Option<UniformType>,
+  /// Number of elements of the uniform, which is `1` if it’s not an array.
+  pub array_size: usize,
+  /// Location of the uniform, or `-1` if the backend doesn’t expose uniform locations as
+  /// integers, as with WebGL.
+  pub location: i32,
+}
+
+/// An active uniform block of a program.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActiveUniformBlock {
+  /// Name of the uniform block.
+  pub name: String,
+  /// Index of the uniform block.
+  pub index: u32,
+  /// Size in bytes of the uniform block.
+  pub size: usize,
+  /// Active members of the uniform block.
+  pub members: Vec<UniformBlockMember>,
+}
+
+/// An active member of an [`ActiveUniformBlock`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UniformBlockMember {
+  /// Name of the member, as reported by the backend.
+  pub name: String,
+  /// Type of the member, or [`None`] if the type is not supported by luminance.
+  pub ty: Option<UniformType>,
+  /// Number of elements of the member, which is `1` if it’s not an array.
+  pub array_size: usize,
+  /// Offset in bytes of the member from the start of the block.
+  pub offset: usize,
+}
+
+/// An active vertex attribute of a program.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActiveAttribute {
+  /// Name of the attribute.
+  pub name: String,
+  /// Type of the attribute, or [`None`] if the type is not supported by luminance.
+  pub ty: Option<UniformType>,
+  /// Location of the attribute, which is `-1` for built-in attributes such as `gl_VertexID`.
+  pub location: i32,
+}
@@ -0,0 +1,541 @@
+//! Shader program hot reloading.
+//!
+//! Iterating on shaders is much faster when you don’t have to restart your application each time
+//! you change a line of GLSL. This module provides a [`ProgramWatcher`], which builds a [`Program`]
+//! from files on disk and rebuilds it when any of them changes.
+//!
+//! Sources are read and preprocessed with a [`FileSystemResolver`], so `#include` directives are
+//! supported and included files are watched too. Changes are detected by polling the modification
+//! time of the files with a [`FileWatcher`]: no thread is spawned and no event is missed, but you
+//! have to call [`ProgramWatcher::poll`] regularly — typically once per frame.
+//!
+//! When a rebuild fails, the error is reported and the previous [`Program`] is kept, so that you
+//! can fix your code without losing the current rendering. The [`UniformInterface`] can also be
+//! re-created without rebuilding the [`Program`] — for instance when its environment changed — with
+//! [`ProgramWatcher::reload_env`], which follows the semantics of [`Program::readapt_env`].
+//!
+//! ```ignore
+//! let sources = ProgramSources::new("shaders/main.vert", "shaders/main.frag");
+//! let mut watcher = ProgramWatcher::<_, Semantics, (), ShaderInterface>::new(&mut ctx, sources)?;
+//!
+//! loop {
+//!   if let Err(e) = watcher.poll(&mut ctx) {
+//!     eprintln!("cannot reload shader program: {}", e);
+//!   }
+//!
+//!   // …
+//!   shd_gate.shade(watcher.program_mut(), |iface, uni, mut rdr_gate| {
+//!     // …
+//!   })?;
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::backend::shader::Shader;
+use crate::context::GraphicsContext;
+use crate::shader::preprocessor::{
+  preprocess, FileSystemResolver, IncludeResolver, PreprocessError, PreprocessedSource,
+  ResolvedSource,
+};
+use crate::shader::{
+  BuiltProgram, Program, ProgramBuilder, ProgramError, TessellationStages, UniformInterface,
+};
+use crate::vertex::Semantics;
+
+/// Watch files for changes by polling their modification time.
+#[derive(Clone, Debug)]
+pub struct FileWatcher {
+  files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl FileWatcher {
+  /// Create a [`FileWatcher`] watching `paths`.
+  ///
+  /// Files that don’t exist yet can be watched: creating them is considered a change.
+  pub fn new<I>(paths: I) -> Self
+  where
+    I: IntoIterator,
+    I::Item: Into<PathBuf>,
+  {
+    let files = paths
+      .into_iter()
+      .map(|path| {
+        let path = path.into();
+        let modified = modification_time(&path);
+        (path, modified)
+      })
+      .collect();
+
+    FileWatcher { files }
+  }
+
+  /// Watched paths.
+  pub fn paths(&self) -> impl Iterator<Item = &Path> {
+    self.files.iter().map(|(path, _)| path.as_path())
+  }
+
+  /// Check whether any watched file has been modified, created or removed since the last call — or
+  /// since the creation of the [`FileWatcher`] for the first call.
+  pub fn changed(&mut self) -> bool {
+    let mut changed = false;
+
+    for (path, modified) in &mut self.files {
+      let now = modification_time(path);
+
+      if now != *modified {
+        *modified = now;
+        changed = true;
+      }
+    }
+
+    changed
+  }
+
+  // watch the files of `other` too, taking their modification time from it
+  fn extend(&mut self, other: FileWatcher) {
+    for (path, modified) in other.files {
+      match self.files.iter_mut().find(|(file, _)| *file == path) {
+        Some(file) => file.1 = modified,
+        None => self.files.push((path, modified)),
+      }
+    }
+  }
+}
+
+fn modification_time(path: &Path) -> Option<SystemTime> {
+  fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Paths of the sources of a shader program.
+///
+/// Relative paths are relative to the current working directory.
+#[derive(Clone, Debug)]
+pub struct ProgramSources {
+  /// Vertex shader.
+  pub vertex: PathBuf,
+  /// Tessellation control and evaluation shaders, if any.
+  pub tess: Option<(PathBuf, PathBuf)>,
+  /// Geometry shader, if any.
+  pub geometry: Option<PathBuf>,
+  /// Fragment shader.
+  pub fragment: PathBuf,
+  /// Resolver used to expand `#include` directives.
+  ///
+  /// Included paths are first looked up relative to the including file. The default resolver then
+  /// looks them up relative to the current working directory.
+  pub includes: FileSystemResolver,
+}
+
+impl ProgramSources {
+  /// Sources of a program with only a vertex and a fragment shader.
+  pub fn new(vertex: impl Into<PathBuf>, fragment: impl Into<PathBuf>) -> Self {
+    ProgramSources {
+      vertex: vertex.into(),
+      tess: None,
+      geometry: None,
+      fragment: fragment.into(),
+      includes: FileSystemResolver::new("."),
+    }
+  }
+
+  fn read(&self, path: &Path, files: &ReadFiles) -> Result<PreprocessedSource, ProgramWatchError> {
+    let code = files
+      .read(path)
+      .map_err(|e| ProgramWatchError::cannot_read(path, e.to_string()))?;
+    let resolver = WatchedResolver {
+      resolver: &self.includes,
+      files,
+    };
+    let preprocessed = preprocess(&path.to_string_lossy(), &code, &resolver)?;
+    Ok(preprocessed)
+  }
+}
+
+// files read while reading the sources of a program, along with their modification time
+#[derive(Default)]
+struct ReadFiles {
+  files: RefCell<Vec<(PathBuf, Option<SystemTime>)>>,
+}
+
+impl ReadFiles {
+  // the modification time is sampled before reading, so that a change happening while reading is
+  // not missed but detected on the next poll
+  fn read(&self, path: &Path) -> io::Result<String> {
+    self.watch(path);
+    fs::read_to_string(path)
+  }
+
+  // record a file without reading it
+  fn watch(&self, path: &Path) {
+    let modified = modification_time(path);
+
+    let mut files = self.files.borrow_mut();
+    if files.iter().all(|(file, _)| file != path) {
+      files.push((path.to_owned(), modified));
+    }
+  }
+}
+
+// resolve includes with a FileSystemResolver, recording the files read
+struct WatchedResolver<'a> {
+  resolver: &'a FileSystemResolver,
+  files: &'a ReadFiles,
+}
+
+impl<'a> IncludeResolver for WatchedResolver<'a> {
+  fn resolve(&self, path: &str, includer: &str) -> Option<ResolvedSource> {
+    let path = match self.resolver.resolve_path(path, includer) {
+      Some(path) => path,
+
+      None => {
+        // watch every path the file was looked up at, so that creating it is noticed
+        for candidate in self.resolver.candidate_paths(path, includer) {
+          self.files.watch(&candidate);
+        }
+
+        return None;
+      }
+    };
+    let code = self.files.read(&path).ok()?;
+    Some(ResolvedSource {
+      name: path.to_string_lossy().into_owned(),
+      code,
+    })
+  }
+}
+
+/// Errors that might happen while (re)building a [`Program`] watched by a [`ProgramWatcher`].
+#[non_exhaustive]
+#[derive(Debug, Eq, PartialEq)]
+pub enum ProgramWatchError {
+  /// A source file cannot be read.
+  CannotRead {
+    /// Path of the file.
+    path: PathBuf,
+    /// Reason why the file cannot be read.
+    reason: String,
+  },
+  /// A source cannot be preprocessed.
+  PreprocessError(PreprocessError),
+  /// The program cannot be built.
+  ProgramError(ProgramError),
+}
+
+impl ProgramWatchError {
+  /// A source file cannot be read.
+  pub fn cannot_read(path: impl Into<PathBuf>, reason: impl Into<String>) -> Self {
+    ProgramWatchError::CannotRead {
+      path: path.into(),
+      reason: reason.into(),
+    }
+  }
+
+  /// A source cannot be preprocessed.
+  pub fn preprocess_error(e: PreprocessError) -> Self {
+    ProgramWatchError::PreprocessError(e)
+  }
+
+  /// The program cannot be built.
+  pub fn program_error(e: ProgramError) -> Self {
+    ProgramWatchError::ProgramError(e)
+  }
+}
+
+impl fmt::Display for ProgramWatchError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      ProgramWatchError::CannotRead {
+        ref path,
+        ref reason,
+      } => write!(f, "cannot read {}: {}", path.display(), reason),
+
+      ProgramWatchError::PreprocessError(ref e) => write!(f, "preprocessing error: {}", e),
+
+      ProgramWatchError::ProgramError(ref e) => write!(f, "{}", e),
+    }
+  }
+}
+
+impl error::Error for ProgramWatchError {
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    match self {
+      ProgramWatchError::PreprocessError(e) => Some(e),
+      ProgramWatchError::ProgramError(e) => Some(e),
+      _ => None,
+    }
+  }
+}
+
+impl From<PreprocessError> for ProgramWatchError {
+  fn from(e: PreprocessError) -> Self {
+    ProgramWatchError::PreprocessError(e)
+  }
+}
+
+impl From<ProgramError> for ProgramWatchError {
+  fn from(e: ProgramError) -> Self {
+    ProgramWatchError::ProgramError(e)
+  }
+}
+
+/// A [`Program`] rebuilt when its source files change.
+///
+/// # Parametricity
+///
+/// - `B` is the backend type.
+/// - `Sem` is the [`Semantics`] type.
+/// - `Out` is the render target type.
+/// - `Uni` is the [`UniformInterface`] type.
+pub struct ProgramWatcher<B, Sem, Out, Uni>
+where
+  B: ?Sized + Shader,
+{
+  sources: ProgramSources,
+  watcher: FileWatcher,
+  program: Program<B, Sem, Out, Uni>,
+  warnings: Vec<ProgramError>,
+}
+
+impl<B, Sem, Out, Uni> ProgramWatcher<B, Sem, Out, Uni>
+where
+  B: ?Sized + Shader,
+  Sem: Semantics,
+{
+  /// Build the [`Program`] from `sources` and start watching them, by using a mutable environment
+  /// variable to create the [`UniformInterface`].
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  /// - `E` is the mutable environment variable.
+  pub fn new_env<C, E>(
+    ctx: &mut C,
+    sources: ProgramSources,
+    env: &mut E,
+  ) -> Result<Self, ProgramWatchError>
+  where
+    C: GraphicsContext<Backend = B>,
+    Uni: UniformInterface<B, E>,
+  {
+    let (stages, watcher) = Stages::read(&sources);
+    let BuiltProgram { program, warnings } = stages?.build(ctx, env)?;
+
+    Ok(ProgramWatcher {
+      sources,
+      watcher,
+      program,
+      warnings,
+    })
+  }
+
+  /// Build the [`Program`] from `sources` and start watching them.
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  pub fn new<C>(ctx: &mut C, sources: ProgramSources) -> Result<Self, ProgramWatchError>
+  where
+    C: GraphicsContext<Backend = B>,
+    Uni: UniformInterface<B>,
+  {
+    Self::new_env(ctx, sources, &mut ())
+  }
+
+  /// Rebuild the [`Program`] if any of its source files — included ones too — changed, by using a
+  /// mutable environment variable to re-create the [`UniformInterface`].
+  ///
+  /// Return `Ok(true)` if the program was rebuilt and `Ok(false)` if nothing changed. If the
+  /// rebuild fails, the error is returned and the previous program is kept; it will be rebuilt
+  /// again on the next change — creating an included file that was missing counts as a change.
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  /// - `E` is the mutable environment variable.
+  pub fn poll_env<C, E>(&mut self, ctx: &mut C, env: &mut E) -> Result<bool, ProgramWatchError>
+  where
+    C: GraphicsContext<Backend = B>,
+    Uni: UniformInterface<B, E>,
+  {
+    if !self.watcher.changed() {
+      return Ok(false);
+    }
+
+    let (stages, watcher) = Stages::read(&self.sources);
+
+    let stages = match stages {
+      Ok(stages) => {
+        // the set of included files might have changed, so watch the new ones even if the build
+        // fails
+        self.watcher = watcher;
+        stages
+      }
+
+      Err(e) => {
+        // the failed read might have stopped before reaching some of the files, so keep watching
+        // them along with the ones it tried, such as an include that doesn’t exist yet
+        self.watcher.extend(watcher);
+        return Err(e);
+      }
+    };
+
+    let BuiltProgram { program, warnings } = stages.build(ctx, env)?;
+    self.program = program;
+    self.warnings = warnings;
+
+    Ok(true)
+  }
+
+  /// Rebuild the [`Program`] if any of its source files — included ones too — changed.
+  ///
+  /// See the documentation of [`ProgramWatcher::poll_env`] for further details.
+  ///
+  /// # Parametricity
+  ///
+  /// - `C` is the graphics context.
+  pub fn poll<C>(&mut self, ctx: &mut C) -> Result<bool, ProgramWatchError>
+  where
+    C: GraphicsContext<Backend = B>,
+    Uni: UniformInterface<B>,
+  {
+    self.poll_env(ctx, &mut ())
+  }
+
+  /// Re-create the [`UniformInterface`] of the current [`Program`] without rebuilding it, by using
+  /// a mutable environment variable.
+  ///
+  /// This is typically useful when the environment changed, and follows the semantics of
+  /// [`Program::readapt_env`]. If the [`UniformInterface`] cannot be re-created, the previous one
+  /// is kept.
+  ///
+  /// # Parametricity
+  ///
+  /// - `E` is the mutable environment variable.
+  pub fn reload_env<E>(&mut self, env: &mut E) -> Result<(), ProgramWatchError>
+  where
+    Uni: UniformInterface<B, E>,
+  {
+    self.warnings = self.program.readapt_env_mut(env)?;
+    Ok(())
+  }
+
+  /// Re-create the [`UniformInterface`] of the current [`Program`] without rebuilding it.
+  ///
+  /// See the documentation of [`ProgramWatcher::reload_env`] for further details.
+  pub fn reload(&mut self) -> Result<(), ProgramWatchError>
+  where
+    Uni: UniformInterface<B>,
+  {
+    self.reload_env(&mut ())
+  }
+
+  /// Sources of the watched [`Program`].
+  pub fn sources(&self) -> &ProgramSources {
+    &self.sources
+  }
+
+  /// Watched files, including the included ones.
+  pub fn files(&self) -> impl Iterator<Item = &Path> {
+    self.watcher.paths()
+  }
+
+  /// Warnings emitted by the last successful build of the [`Program`].
+  pub fn warnings(&self) -> &[ProgramError] {
+    &self.warnings
+  }
+
+  /// Last successfully built [`Program`].
+  pub fn program(&self) -> &Program<B, Sem, Out, Uni> {
+    &self.program
+  }
+
+  /// Last successfully built [`Program`], mutably, typically to shade with it.
+  pub fn program_mut(&mut self) -> &mut Program<B, Sem, Out, Uni> {
+    &mut self.program
+  }
+
+  /// Stop watching and get the last successfully built [`Program`].
+  pub fn into_program(self) -> Program<B, Sem, Out, Uni> {
+    self.program
+  }
+}
+
+// preprocessed sources of all the stages of a program
+struct Stages {
+  vertex: PreprocessedSource,
+  tess: Option<(PreprocessedSource, PreprocessedSource)>,
+  geometry: Option<PreprocessedSource>,
+  fragment: PreprocessedSource,
+}
+
+impl Stages {
+  // read the stages, along with a watcher of all the files read — or tried to — to do so, even if
+  // reading fails
+  fn read(sources: &ProgramSources) -> (Result<Self, ProgramWatchError>, FileWatcher) {
+    let files = ReadFiles::default();
+    let stages = Self::read_files(sources, &files);
+    let watcher = FileWatcher {
+      files: files.files.into_inner(),
+    };
+
+    (stages, watcher)
+  }
+
+  fn read_files(sources: &ProgramSources, files: &ReadFiles) -> Result<Self, ProgramWatchError> {
+    let vertex = sources.read(&sources.vertex, files)?;
+    let tess = match sources.tess {
+      Some((ref control, ref evaluation)) => Some((
+        sources.read(control, files)?,
+        sources.read(evaluation, files)?,
+      )),
+      None => None,
+    };
+    let geometry = match sources.geometry {
+      Some(ref geometry) => Some(sources.read(geometry, files)?),
+      None => None,
+    };
+    let fragment = sources.read(&sources.fragment, files)?;
+
+    Ok(Stages {
+      vertex,
+      tess,
+      geometry,
+      fragment,
+    })
+  }
+
+  fn build<C, Sem, Out, Uni, E>(
+    &self,
+    ctx: &mut C,
+    env: &mut E,
+  ) -> Result<BuiltProgram<C::Backend, Sem, Out, Uni>, ProgramError>
+  where
+    C: GraphicsContext,
+    C::Backend: Shader,
+    Sem: Semantics,
+    Uni: UniformInterface<C::Backend, E>,
+  {
+    let tess = self
+      .tess
+      .as_ref()
+      .map(|(control, evaluation)| TessellationStages {
+        control,
+        evaluation,
+      });
+
+    ProgramBuilder::new(ctx).from_preprocessed_env(
+      &self.vertex,
+      tess,
+      self.geometry.as_ref(),
+      &self.fragment,
+      env,
+    )
+  }
+}
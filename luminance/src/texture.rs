@@ -33,10 +33,19 @@ use std::error;
 use std::fmt;
 use std::marker::PhantomData;
 
-use crate::backend::texture::Texture as TextureBackend;
+use crate::backend::color_slot::ColorSlot;
+use crate::backend::depth_slot::DepthSlot;
+use crate::backend::texture::{
+  BufferTexture as BufferTextureBackend, DynTexture as DynTextureBackend,
+  PendingReadback as PendingReadbackBackend, SamplerObject as SamplerObjectBackend,
+  Texture as TextureBackend, TextureBindless, TextureCopy, TextureCopyFramebuffer, TextureReadback,
+};
 use crate::context::GraphicsContext;
 use crate::depth_test::DepthComparison;
-use crate::pixel::{Pixel, PixelFormat};
+use crate::framebuffer::Framebuffer;
+use crate::pixel::{
+  Floating, Integral, NormIntegral, NormUnsigned, Pixel, PixelFormat, SamplerType, Shadow, Unsigned,
+};
 
 /// How to wrap texture coordinates while sampling textures?
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -53,6 +62,18 @@ pub enum Wrap {
   Repeat,
   /// Same as `Repeat` but it will alternatively repeat between *[0;1]* and *[1;0]*.
   MirroredRepeat,
+  /// Textures coordinates laying outside of *[0;1]* will sample the border color, set with
+  /// [`Sampler::border_color`].
+  ClampToBorder,
+  /// Mirror the texture once around *0* and then clamp to the edge. Picture this as:
+  ///
+  /// ```ignore
+  /// coord_ith = clamp(abs(coord_ith), 0, 1);
+  /// ```
+  ///
+  /// This wrapping mode might not be available on all backends; when it’s not, it is replaced by
+  /// [`Wrap::ClampToEdge`].
+  MirrorClampToEdge,
 }
 
 /// Minification filter.
@@ -130,6 +151,17 @@ pub trait Dimensionable {
   /// For 2D sizes, it represents the area; for 3D sizes, the volume; etc.
   /// For cubemaps, it represents the side length of the cube.
   fn count(size: Self::Size) -> usize;
+
+  /// Size of the mipmap at the given `level`, `0` being the _base level_ of size `size`.
+  ///
+  /// Each level halves the extents of the previous one, down to *1*. Array layers are not
+  /// affected.
+  fn mipmap_size(size: Self::Size, level: usize) -> Self::Size;
+}
+
+// Extent of a mipmap level, never smaller than 1.
+fn mipmap_extent(extent: u32, level: usize) -> u32 {
+  extent.checked_shr(level as u32).unwrap_or(0).max(1)
 }
 
 /// Dimension of a texture.
@@ -147,6 +179,8 @@ pub enum Dim {
   Dim1Array,
   /// 2D array.
   Dim2Array,
+  /// Cubemap array.
+  CubemapArray,
 }
 
 impl fmt::Display for Dim {
@@ -158,6 +192,7 @@ impl fmt::Display for Dim {
       Dim::Cubemap => f.write_str("cubemap"),
       Dim::Dim1Array => f.write_str("1D array"),
       Dim::Dim2Array => f.write_str("2D array"),
+      Dim::CubemapArray => f.write_str("cubemap array"),
     }
   }
 }
@@ -187,6 +222,10 @@ impl Dimensionable for Dim1 {
   fn count(size: Self::Size) -> usize {
     size as usize
   }
+
+  fn mipmap_size(size: Self::Size, level: usize) -> Self::Size {
+    mipmap_extent(size, level)
+  }
 }
 
 /// 2D dimension.
@@ -222,6 +261,10 @@ impl Dimensionable for Dim2 {
   fn count([width, height]: Self::Size) -> usize {
     width as usize * height as usize
   }
+
+  fn mipmap_size([width, height]: Self::Size, level: usize) -> Self::Size {
+    [mipmap_extent(width, level), mipmap_extent(height, level)]
+  }
 }
 
 /// 3D dimension.
@@ -265,6 +308,14 @@ impl Dimensionable for Dim3 {
   fn count([width, height, depth]: Self::Size) -> usize {
     width as usize * height as usize * depth as usize
   }
+
+  fn mipmap_size([width, height, depth]: Self::Size, level: usize) -> Self::Size {
+    [
+      mipmap_extent(width, level),
+      mipmap_extent(height, level),
+      mipmap_extent(depth, level),
+    ]
+  }
 }
 
 /// Cubemap dimension.
@@ -316,6 +367,10 @@ impl Dimensionable for Cubemap {
     let size = size as usize;
     size * size
   }
+
+  fn mipmap_size(size: Self::Size, level: usize) -> Self::Size {
+    mipmap_extent(size, level)
+  }
 }
 
 /// Faces of a cubemap.
@@ -368,6 +423,10 @@ impl Dimensionable for Dim1Array {
   fn count((width, layer): Self::Size) -> usize {
     width as usize * layer as usize
   }
+
+  fn mipmap_size((width, layer): Self::Size, level: usize) -> Self::Size {
+    (mipmap_extent(width, level), layer)
+  }
 }
 
 /// 2D dimension.
@@ -411,8 +470,99 @@ impl Dimensionable for Dim2Array {
   fn count(([width, height], layer): Self::Size) -> usize {
     width as usize * height as usize * layer as usize
   }
+
+  fn mipmap_size(([width, height], layer): Self::Size, level: usize) -> Self::Size {
+    (
+      [mipmap_extent(width, level), mipmap_extent(height, level)],
+      layer,
+    )
+  }
 }
 
+/// Cubemap array dimension.
+///
+/// Sizes are given as `(size, layers)`, where `size` is the side length of the cubes and `layers`
+/// the number of cubemaps in the array. Offsets are given as `([x, y], layer, face)`.
+///
+/// Each layer of the array holds the six faces of a cubemap, so that
+/// [`Dimensionable::depth`] and [`Dimensionable::z_offset`] count _layer-faces_: uploading a
+/// region of `layers` layers covers `layers * 6` faces, starting at the given face of the given
+/// layer.
+#[derive(Clone, Copy, Debug)]
+pub struct CubemapArray;
+
+impl Dimensionable for CubemapArray {
+  type Offset = ([u32; 2], u32, CubeFace);
+  type Size = (u32, u32);
+
+  const ZERO_OFFSET: Self::Offset = ([0, 0], 0, CubeFace::PositiveX);
+
+  fn dim() -> Dim {
+    Dim::CubemapArray
+  }
+
+  fn width((size, _): Self::Size) -> u32 {
+    size
+  }
+
+  fn height((size, _): Self::Size) -> u32 {
+    size
+  }
+
+  fn depth((_, layers): Self::Size) -> u32 {
+    layers * 6
+  }
+
+  fn x_offset(off: Self::Offset) -> u32 {
+    off.0[0]
+  }
+
+  fn y_offset(off: Self::Offset) -> u32 {
+    off.0[1]
+  }
+
+  fn z_offset((_, layer, face): Self::Offset) -> u32 {
+    layer * 6 + Cubemap::z_offset(([0, 0], face))
+  }
+
+  fn count((size, layers): Self::Size) -> usize {
+    let size = size as usize;
+    size * size * layers as usize * 6
+  }
+
+  fn mipmap_size((size, layers): Self::Size, level: usize) -> Self::Size {
+    (mipmap_extent(size, level), layers)
+  }
+}
+
+/// Dimensions of textures that can be sampled in shaders with the sampler type `S`.
+///
+/// Every dimension can be sampled with the regular sampler types. Depth comparison — [`Shadow`] —
+/// is available for all of them but [`Dim3`], as there is no 3D shadow sampler; uniforms of 3D
+/// shadow bindings are then rejected at compile-time.
+pub trait SampledWith<S>: Dimensionable
+where
+  S: SamplerType,
+{
+}
+
+macro_rules! impl_SampledWith {
+  (regular $($s:ty),*) => {
+    $(
+      impl<D> SampledWith<$s> for D where D: Dimensionable {}
+    )*
+  };
+
+  (shadow $($d:ty),*) => {
+    $(
+      impl SampledWith<Shadow> for $d {}
+    )*
+  };
+}
+
+impl_SampledWith!(regular NormIntegral, NormUnsigned, Integral, Unsigned, Floating);
+impl_SampledWith!(shadow Dim1, Dim2, Cubemap, Dim1Array, Dim2Array, CubemapArray);
+
 /// A `Sampler` object gives hint on how a `Texture` should be sampled.
 #[derive(Clone, Copy, Debug)]
 pub struct Sampler {
@@ -428,6 +578,24 @@ pub struct Sampler {
   pub mag_filter: MagFilter,
   /// For depth textures, should we perform depth comparison and if so, how?
   pub depth_comparison: Option<DepthComparison>,
+  /// Maximum anisotropy to use when filtering.
+  ///
+  /// `None` disables anisotropic filtering. Values are clamped to the maximum supported by the
+  /// backend. If the backend doesn’t support anisotropic filtering at all, this setting is ignored.
+  pub max_anisotropy: Option<f32>,
+  /// Minimum level of detail (i.e. mipmap level) that can be selected while sampling.
+  pub min_lod: f32,
+  /// Maximum level of detail (i.e. mipmap level) that can be selected while sampling.
+  pub max_lod: f32,
+  /// Bias added to the level of detail computed while sampling.
+  pub lod_bias: f32,
+  /// Color used when sampling outside of the texture with [`Wrap::ClampToBorder`], as *RGBA*.
+  pub border_color: [f32; 4],
+  /// Channel swizzling to apply when sampling.
+  ///
+  /// Swizzling is part of the texture’s state, not the sampler’s: it is ignored by
+  /// [`SamplerObject`].
+  pub swizzle: Swizzle,
 }
 
 /// Default value is as following:
@@ -440,10 +608,92 @@ impl Default for Sampler {
       min_filter: MinFilter::NearestMipmapLinear,
       mag_filter: MagFilter::Linear,
       depth_comparison: None,
+      max_anisotropy: None,
+      min_lod: -1000.,
+      max_lod: 1000.,
+      lod_bias: 0.,
+      border_color: [0., 0., 0., 0.],
+      swizzle: Swizzle::IDENTITY,
     }
   }
 }
 
+/// Source of a channel when swizzling.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SwizzleComponent {
+  /// Red channel of the texture.
+  Red,
+  /// Green channel of the texture.
+  Green,
+  /// Blue channel of the texture.
+  Blue,
+  /// Alpha channel of the texture.
+  Alpha,
+  /// Constant *0*.
+  Zero,
+  /// Constant *1*.
+  One,
+}
+
+/// Swizzle mask.
+///
+/// A swizzle mask tells, for each channel a shader reads, which channel of the texture it actually
+/// comes from. For instance, a single-channel texture can be read as `(r, r, r, 1)` with:
+///
+/// ```
+/// use luminance::texture::{Swizzle, SwizzleComponent};
+///
+/// let swizzle = Swizzle::new(
+///   SwizzleComponent::Red,
+///   SwizzleComponent::Red,
+///   SwizzleComponent::Red,
+///   SwizzleComponent::One,
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Swizzle {
+  /// Source of the red channel.
+  pub r: SwizzleComponent,
+  /// Source of the green channel.
+  pub g: SwizzleComponent,
+  /// Source of the blue channel.
+  pub b: SwizzleComponent,
+  /// Source of the alpha channel.
+  pub a: SwizzleComponent,
+}
+
+impl Swizzle {
+  /// Swizzle mask leaving all channels untouched.
+  pub const IDENTITY: Self = Swizzle {
+    r: SwizzleComponent::Red,
+    g: SwizzleComponent::Green,
+    b: SwizzleComponent::Blue,
+    a: SwizzleComponent::Alpha,
+  };
+
+  /// Create a new [`Swizzle`] mask.
+  pub fn new(
+    r: SwizzleComponent,
+    g: SwizzleComponent,
+    b: SwizzleComponent,
+    a: SwizzleComponent,
+  ) -> Self {
+    Swizzle { r, g, b, a }
+  }
+
+  /// Whether this mask leaves all channels untouched.
+  pub fn is_identity(&self) -> bool {
+    *self == Self::IDENTITY
+  }
+}
+
+/// Default value is [`Swizzle::IDENTITY`].
+impl Default for Swizzle {
+  fn default() -> Self {
+    Self::IDENTITY
+  }
+}
+
 /// Whether mipmaps should be generated.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum GenMipmaps {
@@ -485,6 +735,21 @@ pub enum TextureError {
   CannotRetrieveTexels(String),
   /// Failed to upload texels.
   CannotUploadTexels(String),
+  /// A sampler object failed to be created.
+  ///
+  /// The carried [`String`] gives the reason of the failure.
+  SamplerCreationFailed(String),
+  /// Unsupported swizzle mask.
+  ///
+  /// Some backends cannot swizzle texture channels at all.
+  UnsupportedSwizzle(Swizzle),
+  /// Failed to copy texels.
+  CannotCopyTexels(String),
+  /// A bindless texture operation failed.
+  ///
+  /// The carried [`String`] gives the reason of the failure — most of the time, bindless textures
+  /// not being supported.
+  BindlessFailed(String),
 }
 
 impl TextureError {
@@ -515,6 +780,26 @@ impl TextureError {
   pub fn cannot_upload_texels(reason: impl Into<String>) -> Self {
     TextureError::CannotUploadTexels(reason.into())
   }
+
+  /// A sampler object failed to be created.
+  pub fn sampler_creation_failed(reason: impl Into<String>) -> Self {
+    TextureError::SamplerCreationFailed(reason.into())
+  }
+
+  /// Unsupported swizzle mask.
+  pub fn unsupported_swizzle(swizzle: Swizzle) -> Self {
+    TextureError::UnsupportedSwizzle(swizzle)
+  }
+
+  /// Failed to copy texels.
+  pub fn cannot_copy_texels(reason: impl Into<String>) -> Self {
+    TextureError::CannotCopyTexels(reason.into())
+  }
+
+  /// A bindless texture operation failed.
+  pub fn bindless_failed(reason: impl Into<String>) -> Self {
+    TextureError::BindlessFailed(reason.into())
+  }
 }
 
 impl fmt::Display for TextureError {
@@ -544,6 +829,22 @@ impl fmt::Display for TextureError {
       TextureError::CannotUploadTexels(ref e) => {
         write!(f, "cannot upload texels to texture: {}", e)
       }
+
+      TextureError::SamplerCreationFailed(ref e) => {
+        write!(f, "sampler object creation failed: {}", e)
+      }
+
+      TextureError::UnsupportedSwizzle(ref swizzle) => {
+        write!(f, "unsupported swizzle mask: {:?}", swizzle)
+      }
+
+      TextureError::CannotCopyTexels(ref e) => {
+        write!(f, "cannot copy texels: {}", e)
+      }
+
+      TextureError::BindlessFailed(ref e) => {
+        write!(f, "bindless texture operation failed: {}", e)
+      }
     }
   }
 }
@@ -559,6 +860,7 @@ where
 {
   pub(crate) repr: B::TextureRepr,
   size: D::Size,
+  sampler: Sampler,
   _phantom: PhantomData<*const P>,
 }
 
@@ -598,6 +900,7 @@ where
         .map(|repr| Texture {
           repr,
           size,
+          sampler,
           _phantom: PhantomData,
         })
     }
@@ -639,6 +942,7 @@ where
         .map(|repr| Texture {
           repr,
           size,
+          sampler,
           _phantom: PhantomData,
         })?
     };
@@ -682,6 +986,7 @@ where
         .map(|repr| Texture {
           repr,
           size,
+          sampler,
           _phantom: PhantomData,
         })?
     };
@@ -694,11 +999,27 @@ where
     unsafe { B::mipmaps(&self.repr) }
   }
 
+  /// Return the size of the texture at the given mipmap `level`, `0` being the _base level_.
+  ///
+  /// Return [`None`] if the texture doesn’t have that many levels.
+  pub fn size_at_level(&self, level: usize) -> Option<D::Size> {
+    if level < self.mipmaps() {
+      Some(D::mipmap_size(self.size, level))
+    } else {
+      None
+    }
+  }
+
   /// Return the size of the texture.
   pub fn size(&self) -> D::Size {
     self.size
   }
 
+  /// Return the [`Sampler`] the texture was created with.
+  pub fn sampler(&self) -> &Sampler {
+    &self.sampler
+  }
+
   /// Resize the texture by providing a new size and texels by reusing its GPU resources.
   ///
   /// This function works similarly to [`Texture::new`] but instead of creating a brand new texture, reuses the texture
@@ -794,6 +1115,67 @@ where
     unsafe { B::upload_raw(&mut self.repr, gen_mipmaps, self.size, texels) }
   }
 
+  /// Upload pixels to a region of the given mipmap `level` of the texture, described by the
+  /// rectangle made with `size` and `offset`.
+  ///
+  /// `offset` and `size` are expressed in the mipmap `level` coordinates — see
+  /// [`Texture::size_at_level`]. Uploading to the mipmap levels yourself allows to use
+  /// precomputed mipmaps instead of generated ones; `0` is the _base level_.
+  pub fn upload_part_level(
+    &mut self,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+    texels: &[P::Encoding],
+  ) -> Result<(), TextureError> {
+    unsafe { B::upload_part_level(&mut self.repr, level, offset, size, texels) }
+  }
+
+  /// Upload pixels to the whole mipmap `level` of the texture.
+  ///
+  /// See [`Texture::upload_part_level`] for further details.
+  pub fn upload_level(&mut self, level: usize, texels: &[P::Encoding]) -> Result<(), TextureError> {
+    let size = self.level_size_or_err(level)?;
+    unsafe { B::upload_part_level(&mut self.repr, level, D::ZERO_OFFSET, size, texels) }
+  }
+
+  /// Upload raw data to a region of the given mipmap `level` of the texture, described by the
+  /// rectangle made with `size` and `offset`.
+  ///
+  /// See [`Texture::upload_part_level`] for further details.
+  pub fn upload_part_level_raw(
+    &mut self,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+    texels: &[P::RawEncoding],
+  ) -> Result<(), TextureError> {
+    unsafe { B::upload_part_level_raw(&mut self.repr, level, offset, size, texels) }
+  }
+
+  /// Upload raw data to the whole mipmap `level` of the texture.
+  ///
+  /// See [`Texture::upload_part_level`] for further details.
+  pub fn upload_level_raw(
+    &mut self,
+    level: usize,
+    texels: &[P::RawEncoding],
+  ) -> Result<(), TextureError> {
+    let size = self.level_size_or_err(level)?;
+    unsafe { B::upload_part_level_raw(&mut self.repr, level, D::ZERO_OFFSET, size, texels) }
+  }
+
+  // Size of a mipmap level, failing to upload to it if it doesn’t exist.
+  fn level_size_or_err(&self, level: usize) -> Result<D::Size, TextureError> {
+    self.size_at_level(level).ok_or_else(|| {
+      TextureError::cannot_upload_texels(format!(
+        "mipmap level {} out of bounds (texture has {} levels)",
+        level,
+        self.mipmaps()
+      ))
+    })
+  }
+
   /// Get a copy of all the pixels from the texture.
   pub fn get_raw_texels(&self) -> Result<Vec<P::RawEncoding>, TextureError>
   where
@@ -801,4 +1183,591 @@ where
   {
     unsafe { B::get_raw_texels(&self.repr, self.size) }
   }
+
+  /// Get a copy of the pixels of a region of the texture, in the given mipmap `level`.
+  ///
+  /// The region starts at `offset` and spans `size`, both expressed in the mipmap `level`
+  /// coordinates — see [`Texture::size_at_level`]. For [`Cubemap`] textures, a single face —
+  /// given by `offset` — is read back. For textures with layers (3D and arrays), layers are
+  /// returned one after the other.
+  pub fn get_raw_texels_part(
+    &self,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+  ) -> Result<Vec<P::RawEncoding>, TextureError>
+  where
+    P::RawEncoding: Copy + Default,
+  {
+    unsafe { B::get_raw_texels_part(&self.repr, level, offset, size) }
+  }
+
+  /// Start reading back the pixels of a region of the texture, in the given mipmap `level`,
+  /// without waiting for the GPU.
+  ///
+  /// This is the asynchronous version of [`Texture::get_raw_texels_part`]: the texels are
+  /// retrieved later via the returned [`PendingReadback`], once the GPU is done with them.
+  pub fn read_texels_async(
+    &self,
+    level: usize,
+    offset: D::Offset,
+    size: D::Size,
+  ) -> Result<PendingReadback<B, P>, TextureError>
+  where
+    B: TextureReadback<D, P>,
+  {
+    unsafe {
+      B::start_texture_readback(&self.repr, level, offset, size).map(PendingReadback::from_repr)
+    }
+  }
+
+  /// Copy a region of another texture into this texture, without going through the CPU.
+  ///
+  /// The region of `src` starting at `src_offset` in the mipmap level `src_level` and spanning
+  /// `size` is copied into this texture, at `dst_offset` in the mipmap level `dst_level`. Levels
+  /// are `0` for the _base level_.
+  ///
+  /// For [`Cubemap`] textures, a single face — given by the offsets — is copied.
+  pub fn copy_from(
+    &mut self,
+    src: &Self,
+    src_level: usize,
+    src_offset: D::Offset,
+    dst_level: usize,
+    dst_offset: D::Offset,
+    size: D::Size,
+  ) -> Result<(), TextureError>
+  where
+    B: TextureCopy<D, P>,
+  {
+    unsafe {
+      B::copy_texture(
+        &mut self.repr,
+        &src.repr,
+        src_level,
+        src_offset,
+        dst_level,
+        dst_offset,
+        size,
+      )
+    }
+  }
+
+  /// Copy a rectangular region of a framebuffer’s color attachment into this texture.
+  ///
+  /// The region starting at `src_offset` and spanning `size` in the color attachment at
+  /// `attachment_index` is copied into this texture, at `dst_offset` in the mipmap level
+  /// `dst_level`. For textures with layers (3D, cubemaps, arrays), the copied region is a single
+  /// layer. For back buffers, `attachment_index` must be `0`.
+  pub fn copy_from_framebuffer<FD, CS, DS>(
+    &mut self,
+    framebuffer: &Framebuffer<B, FD, CS, DS>,
+    attachment_index: usize,
+    src_offset: [u32; 2],
+    dst_level: usize,
+    dst_offset: D::Offset,
+    size: [u32; 2],
+  ) -> Result<(), TextureError>
+  where
+    B: TextureCopyFramebuffer<D, P, FD>,
+    FD: Dimensionable,
+    CS: ColorSlot<B, FD>,
+    DS: DepthSlot<B, FD>,
+  {
+    unsafe {
+      B::copy_from_framebuffer(
+        &mut self.repr,
+        &framebuffer.repr,
+        attachment_index,
+        src_offset,
+        dst_level,
+        dst_offset,
+        size,
+      )
+    }
+  }
+
+  /// Get a resident [`TextureHandle`] to access this texture in shaders without binding it.
+  ///
+  /// Once a handle is obtained, the texture’s sampling parameters are frozen. The handle stays
+  /// valid until the texture is dropped, and can be used in shaders as long as the texture is
+  /// resident — see [`Texture::make_non_resident`].
+  pub fn bindless_handle(&mut self) -> Result<TextureHandle<D, P::SamplerType>, TextureError>
+  where
+    B: TextureBindless<D, P>,
+  {
+    unsafe {
+      let handle = B::bindless_handle(&mut self.repr)?;
+      B::set_bindless_residency(&mut self.repr, true)?;
+
+      Ok(TextureHandle {
+        handle,
+        _phantom: PhantomData,
+      })
+    }
+  }
+
+  /// Make the texture resident, so that its [`TextureHandle`] can be used in shaders.
+  pub fn make_resident(&mut self) -> Result<(), TextureError>
+  where
+    B: TextureBindless<D, P>,
+  {
+    unsafe { B::set_bindless_residency(&mut self.repr, true) }
+  }
+
+  /// Make the texture non-resident.
+  ///
+  /// Its [`TextureHandle`] must not be used in shaders until the texture is made resident again.
+  pub fn make_non_resident(&mut self) -> Result<(), TextureError>
+  where
+    B: TextureBindless<D, P>,
+  {
+    unsafe { B::set_bindless_residency(&mut self.repr, false) }
+  }
+}
+
+/// Bindless handle of a [`Texture`].
+///
+/// Such a handle is obtained with [`Texture::bindless_handle`] and can be set on a [`Uniform`] as
+/// a regular texture binding, without binding the texture in a pipeline. It is a plain 64-bit
+/// value, so it can also be stored in any GPU memory read by shaders.
+///
+/// # Parametricity
+///
+/// - `D` is the dimension of the texture.
+/// - `S` is the [`SamplerType`] of the texture.
+///
+/// [`Uniform`]: crate::shader::Uniform
+/// [`SamplerType`]: crate::pixel::SamplerType
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct TextureHandle<D, S> {
+  handle: u64,
+  _phantom: PhantomData<*const (D, S)>,
+}
+
+impl<D, S> Clone for TextureHandle<D, S> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<D, S> Copy for TextureHandle<D, S> {}
+
+impl<D, S> TextureHandle<D, S> {
+  /// Access the underlying handle value.
+  pub fn handle(self) -> u64 {
+    self.handle
+  }
+}
+
+/// Standalone sampler object.
+///
+/// A [`SamplerObject`] holds a [`Sampler`] that is not tied to any [`Texture`]. Binding a texture
+/// along with a [`SamplerObject`] — see [`Pipeline::bind_texture_with_sampler`] — makes the
+/// texture sampled with the [`SamplerObject`]’s parameters instead of the ones it was created with.
+/// That allows to sample the same texture in different ways without having to duplicate it.
+///
+/// [`Pipeline::bind_texture_with_sampler`]: crate::pipeline::Pipeline::bind_texture_with_sampler
+pub struct SamplerObject<B>
+where
+  B: ?Sized + SamplerObjectBackend,
+{
+  pub(crate) repr: B::SamplerObjectRepr,
+  sampler: Sampler,
+}
+
+impl<B> SamplerObject<B>
+where
+  B: ?Sized + SamplerObjectBackend,
+{
+  /// Create a new [`SamplerObject`] from a [`Sampler`].
+  ///
+  /// # Notes
+  ///
+  /// Feel free to have a look at the documentation of [`GraphicsContext::new_sampler`] for a
+  /// simpler interface.
+  pub fn new<C>(ctx: &mut C, sampler: Sampler) -> Result<Self, TextureError>
+  where
+    C: GraphicsContext<Backend = B>,
+  {
+    unsafe {
+      ctx
+        .backend()
+        .new_sampler_object(sampler)
+        .map(|repr| SamplerObject { repr, sampler })
+    }
+  }
+
+  /// Get the [`Sampler`] this object was created with.
+  pub fn sampler(&self) -> &Sampler {
+    &self.sampler
+  }
+}
+
+/// Buffer texture.
+///
+/// A [`BufferTexture`] is a one-dimensional array of texels stored in a GPU buffer. Contrary to
+/// [`Texture`], it cannot be sampled: shaders fetch its texels by index (`texelFetch` on a
+/// `samplerBuffer`). However, it can hold far more texels than a 1D texture, which makes it a
+/// good fit for large lookup tables or skinning matrices.
+///
+/// Buffer textures are bound with [`Pipeline::bind_buffer_texture`].
+///
+/// # Parametricity
+///
+/// - `B` is the backend type. It must implement [`backend::texture::BufferTexture`].
+/// - `P` is the pixel type. It must implement [`Pixel`].
+///
+/// [`Pipeline::bind_buffer_texture`]: crate::pipeline::Pipeline::bind_buffer_texture
+/// [`backend::texture::BufferTexture`]: crate::backend::texture::BufferTexture
+pub struct BufferTexture<B, P>
+where
+  B: ?Sized + BufferTextureBackend<P>,
+  P: Pixel,
+{
+  pub(crate) repr: B::BufferTextureRepr,
+  len: usize,
+  _phantom: PhantomData<*const P>,
+}
+
+impl<B, P> BufferTexture<B, P>
+where
+  B: ?Sized + BufferTextureBackend<P>,
+  P: Pixel,
+{
+  /// Create a new [`BufferTexture`] holding the given texels.
+  ///
+  /// # Notes
+  ///
+  /// Feel free to have a look at the documentation of [`GraphicsContext::new_buffer_texture`] for
+  /// a simpler interface.
+  pub fn new<C>(ctx: &mut C, texels: &[P::Encoding]) -> Result<Self, TextureError>
+  where
+    C: GraphicsContext<Backend = B>,
+  {
+    unsafe {
+      ctx
+        .backend()
+        .new_buffer_texture(texels)
+        .map(|repr| BufferTexture {
+          repr,
+          len: texels.len(),
+          _phantom: PhantomData,
+        })
+    }
+  }
+
+  /// Create a new [`BufferTexture`] holding the given raw texels.
+  ///
+  /// [`TextureError::CannotUploadTexels`] is returned if `texels` ends with a partial texel.
+  ///
+  /// # Notes
+  ///
+  /// Feel free to have a look at the documentation of [`GraphicsContext::new_buffer_texture_raw`]
+  /// for a simpler interface.
+  pub fn new_raw<C>(ctx: &mut C, texels: &[P::RawEncoding]) -> Result<Self, TextureError>
+  where
+    C: GraphicsContext<Backend = B>,
+  {
+    let len = Self::raw_texels_len(texels)?;
+
+    unsafe {
+      ctx
+        .backend()
+        .new_buffer_texture_raw(texels)
+        .map(|repr| BufferTexture {
+          repr,
+          len,
+          _phantom: PhantomData,
+        })
+    }
+  }
+
+  /// Number of texels in the buffer texture.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Whether the buffer texture holds no texels.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Upload texels to the buffer texture, starting at the texel at index `offset`.
+  pub fn upload_part(&mut self, offset: usize, texels: &[P::Encoding]) -> Result<(), TextureError> {
+    self.check_bounds(offset, texels.len())?;
+    unsafe { B::upload_buffer_texture_part(&mut self.repr, offset, texels) }
+  }
+
+  /// Upload raw texels to the buffer texture, starting at the texel at index `offset`.
+  ///
+  /// [`TextureError::CannotUploadTexels`] is returned if `texels` ends with a partial texel.
+  pub fn upload_part_raw(
+    &mut self,
+    offset: usize,
+    texels: &[P::RawEncoding],
+  ) -> Result<(), TextureError> {
+    self.check_bounds(offset, Self::raw_texels_len(texels)?)?;
+    unsafe { B::upload_buffer_texture_part_raw(&mut self.repr, offset, texels) }
+  }
+
+  /// Number of texels in raw texels, which must not end with a partial texel.
+  fn raw_texels_len(texels: &[P::RawEncoding]) -> Result<usize, TextureError> {
+    let raw_len = P::pixel_format().raw_len();
+
+    if texels.len() % raw_len != 0 {
+      Err(TextureError::cannot_upload_texels(format!(
+        "{} raw values are not a whole number of texels ({} per texel)",
+        texels.len(),
+        raw_len
+      )))
+    } else {
+      Ok(texels.len() / raw_len)
+    }
+  }
+
+  fn check_bounds(&self, offset: usize, len: usize) -> Result<(), TextureError> {
+    if offset + len > self.len {
+      Err(TextureError::cannot_upload_texels(format!(
+        "uploading {} texels at offset {} overflows the buffer texture ({} texels)",
+        len, offset, self.len
+      )))
+    } else {
+      Ok(())
+    }
+  }
+}
+
+/// GPU textures which pixel format is only known at runtime.
+///
+/// [`Texture`] requires its pixel type at compile-time, which is not always possible — asset
+/// loaders, for instance, only know the pixel format of the textures they load once they have read
+/// them. A [`DynTexture`] carries its [`PixelFormat`] at runtime instead and is fed with raw bytes,
+/// laid out as the [`Pixel::RawEncoding`] of the pixel type matching that format.
+///
+/// Dynamic textures are bound with [`Pipeline::bind_dyn_texture`]. Once the pixel format is known,
+/// they can be turned into typed textures with [`DynTexture::into_texture`].
+///
+/// # Parametricity
+///
+/// - `B` is the backend type. It must implement [`backend::texture::DynTexture`].
+/// - `D` is the dimension type. It must implement [`Dimensionable`].
+///
+/// [`Pipeline::bind_dyn_texture`]: crate::pipeline::Pipeline::bind_dyn_texture
+/// [`backend::texture::DynTexture`]: crate::backend::texture::DynTexture
+pub struct DynTexture<B, D>
+where
+  B: ?Sized + DynTextureBackend<D>,
+  D: Dimensionable,
+{
+  pub(crate) repr: B::TextureRepr,
+  size: D::Size,
+  sampler: Sampler,
+  pixel_format: PixelFormat,
+}
+
+impl<B, D> DynTexture<B, D>
+where
+  B: ?Sized + DynTextureBackend<D>,
+  D: Dimensionable,
+{
+  /// Create a new [`DynTexture`] by reserving space for texels of the given [`PixelFormat`].
+  ///
+  /// `size`, `mipmaps` and `sampler` have the same meaning as in [`Texture::new`].
+  ///
+  /// # Notes
+  ///
+  /// Feel free to have a look at the documentation of [`GraphicsContext::new_dyn_texture`] for a
+  /// simpler interface.
+  pub fn new<C>(
+    ctx: &mut C,
+    size: D::Size,
+    mipmaps: usize,
+    sampler: Sampler,
+    pixel_format: PixelFormat,
+  ) -> Result<Self, TextureError>
+  where
+    C: GraphicsContext<Backend = B>,
+  {
+    unsafe {
+      ctx
+        .backend()
+        .new_dyn_texture(size, mipmaps, sampler, pixel_format)
+        .map(|repr| DynTexture {
+          repr,
+          size,
+          sampler,
+          pixel_format,
+        })
+    }
+  }
+
+  /// Create a new [`DynTexture`] of the given [`PixelFormat`] with raw texels.
+  ///
+  /// `texels` must hold at least enough bytes to fill the whole texture; otherwise,
+  /// [`TextureError::NotEnoughPixels`] is returned.
+  ///
+  /// # Notes
+  ///
+  /// Feel free to have a look at the documentation of [`GraphicsContext::new_dyn_texture_raw`] for
+  /// a simpler interface.
+  pub fn new_raw<C>(
+    ctx: &mut C,
+    size: D::Size,
+    mipmaps: usize,
+    sampler: Sampler,
+    pixel_format: PixelFormat,
+    gen_mipmaps: GenMipmaps,
+    texels: &[u8],
+  ) -> Result<Self, TextureError>
+  where
+    C: GraphicsContext<Backend = B>,
+  {
+    let mut tex = Self::new(ctx, size, mipmaps, sampler, pixel_format)?;
+    tex.upload_raw(gen_mipmaps, texels)?;
+    Ok(tex)
+  }
+
+  /// Return the number of mipmaps.
+  pub fn mipmaps(&self) -> usize {
+    unsafe { B::dyn_mipmaps(&self.repr) }
+  }
+
+  /// Return the size of the texture.
+  pub fn size(&self) -> D::Size {
+    self.size
+  }
+
+  /// Return the [`Sampler`] the texture was created with.
+  pub fn sampler(&self) -> &Sampler {
+    &self.sampler
+  }
+
+  /// Return the [`PixelFormat`] of the texture.
+  pub fn pixel_format(&self) -> PixelFormat {
+    self.pixel_format
+  }
+
+  /// Upload raw texels to a part of the texture.
+  ///
+  /// `texels` must hold at least enough bytes to fill the region; otherwise,
+  /// [`TextureError::NotEnoughPixels`] is returned.
+  pub fn upload_part_raw(
+    &mut self,
+    gen_mipmaps: GenMipmaps,
+    offset: D::Offset,
+    size: D::Size,
+    texels: &[u8],
+  ) -> Result<(), TextureError> {
+    let expected_bytes = D::count(size) * self.pixel_format.raw_bytes_len();
+
+    if texels.len() < expected_bytes {
+      return Err(TextureError::not_enough_pixels(
+        expected_bytes,
+        texels.len(),
+      ));
+    }
+
+    unsafe {
+      B::upload_dyn_part_raw(
+        &mut self.repr,
+        self.pixel_format,
+        gen_mipmaps,
+        offset,
+        size,
+        texels,
+      )
+    }
+  }
+
+  /// Upload raw texels to the whole texture.
+  ///
+  /// `texels` must hold at least enough bytes to fill the whole texture; otherwise,
+  /// [`TextureError::NotEnoughPixels`] is returned.
+  pub fn upload_raw(&mut self, gen_mipmaps: GenMipmaps, texels: &[u8]) -> Result<(), TextureError> {
+    self.upload_part_raw(gen_mipmaps, D::ZERO_OFFSET, self.size, texels)
+  }
+
+  /// Turn the [`DynTexture`] into a typed [`Texture`].
+  ///
+  /// The [`DynTexture`] is given back if its [`PixelFormat`] is not the one of `P`.
+  pub fn into_texture<P>(self) -> Result<Texture<B, D, P>, Self>
+  where
+    B: TextureBackend<D, P>,
+    P: Pixel,
+  {
+    if self.pixel_format == P::pixel_format() {
+      Ok(Texture {
+        repr: self.repr,
+        size: self.size,
+        sampler: self.sampler,
+        _phantom: PhantomData,
+      })
+    } else {
+      Err(self)
+    }
+  }
+}
+
+impl<B, D, P> From<Texture<B, D, P>> for DynTexture<B, D>
+where
+  B: ?Sized + TextureBackend<D, P> + DynTextureBackend<D>,
+  D: Dimensionable,
+  P: Pixel,
+{
+  fn from(texture: Texture<B, D, P>) -> Self {
+    DynTexture {
+      repr: texture.repr,
+      size: texture.size,
+      sampler: texture.sampler,
+      pixel_format: P::pixel_format(),
+    }
+  }
+}
+
+/// Texels being read back from the GPU.
+///
+/// Such objects are returned by [`Texture::read_texels_async`] and
+/// [`Framebuffer::read_color_async`]. Reading back texels that way doesn’t stall the GPU: the
+/// texels are copied into a GPU-side buffer and can be retrieved later, either by polling with
+/// [`PendingReadback::try_get`] or by blocking with [`PendingReadback::wait`].
+pub struct PendingReadback<B, P>
+where
+  B: ?Sized + PendingReadbackBackend<P>,
+  P: Pixel,
+{
+  repr: B::PendingReadbackRepr,
+  _phantom: PhantomData<*const P>,
+}
+
+impl<B, P> PendingReadback<B, P>
+where
+  B: ?Sized + PendingReadbackBackend<P>,
+  P: Pixel,
+{
+  pub(crate) fn from_repr(repr: B::PendingReadbackRepr) -> Self {
+    PendingReadback {
+      repr,
+      _phantom: PhantomData,
+    }
+  }
+
+  /// Get the texels if the GPU is done reading them back.
+  ///
+  /// Returns `Ok(None)` if the texels are not available yet.
+  pub fn try_get(&mut self) -> Result<Option<Vec<P::RawEncoding>>, TextureError>
+  where
+    P::RawEncoding: Copy + Default,
+  {
+    unsafe { B::try_get_readback(&mut self.repr) }
+  }
+
+  /// Wait for the GPU to be done reading back the texels and get them.
+  pub fn wait(mut self) -> Result<Vec<P::RawEncoding>, TextureError>
+  where
+    P::RawEncoding: Copy + Default,
+  {
+    unsafe { B::wait_readback(&mut self.repr) }
+  }
 }
@@ -124,6 +124,29 @@ impl VertexAttribDesc {
       ..self
     }
   }
+
+  /// GLSL type of the attribute, as seen by a vertex shader.
+  ///
+  /// Normalized integral attributes are seen as floating-point values and boolean attributes as
+  /// unsigned integral values.
+  pub fn glsl_type(&self) -> &'static str {
+    let types = match self.ty {
+      VertexAttribType::Floating
+      | VertexAttribType::Integral(Normalized::Yes)
+      | VertexAttribType::Unsigned(Normalized::Yes) => ["float", "vec2", "vec3", "vec4"],
+      VertexAttribType::Integral(Normalized::No) => ["int", "ivec2", "ivec3", "ivec4"],
+      VertexAttribType::Unsigned(Normalized::No) | VertexAttribType::Boolean => {
+        ["uint", "uvec2", "uvec3", "uvec4"]
+      }
+    };
+
+    match self.dim {
+      VertexAttribDim::Dim1 => types[0],
+      VertexAttribDim::Dim2 => types[1],
+      VertexAttribDim::Dim3 => types[2],
+      VertexAttribDim::Dim4 => types[3],
+    }
+  }
 }
 
 /// Possible type of vertex attributes.
@@ -235,6 +258,23 @@ impl Semantics for () {
   }
 }
 
+/// Vertex attribute semantics that can declare their vertex shader inputs in GLSL.
+///
+/// > Note: this trait is automatically implemented when deriving [`Semantics`] with
+/// > [luminance-derive].
+///
+/// [luminance-derive]: https://crates.io/crates/luminance-derive
+pub trait SemanticsDeclarations: Semantics {
+  /// GLSL declarations of the vertex shader inputs, such as `in vec3 position;`, one per line.
+  fn input_declarations() -> String;
+}
+
+impl SemanticsDeclarations for () {
+  fn input_declarations() -> String {
+    String::new()
+  }
+}
+
 /// Semantics description.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct SemanticsDesc {
@@ -0,0 +1,45 @@
+use luminance::atlas::ShelfAllocator;
+
+#[test]
+fn shelf_allocator_packs_on_shelves() {
+  let mut allocator = ShelfAllocator::new([64, 64]);
+
+  assert_eq!(allocator.allocate([32, 16]), Some([0, 0]));
+  assert_eq!(allocator.allocate([32, 16]), Some([32, 0]));
+  assert_eq!(allocator.allocate([16, 8]), Some([0, 16]));
+  // the second shelf wastes less height than a new one
+  assert_eq!(allocator.allocate([16, 8]), Some([16, 16]));
+  assert_eq!(allocator.allocate([64, 40]), Some([0, 24]));
+  assert_eq!(allocator.allocate([1, 1]), Some([32, 16]));
+  assert_eq!(allocator.allocate([64, 1]), None);
+}
+
+#[test]
+fn shelf_allocator_rejects_too_large_rectangles() {
+  let mut allocator = ShelfAllocator::new([64, 64]);
+
+  assert_eq!(allocator.allocate([65, 1]), None);
+  assert_eq!(allocator.allocate([1, 65]), None);
+  assert_eq!(allocator.allocate([0, 1]), None);
+}
+
+#[test]
+fn shelf_allocator_reuses_freed_room() {
+  let mut allocator = ShelfAllocator::new([64, 64]);
+
+  let a = allocator.allocate([16, 16]).unwrap();
+  let b = allocator.allocate([16, 16]).unwrap();
+  let c = allocator.allocate([16, 16]).unwrap();
+
+  // freeing adjacent rectangles merges their room
+  allocator.deallocate(a, [16, 16]);
+  allocator.deallocate(b, [16, 16]);
+  assert_eq!(allocator.allocate([32, 8]), Some([0, 0]));
+
+  allocator.deallocate([0, 0], [32, 8]);
+  allocator.deallocate(c, [16, 16]);
+  assert!(allocator.is_empty());
+
+  // the whole area is available again, for any height
+  assert_eq!(allocator.allocate([64, 64]), Some([0, 0]));
+}
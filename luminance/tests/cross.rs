@@ -0,0 +1,56 @@
+#![cfg(feature = "naga")]
+
+use luminance::shader::cross::{glsl450_to_glsl, CrossCompileError};
+use luminance::shader::{GlslTarget, StageType};
+
+const VS: &str = "#version 450
+
+layout (location = 0) in vec2 co;
+layout (location = 1) in vec3 color;
+
+layout (location = 0) out vec3 v_color;
+
+layout (set = 0, binding = 0) uniform Transform {
+  mat4 projection;
+};
+
+void main() {
+  gl_Position = projection * vec4(co, 0., 1.);
+  v_color = color;
+}
+";
+
+const FS: &str = "#version 450
+
+layout (location = 0) in vec3 v_color;
+layout (location = 0) out vec4 frag;
+
+layout (set = 0, binding = 1) uniform texture2D tex;
+layout (set = 0, binding = 2) uniform sampler smp;
+
+void main() {
+  frag = vec4(v_color, 1.) * texture(sampler2D(tex, smp), vec2(0.5));
+}
+";
+
+#[test]
+fn cross_compile_glsl450() {
+  for &target in &[GlslTarget::Glsl330, GlslTarget::GlslEs300] {
+    let vs = glsl450_to_glsl(VS, StageType::VertexShader, target).unwrap();
+    let fs = glsl450_to_glsl(FS, StageType::FragmentShader, target).unwrap();
+    assert!(!vs.code().contains("#version"));
+    assert!(vs.code().contains("gl_Position"));
+    assert!(!fs.code().contains("#version"));
+    assert!(fs.code().contains(fs.name("tex").unwrap()));
+  }
+}
+
+#[test]
+fn cross_compile_unsupported_stage() {
+  assert_eq!(
+    glsl450_to_glsl(VS, StageType::GeometryShader, GlslTarget::Glsl330),
+    Err(CrossCompileError::UnsupportedStage(
+      StageType::GeometryShader
+    ))
+  );
+}
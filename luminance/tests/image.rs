@@ -0,0 +1,238 @@
+#![cfg(feature = "image")]
+
+use image::{ColorType, ImageBuffer, Luma, PixelWithColorType as _, Rgb, Rgba};
+use luminance::backend::texture::{Texture as TextureBackend, TextureBase};
+use luminance::context::GraphicsContext;
+use luminance::image::{ImageOptions, ImagePixel};
+use luminance::pixel::{NormR8UI, NormRGB8UI, NormRGBA16UI, RGBA32F, SRGBA8UI};
+use luminance::texture::{Dim2, GenMipmaps, Sampler, Texture, TextureError};
+
+// backend keeping the raw texels uploaded to textures in memory; only whole uploads are supported
+struct Memory;
+
+unsafe impl TextureBase for Memory {
+  type TextureRepr = Vec<u8>;
+}
+
+unsafe impl TextureBackend<Dim2, NormRGB8UI> for Memory {
+  unsafe fn new_texture(
+    &mut self,
+    _: [u32; 2],
+    _: usize,
+    _: Sampler,
+  ) -> Result<Vec<u8>, TextureError> {
+    Ok(Vec::new())
+  }
+
+  unsafe fn mipmaps(_: &Vec<u8>) -> usize {
+    0
+  }
+
+  unsafe fn clear_part(
+    _: &mut Vec<u8>,
+    _: GenMipmaps,
+    _: [u32; 2],
+    _: [u32; 2],
+    _: [u8; 3],
+  ) -> Result<(), TextureError> {
+    unimplemented!()
+  }
+
+  unsafe fn clear(
+    _: &mut Vec<u8>,
+    _: GenMipmaps,
+    _: [u32; 2],
+    _: [u8; 3],
+  ) -> Result<(), TextureError> {
+    unimplemented!()
+  }
+
+  unsafe fn upload_part(
+    _: &mut Vec<u8>,
+    _: GenMipmaps,
+    _: [u32; 2],
+    _: [u32; 2],
+    _: &[[u8; 3]],
+  ) -> Result<(), TextureError> {
+    unimplemented!()
+  }
+
+  unsafe fn upload(
+    _: &mut Vec<u8>,
+    _: GenMipmaps,
+    _: [u32; 2],
+    _: &[[u8; 3]],
+  ) -> Result<(), TextureError> {
+    unimplemented!()
+  }
+
+  unsafe fn upload_part_raw(
+    _: &mut Vec<u8>,
+    _: GenMipmaps,
+    _: [u32; 2],
+    _: [u32; 2],
+    _: &[u8],
+  ) -> Result<(), TextureError> {
+    unimplemented!()
+  }
+
+  unsafe fn upload_raw(
+    texture: &mut Vec<u8>,
+    _: GenMipmaps,
+    _: [u32; 2],
+    texels: &[u8],
+  ) -> Result<(), TextureError> {
+    *texture = texels.to_owned();
+    Ok(())
+  }
+
+  unsafe fn upload_part_level(
+    _: &mut Vec<u8>,
+    _: usize,
+    _: [u32; 2],
+    _: [u32; 2],
+    _: &[[u8; 3]],
+  ) -> Result<(), TextureError> {
+    unimplemented!()
+  }
+
+  unsafe fn upload_part_level_raw(
+    _: &mut Vec<u8>,
+    _: usize,
+    _: [u32; 2],
+    _: [u32; 2],
+    _: &[u8],
+  ) -> Result<(), TextureError> {
+    unimplemented!()
+  }
+
+  unsafe fn get_raw_texels(texture: &Vec<u8>, _: [u32; 2]) -> Result<Vec<u8>, TextureError> {
+    Ok(texture.clone())
+  }
+
+  unsafe fn get_raw_texels_part(
+    _: &Vec<u8>,
+    _: usize,
+    _: [u32; 2],
+    _: [u32; 2],
+  ) -> Result<Vec<u8>, TextureError> {
+    unimplemented!()
+  }
+
+  unsafe fn resize(_: &mut Vec<u8>, _: [u32; 2], _: usize) -> Result<(), TextureError> {
+    unimplemented!()
+  }
+}
+
+struct MemoryContext(Memory);
+
+unsafe impl GraphicsContext for MemoryContext {
+  type Backend = Memory;
+
+  fn backend(&mut self) -> &mut Memory {
+    &mut self.0
+  }
+}
+
+#[test]
+fn image_pixels_map_to_color_types() {
+  assert_eq!(NormR8UI::COLOR_TYPE, Luma::<u8>::COLOR_TYPE);
+  assert_eq!(NormRGB8UI::COLOR_TYPE, Rgb::<u8>::COLOR_TYPE);
+  assert_eq!(NormRGBA16UI::COLOR_TYPE, Rgba::<u16>::COLOR_TYPE);
+  assert_eq!(RGBA32F::COLOR_TYPE, Rgba::<f32>::COLOR_TYPE);
+}
+
+#[test]
+fn srgb_pixels_share_color_types_with_linear_ones() {
+  assert_eq!(SRGBA8UI::COLOR_TYPE, ColorType::Rgba8);
+}
+
+#[test]
+fn texels_to_image_flips_rows() {
+  use luminance::image::texels_to_image;
+
+  let texels = [255, 0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255];
+  let image = texels_to_image::<NormRGB8UI>([2, 2], &texels).unwrap();
+
+  assert_eq!(image.color(), ColorType::Rgb8);
+  assert_eq!(
+    image.as_bytes(),
+    &[0, 0, 255, 255, 255, 255, 255, 0, 0, 0, 255, 0]
+  );
+}
+
+#[test]
+fn texels_to_image_normalizes_integral_pixels() {
+  use luminance::image::texels_to_image;
+  use luminance::pixel::{R16I, R32UI, R8I, R8UI};
+
+  let image = texels_to_image::<R32UI>([3, 1], &[0, 1 << 31, u32::MAX]).unwrap();
+  assert_eq!(image.color(), ColorType::L16);
+  assert_eq!(image.to_luma16().into_raw(), vec![0, 32768, 65535]);
+
+  let image = texels_to_image::<R8I>([3, 1], &[i8::MIN, 0, i8::MAX]).unwrap();
+  assert_eq!(image.color(), ColorType::L8);
+  assert_eq!(image.to_luma8().into_raw(), vec![0, 128, 255]);
+
+  let image = texels_to_image::<R16I>([2, 1], &[i16::MIN, i16::MAX]).unwrap();
+  assert_eq!(image.to_luma16().into_raw(), vec![0, 65535]);
+
+  // constant images keep their value
+  let image = texels_to_image::<R8UI>([2, 1], &[51, 51]).unwrap();
+  assert_eq!(image.to_luma8().into_raw(), vec![51, 51]);
+}
+
+#[test]
+fn texels_to_image_expands_depth_to_grayscale() {
+  use luminance::image::texels_to_image;
+  use luminance::pixel::Depth32F;
+
+  let image = texels_to_image::<Depth32F>([2, 1], &[0.25, 1.]).unwrap();
+
+  assert_eq!(image.color(), ColorType::Rgb32F);
+  assert_eq!(
+    image.to_rgb32f().into_raw(),
+    vec![0.25, 0.25, 0.25, 1., 1., 1.]
+  );
+}
+
+#[test]
+fn texels_to_image_decodes_packed_pixels() {
+  use luminance::image::texels_to_image;
+  use luminance::pixel::RGB10A2;
+
+  let texel = 1023 | (3 << 30);
+  let image = texels_to_image::<RGB10A2>([1, 1], &[texel]).unwrap();
+
+  assert_eq!(image.color(), ColorType::Rgba16);
+  assert_eq!(image.to_rgba16().into_raw(), vec![65535, 0, 0, 65535]);
+}
+
+#[test]
+fn image_buffer_texels() {
+  // two rows of one texel, followed by subpixels past the end of the image
+  let image = ImageBuffer::<Rgb<u8>, _>::from_raw(1, 2, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]).unwrap();
+  let mut ctx = MemoryContext(Memory);
+
+  let texture = Texture::<_, Dim2, NormRGB8UI>::from_image_buffer(
+    &mut ctx,
+    &image,
+    ImageOptions {
+      flip_vertically: false,
+      ..ImageOptions::default()
+    },
+  )
+  .unwrap();
+  assert_eq!(texture.get_raw_texels().unwrap(), [1, 2, 3, 4, 5, 6]);
+
+  let texture = Texture::<_, Dim2, NormRGB8UI>::from_image_buffer(
+    &mut ctx,
+    &image,
+    ImageOptions {
+      flip_vertically: true,
+      ..ImageOptions::default()
+    },
+  )
+  .unwrap();
+  assert_eq!(texture.get_raw_texels().unwrap(), [4, 5, 6, 1, 2, 3]);
+}
@@ -0,0 +1,50 @@
+use luminance::shader::permutation::Defines;
+
+#[test]
+fn defines_after_version() {
+  let defines = Defines::new()
+    .define("SHADOW_CASCADES", 4)
+    .flag("NORMAL_MAP");
+  let src = "// main shader\n\n#version 330 core\n\nvoid main() {}\n";
+
+  assert_eq!(
+    defines.inject(src),
+    "// main shader\n\n#version 330 core\n#define NORMAL_MAP\n#define SHADOW_CASCADES 4\n#line 4\n\nvoid main() {}\n"
+  );
+}
+
+#[test]
+fn defines_without_version() {
+  let defines = Defines::new().flag("SKINNING");
+
+  assert_eq!(
+    defines.inject("void main() {}\n"),
+    "#define SKINNING\n#line 1\nvoid main() {}\n"
+  );
+  assert_eq!(
+    defines.inject("#version 300 es"),
+    "#version 300 es\n#define SKINNING\n#line 2\n"
+  );
+  assert_eq!(Defines::new().inject("void main() {}"), "void main() {}");
+}
+
+#[test]
+fn defines_order_independent() {
+  let a = Defines::new()
+    .flag("NORMAL_MAP")
+    .define("SHADOW_CASCADES", 2);
+  let mut b: Defines = vec![("SHADOW_CASCADES", 4)].into_iter().collect();
+  assert_ne!(a, b);
+
+  b.set("SHADOW_CASCADES", 2);
+  b.extend(vec![("NORMAL_MAP", "")]);
+  assert_eq!(a, b);
+  assert_eq!(b.get("SHADOW_CASCADES"), Some("2"));
+  assert_eq!(
+    b.iter().collect::<Vec<_>>(),
+    vec![("NORMAL_MAP", ""), ("SHADOW_CASCADES", "2")]
+  );
+
+  assert_eq!(b.remove("NORMAL_MAP"), Some(String::new()));
+  assert_eq!(b.len(), 1);
+}
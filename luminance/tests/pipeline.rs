@@ -0,0 +1,48 @@
+use luminance::pipeline::{check_shadow_sampling, PipelineError};
+use luminance::pixel::{Depth32F, Pixel, SamplerType, Shadow, RGBA32F};
+use luminance::texture::{Cubemap, Dim2, Dim2Array, Dim3};
+
+#[test]
+fn shadow_sampling_depth_textures() {
+  let depth = Depth32F::pixel_format();
+
+  assert_eq!(check_shadow_sampling::<Dim2>(depth, true), Ok(()));
+  assert_eq!(check_shadow_sampling::<Dim2Array>(depth, true), Ok(()));
+  assert_eq!(check_shadow_sampling::<Cubemap>(depth, true), Ok(()));
+}
+
+#[test]
+fn shadow_sampling_rejects_color_textures() {
+  let color = RGBA32F::pixel_format();
+
+  assert_eq!(
+    check_shadow_sampling::<Dim2>(color, true),
+    Err(PipelineError::incompatible_sampler_type(
+      color,
+      Shadow::sample_type()
+    ))
+  );
+}
+
+#[test]
+fn shadow_sampling_rejects_3d_textures() {
+  let depth = Depth32F::pixel_format();
+
+  assert_eq!(
+    check_shadow_sampling::<Dim3>(depth, true),
+    Err(PipelineError::incompatible_sampler_type(
+      depth,
+      Shadow::sample_type()
+    ))
+  );
+}
+
+#[test]
+fn shadow_sampling_requires_depth_comparison() {
+  let depth = Depth32F::pixel_format();
+
+  assert_eq!(
+    check_shadow_sampling::<Dim2>(depth, false),
+    Err(PipelineError::depth_comparison_disabled())
+  );
+}
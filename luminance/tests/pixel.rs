@@ -0,0 +1,72 @@
+use luminance::pixel::{
+  Depth16, Depth24, Depth32F, Floating, NormRGBA8UI, NormUnsigned, Pixel, SamplerType, Shadow,
+  Type, R11G11B10F, RG16UI, RGB10A2, RGB10A2UI, RGB9E5, RGBA32F, RGBA8UI, SRGB8UI,
+};
+
+#[test]
+fn packed_pixels_are_a_single_raw_value() {
+  assert_eq!(RGB10A2::pixel_format().raw_len(), 1);
+  assert_eq!(RGB10A2UI::pixel_format().raw_len(), 1);
+  assert_eq!(RGB9E5::pixel_format().raw_len(), 1);
+  assert_eq!(RGB9E5::pixel_format().channels_len(), 3);
+  assert_eq!(RGB9E5::pixel_format().format.bytes_len(), 4);
+}
+
+#[test]
+fn unpacked_pixels_have_a_raw_value_per_channel() {
+  assert_eq!(RGBA8UI::pixel_format().raw_len(), 4);
+  assert_eq!(R11G11B10F::pixel_format().raw_len(), 3);
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn half_pixels_are_two_bytes_per_channel() {
+  use luminance::pixel::RGBA16F;
+
+  assert_eq!(RGBA16F::pixel_format().format.bytes_len(), 8);
+  assert_eq!(RGBA16F::pixel_format().raw_len(), 4);
+}
+
+#[test]
+fn raw_bytes_len_matches_raw_encoding() {
+  use std::mem::size_of;
+
+  fn check<P>()
+  where
+    P: Pixel,
+  {
+    let pf = P::pixel_format();
+    assert_eq!(
+      pf.raw_bytes_len(),
+      pf.raw_len() * size_of::<P::RawEncoding>()
+    );
+  }
+
+  check::<NormRGBA8UI>();
+  check::<SRGB8UI>();
+  check::<RG16UI>();
+  check::<RGBA32F>();
+  check::<R11G11B10F>();
+  check::<RGB10A2>();
+  check::<RGB9E5>();
+  check::<Depth16>();
+  check::<Depth24>();
+  check::<Depth32F>();
+}
+
+#[test]
+fn small_depth_pixels() {
+  assert!(Depth16::pixel_format().is_depth_pixel());
+  assert_eq!(Depth16::pixel_format().format.bytes_len(), 2);
+  assert!(Depth24::pixel_format().is_depth_pixel());
+  assert_eq!(Depth24::pixel_format().format.bytes_len(), 3);
+  assert_eq!(Depth24::pixel_format().raw_bytes_len(), 4);
+}
+
+#[test]
+fn only_shadow_samplers_compare_depth() {
+  assert_eq!(Shadow::sample_type(), Type::Floating);
+  assert!(Shadow::depth_comparison());
+  assert!(!Floating::depth_comparison());
+  assert!(!NormUnsigned::depth_comparison());
+}
@@ -0,0 +1,212 @@
+use luminance::shader::preprocessor::{
+  preprocess, with_prelude, FileSystemResolver, PreprocessError, MAX_INCLUDE_DEPTH,
+};
+use std::collections::HashMap;
+use std::fs;
+
+#[test]
+fn includes_are_expanded_and_mapped() {
+  let mut includes = HashMap::new();
+  includes.insert("common.glsl", "const float PI = 3.14159265;");
+  includes.insert(
+    "brdf.glsl",
+    "#include \"common.glsl\"\nfloat brdf() {\n  return 1. / PI;\n}",
+  );
+
+  let src = "out vec4 frag;\n#include \"brdf.glsl\"\nvoid main() {}";
+  let preprocessed = preprocess("main.frag", src, &includes).unwrap();
+
+  assert_eq!(
+    preprocessed.code(),
+    "out vec4 frag;\nconst float PI = 3.14159265;\nfloat brdf() {\n  return 1. / PI;\n}\nvoid main() {}\n"
+  );
+
+  let line_map = preprocessed.line_map();
+  assert_eq!(line_map.origin(1), Some(("main.frag", 1)));
+  assert_eq!(line_map.origin(2), Some(("common.glsl", 1)));
+  assert_eq!(line_map.origin(4), Some(("brdf.glsl", 3)));
+  assert_eq!(line_map.origin(6), Some(("main.frag", 3)));
+  assert_eq!(line_map.origin(0), None);
+  assert_eq!(line_map.origin(7), None);
+}
+
+#[test]
+fn pragma_once_and_guards_include_sources_once() {
+  let includes = [
+    ("once.glsl", "#pragma once\nfloat once;"),
+    (
+      "guarded.glsl",
+      "#ifndef GUARDED\n#define GUARDED\nfloat guarded;\n#endif",
+    ),
+    (
+      "cycle.glsl",
+      "#pragma once\n#include \"cycle.glsl\"\nfloat cycle;",
+    ),
+  ];
+
+  let src = "#include \"once.glsl\"\n#include \"once.glsl\"\n#include \"guarded.glsl\"\n#include \"guarded.glsl\"\n#include \"cycle.glsl\"";
+  let preprocessed = preprocess("main.frag", src, &includes).unwrap();
+
+  assert_eq!(
+    preprocessed.code(),
+    "float once;\n#ifndef GUARDED\n#define GUARDED\nfloat guarded;\n#endif\nfloat cycle;\n"
+  );
+}
+
+#[test]
+fn include_errors() {
+  let includes = [("cycle.glsl", "#include \"cycle.glsl\"")];
+
+  assert_eq!(
+    preprocess("main.frag", "\n#include \"missing.glsl\"", &includes),
+    Err(PreprocessError::include_not_found(
+      "missing.glsl",
+      "main.frag",
+      2
+    ))
+  );
+  assert_eq!(
+    preprocess("main.frag", "#include \"cycle.glsl\"", &includes),
+    Err(PreprocessError::include_cycle(
+      "cycle.glsl",
+      "cycle.glsl",
+      1
+    ))
+  );
+  assert_eq!(
+    preprocess("main.frag", "#include <cycle.glsl>", &includes),
+    Err(PreprocessError::malformed_include("main.frag", 1))
+  );
+}
+
+#[test]
+fn includes_in_comments_are_ignored() {
+  let includes: [(&str, &str); 0] = [];
+  let src = "/*\n#include \"missing.glsl\"\n*/\n// #include \"missing.glsl\"";
+
+  assert!(preprocess("main.frag", src, &includes).is_ok());
+}
+
+#[test]
+fn compilation_logs_are_remapped() {
+  let includes = [("lighting.glsl", "float lambert() {\n  return oops;\n}")];
+  let src = "out vec4 frag;\n#include \"lighting.glsl\"\nvoid main() {}";
+  let line_map = preprocess("main.frag", src, &includes)
+    .unwrap()
+    .line_map()
+    .clone();
+
+  assert_eq!(
+    line_map.remap_log("0:3(10): error: `oops' undeclared\n0:5(1): error: syntax error"),
+    "lighting.glsl:2(10): error: `oops' undeclared\nmain.frag:3(1): error: syntax error"
+  );
+  assert_eq!(
+    line_map.remap_log("0(3) : error C1008: undefined variable \"oops\""),
+    "lighting.glsl(2) : error C1008: undefined variable \"oops\""
+  );
+  assert_eq!(
+    line_map.remap_log("ERROR: 0:3: 'oops' : undeclared identifier"),
+    "ERROR: lighting.glsl:2: 'oops' : undeclared identifier"
+  );
+  assert_eq!(
+    line_map.remap_log("ERROR: 0:42: out of range"),
+    "ERROR: 0:42: out of range"
+  );
+}
+
+// line a driver reports for the first line containing `needle`, honoring #line directives
+fn driver_line(code: &str, needle: &str) -> usize {
+  let mut next = 1;
+
+  for line in code.lines() {
+    if let Some(reset) = line.strip_prefix("#line ") {
+      next = reset.trim().parse().unwrap();
+      continue;
+    }
+
+    if line.contains(needle) {
+      return next;
+    }
+
+    next += 1;
+  }
+
+  panic!("{} not found", needle);
+}
+
+#[test]
+fn compilation_logs_are_remapped_through_prelude() {
+  let includes = [("lighting.glsl", "float lambert() {\n  return oops;\n}")];
+  let src = "out vec4 frag;\n#include \"lighting.glsl\"\nvoid main() {}";
+  let preprocessed = preprocess("main.frag", src, &includes).unwrap();
+
+  let preludes = [
+    "#version 330 core\n#extension GL_ARB_separate_shader_objects : require\n",
+    "#version 330 core\n#extension GL_ARB_separate_shader_objects : require\n#extension GL_ARB_gpu_shader_fp64 : require\n#extension GL_ARB_bindless_texture : require\n",
+    "#version 300 es\nprecision highp float;\nprecision highp int;\n",
+  ];
+
+  for prelude in &preludes {
+    let code = with_prelude(prelude, preprocessed.code());
+    let log = format!(
+      "0:{}(10): error: `oops' undeclared",
+      driver_line(&code, "oops")
+    );
+
+    assert_eq!(
+      preprocessed.line_map().remap_log(&log),
+      "lighting.glsl:2(10): error: `oops' undeclared"
+    );
+  }
+}
+
+#[test]
+fn includes_too_deep() {
+  let includes: Vec<_> = (0..=MAX_INCLUDE_DEPTH)
+    .map(|i| {
+      (
+        format!("{}.glsl", i),
+        format!("#include \"{}.glsl\"", i + 1),
+      )
+    })
+    .collect();
+  let includes: HashMap<_, _> = includes
+    .iter()
+    .map(|(path, code)| (path.as_str(), code.as_str()))
+    .collect();
+
+  // 0.glsl is the first nested include
+  let last = format!("{}.glsl", MAX_INCLUDE_DEPTH - 1);
+  let too_deep = format!("{}.glsl", MAX_INCLUDE_DEPTH);
+  assert_eq!(
+    preprocess("main.frag", "#include \"0.glsl\"", &includes),
+    Err(PreprocessError::include_too_deep(too_deep, last, 1))
+  );
+}
+
+#[test]
+fn file_system_cycles_through_parent_directories() {
+  let root = std::env::temp_dir().join(format!("luminance-preprocessor-{}", std::process::id()));
+  let dir = root.join("lighting");
+  fs::create_dir_all(&dir).unwrap();
+  fs::write(
+    dir.join("brdf.glsl"),
+    "#include \"../lighting/brdf.glsl\"\nfloat brdf;",
+  )
+  .unwrap();
+
+  let resolver = FileSystemResolver::new(&root);
+  let includer = root.join("main.frag");
+  let includer = includer.to_str().unwrap();
+
+  match preprocess(includer, "#include \"lighting/brdf.glsl\"", &resolver) {
+    Err(PreprocessError::IncludeCycle { path, line, .. }) => {
+      assert_eq!(path, "../lighting/brdf.glsl");
+      assert_eq!(line, 1);
+    }
+
+    r => panic!("expected an include cycle, got {:?}", r),
+  }
+
+  fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,60 @@
+use luminance::shader::cache::{DirectoryCache, ProgramBinary, ProgramCache};
+use std::collections::HashMap;
+use std::fs;
+
+fn binary() -> ProgramBinary {
+  ProgramBinary {
+    driver: "Vendor; Renderer; 4.6; 4.60".to_owned(),
+    source_hash: 0xDEAD_BEEF_CAFE_BABE,
+    format: 0x8741,
+    data: vec![1, 2, 3, 4, 5, 6, 7],
+  }
+}
+
+#[test]
+fn program_binary_bytes_round_trip() {
+  let binary = binary();
+  let bytes = binary.to_bytes();
+
+  assert_eq!(ProgramBinary::from_bytes(&bytes), Some(binary));
+
+  // truncated and corrupted encodings
+  assert_eq!(ProgramBinary::from_bytes(&bytes[..10]), None);
+  assert_eq!(ProgramBinary::from_bytes(&bytes[1..]), None);
+  assert_eq!(ProgramBinary::from_bytes(&[]), None);
+}
+
+#[test]
+fn memory_cache() {
+  let mut cache = HashMap::new();
+  assert_eq!(cache.load("main"), None);
+
+  cache.store("main", &binary());
+  assert_eq!(cache.load("main"), Some(binary()));
+  assert_eq!(cache.load("other"), None);
+}
+
+#[test]
+fn directory_cache() {
+  let dir = std::env::temp_dir().join(format!("luminance-program-cache-{}", std::process::id()));
+  let _ = fs::remove_dir_all(&dir);
+  let mut cache = DirectoryCache::new(&dir);
+
+  assert_eq!(cache.load("deferred/gbuffer"), None);
+
+  cache.store("deferred/gbuffer", &binary());
+  assert!(cache.path("deferred/gbuffer").is_file());
+  assert_eq!(cache.load("deferred/gbuffer"), Some(binary()));
+
+  // a corrupted file is a cache miss
+  fs::write(cache.path("deferred/gbuffer"), b"garbage").unwrap();
+  assert_eq!(cache.load("deferred/gbuffer"), None);
+
+  // keys cannot refer to files outside of the directory
+  for key in ["deferred/gbuffer", "../escape", "..", "/abs", "a\\b", "C:x"] {
+    assert_eq!(cache.path(key).parent(), Some(dir.as_path()), "{}", key);
+  }
+  assert_ne!(cache.path("a/b"), cache.path("a%2Fb"));
+
+  fs::remove_dir_all(&dir).unwrap();
+}
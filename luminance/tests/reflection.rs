@@ -0,0 +1,157 @@
+use luminance::backend::shader::{Shader, ShaderReflection, Uniformable};
+use luminance::context::GraphicsContext;
+use luminance::shader::reflection::{
+  ActiveAttribute, ActiveUniform, ActiveUniformBlock, UniformBlockMember,
+};
+use luminance::shader::{
+  ProgramBuilder, ProgramError, StageError, StageType, TessellationStages, Uniform, UniformType,
+  UniformWarning, VertexAttribWarning,
+};
+use luminance::vertex::Semantics;
+
+// backend whose programs all report the same active items
+struct Reflected;
+
+fn uniforms() -> Vec<ActiveUniform> {
+  vec![
+    ActiveUniform {
+      name: "time".to_owned(),
+      ty: Some(UniformType::Float),
+      array_size: 1,
+      location: 0,
+    },
+    ActiveUniform {
+      name: "lights[0]".to_owned(),
+      ty: Some(UniformType::Vec4),
+      array_size: 8,
+      location: 1,
+    },
+    ActiveUniform {
+      name: "atomic".to_owned(),
+      ty: None,
+      array_size: 1,
+      location: 9,
+    },
+  ]
+}
+
+fn uniform_blocks() -> Vec<ActiveUniformBlock> {
+  vec![ActiveUniformBlock {
+    name: "Camera".to_owned(),
+    index: 0,
+    size: 80,
+    members: vec![
+      UniformBlockMember {
+        name: "view".to_owned(),
+        ty: Some(UniformType::M44),
+        array_size: 1,
+        offset: 0,
+      },
+      UniformBlockMember {
+        name: "eye".to_owned(),
+        ty: Some(UniformType::Vec4),
+        array_size: 1,
+        offset: 64,
+      },
+    ],
+  }]
+}
+
+fn attributes() -> Vec<ActiveAttribute> {
+  vec![
+    ActiveAttribute {
+      name: "co".to_owned(),
+      ty: Some(UniformType::Vec2),
+      location: 0,
+    },
+    ActiveAttribute {
+      name: "gl_VertexID".to_owned(),
+      ty: Some(UniformType::Int),
+      location: -1,
+    },
+  ]
+}
+
+unsafe impl Shader for Reflected {
+  type StageRepr = ();
+
+  type ProgramRepr = ();
+
+  type UniformBuilderRepr = ();
+
+  unsafe fn new_stage(&mut self, _: StageType, _: &str) -> Result<(), StageError> {
+    Ok(())
+  }
+
+  unsafe fn new_program(
+    &mut self,
+    _: &(),
+    _: Option<TessellationStages<()>>,
+    _: Option<&()>,
+    _: &(),
+  ) -> Result<(), ProgramError> {
+    Ok(())
+  }
+
+  unsafe fn apply_semantics<Sem>(_: &mut ()) -> Result<Vec<VertexAttribWarning>, ProgramError>
+  where
+    Sem: Semantics,
+  {
+    Ok(Vec::new())
+  }
+
+  unsafe fn new_uniform_builder(_: &mut ()) -> Result<(), ProgramError> {
+    Ok(())
+  }
+
+  unsafe fn ask_uniform<T>(_: &mut (), _: &str) -> Result<Uniform<T>, UniformWarning>
+  where
+    T: Uniformable<Self>,
+  {
+    Ok(Uniform::new(0))
+  }
+
+  unsafe fn unbound<T>(_: &mut ()) -> Uniform<T>
+  where
+    T: Uniformable<Self>,
+  {
+    Uniform::new(-1)
+  }
+}
+
+unsafe impl ShaderReflection for Reflected {
+  unsafe fn active_uniforms(_: &()) -> Vec<ActiveUniform> {
+    uniforms()
+  }
+
+  unsafe fn active_uniform_blocks(_: &()) -> Vec<ActiveUniformBlock> {
+    uniform_blocks()
+  }
+
+  unsafe fn active_attributes(_: &()) -> Vec<ActiveAttribute> {
+    attributes()
+  }
+}
+
+struct ReflectedContext(Reflected);
+
+unsafe impl GraphicsContext for ReflectedContext {
+  type Backend = Reflected;
+
+  fn backend(&mut self) -> &mut Reflected {
+    &mut self.0
+  }
+}
+
+#[test]
+fn program_reflection() {
+  let mut ctx = ReflectedContext(Reflected);
+  let program = ProgramBuilder::<_, (), (), ()>::new(&mut ctx)
+    .from_strings("", None, None, "")
+    .unwrap()
+    .ignore_warnings();
+
+  assert_eq!(program.active_uniforms(), uniforms());
+  assert_eq!(program.active_uniform_blocks(), uniform_blocks());
+  assert_eq!(program.active_attributes(), attributes());
+}
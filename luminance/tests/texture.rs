@@ -0,0 +1,111 @@
+use luminance::backend::texture::BufferTexture as BufferTextureBackend;
+use luminance::context::GraphicsContext;
+use luminance::pixel::{Pixel, RGB32F};
+use luminance::texture::{
+  CubeFace, Cubemap, CubemapArray, Dim1, Dim2, Dim2Array, Dim3, Dimensionable, TextureError,
+};
+
+// backend keeping buffer textures in memory, as raw values
+struct Memory;
+
+unsafe impl BufferTextureBackend<RGB32F> for Memory {
+  type BufferTextureRepr = Vec<f32>;
+
+  unsafe fn new_buffer_texture(&mut self, texels: &[[f32; 3]]) -> Result<Vec<f32>, TextureError> {
+    Ok(texels.concat())
+  }
+
+  unsafe fn new_buffer_texture_raw(&mut self, texels: &[f32]) -> Result<Vec<f32>, TextureError> {
+    Ok(texels.to_owned())
+  }
+
+  unsafe fn upload_buffer_texture_part(
+    texture: &mut Vec<f32>,
+    offset: usize,
+    texels: &[[f32; 3]],
+  ) -> Result<(), TextureError> {
+    Self::upload_buffer_texture_part_raw(texture, offset, &texels.concat())
+  }
+
+  unsafe fn upload_buffer_texture_part_raw(
+    texture: &mut Vec<f32>,
+    offset: usize,
+    texels: &[f32],
+  ) -> Result<(), TextureError> {
+    let start = offset * RGB32F::pixel_format().raw_len();
+    texture[start..start + texels.len()].copy_from_slice(texels);
+    Ok(())
+  }
+}
+
+struct MemoryContext(Memory);
+
+unsafe impl GraphicsContext for MemoryContext {
+  type Backend = Memory;
+
+  fn backend(&mut self) -> &mut Memory {
+    &mut self.0
+  }
+}
+
+#[test]
+fn mipmap_size_halves_extents() {
+  assert_eq!(Dim1::mipmap_size(256, 0), 256);
+  assert_eq!(Dim1::mipmap_size(256, 3), 32);
+  assert_eq!(Dim2::mipmap_size([64, 16], 2), [16, 4]);
+  assert_eq!(Dim3::mipmap_size([8, 8, 4], 1), [4, 4, 2]);
+  assert_eq!(Cubemap::mipmap_size(128, 4), 8);
+}
+
+#[test]
+fn mipmap_size_never_below_one() {
+  assert_eq!(Dim2::mipmap_size([64, 16], 5), [2, 1]);
+  assert_eq!(Dim2::mipmap_size([64, 16], 100), [1, 1]);
+  assert_eq!(Dim1::mipmap_size(1, 1), 1);
+}
+
+#[test]
+fn mipmap_size_keeps_array_layers() {
+  assert_eq!(Dim2Array::mipmap_size(([32, 32], 6), 3), ([4, 4], 6));
+  assert_eq!(CubemapArray::mipmap_size((32, 3), 2), (8, 3));
+}
+
+#[test]
+fn cubemap_array_layer_faces() {
+  assert_eq!(CubemapArray::depth((16, 3)), 18);
+  assert_eq!(CubemapArray::count((16, 3)), 16 * 16 * 18);
+  assert_eq!(
+    CubemapArray::z_offset(([0, 0], 2, CubeFace::NegativeY)),
+    2 * 6 + 3
+  );
+}
+
+#[test]
+fn buffer_texture_raw_texels() {
+  let mut ctx = MemoryContext(Memory);
+  let mut texture = ctx.new_buffer_texture_raw::<RGB32F>(&[0.; 6]).unwrap();
+  assert_eq!(texture.len(), 2);
+
+  assert!(texture.upload_part_raw(1, &[1., 2., 3.]).is_ok());
+  assert!(texture
+    .upload_part_raw(1, &[1., 2., 3., 4., 5., 6.])
+    .is_err());
+}
+
+#[test]
+fn buffer_texture_partial_raw_texels() {
+  let mut ctx = MemoryContext(Memory);
+
+  assert!(matches!(
+    ctx.new_buffer_texture_raw::<RGB32F>(&[0.; 7]),
+    Err(TextureError::CannotUploadTexels(_))
+  ));
+
+  let mut texture = ctx.new_buffer_texture_raw::<RGB32F>(&[0.; 6]).unwrap();
+
+  // one texel and a half would fit in the buffer texture if it was truncated to one texel
+  assert!(matches!(
+    texture.upload_part_raw(0, &[1., 2., 3., 4.]),
+    Err(TextureError::CannotUploadTexels(_))
+  ));
+}
@@ -0,0 +1,43 @@
+use luminance::shader::{UniformCache, UniformCacheStats};
+
+#[test]
+fn uniform_cache_hits_and_misses() {
+  let mut cache = UniformCache::new();
+
+  unsafe {
+    assert!(!cache.is_cached(0, &[1., 2.]));
+    assert!(cache.is_cached(0, &[1., 2.]));
+    assert!(!cache.is_cached(1, &[1., 2.]));
+    assert!(!cache.is_cached(0, &[1., 3.]));
+    assert!(cache.is_cached(0, &[1., 3.]));
+    assert!(!cache.is_cached(0, &[1.]));
+  }
+
+  assert_eq!(cache.stats(), UniformCacheStats { hits: 2, misses: 4 });
+
+  // the values are kept
+  cache.reset_stats();
+  assert_eq!(cache.stats(), UniformCacheStats::default());
+  assert!(unsafe { cache.is_cached(1, &[1., 2.]) });
+  assert_eq!(cache.stats(), UniformCacheStats { hits: 1, misses: 0 });
+}
+
+#[test]
+fn uniform_cache_invalidate() {
+  let mut cache = UniformCache::new();
+
+  unsafe {
+    assert!(!cache.is_cached(0, &[1.]));
+    assert!(!cache.is_cached(1, &[2.]));
+    assert!(!cache.is_cached(2, &[3.]));
+
+    cache.invalidate(|&key| key >= 1);
+
+    assert!(cache.is_cached(0, &[1.]));
+    assert!(!cache.is_cached(1, &[2.]));
+    assert!(!cache.is_cached(2, &[3.]));
+  }
+
+  // invalidating doesn’t count as a hit or a miss
+  assert_eq!(cache.stats(), UniformCacheStats { hits: 1, misses: 5 });
+}
@@ -1,7 +1,177 @@
 #![cfg(feature = "derive")]
 
-use luminance::shader::Uniform;
+use luminance::backend::shader::{Shader, Uniformable};
+use luminance::context::GraphicsContext;
+use luminance::pipeline::{BufferBinding, TextureBinding};
+use luminance::pixel::Shadow;
+use luminance::shader::{
+  ProgramBuilder, ProgramError, StageError, StageType, TessellationStages, Uniform,
+  UniformDeclarations, UniformType, UniformWarning, VertexAttribWarning,
+};
+use luminance::texture::{Dim2, Dim2Array};
+use luminance::vertex::Semantics;
 use luminance::UniformInterface;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// backend only able to tell the type of a few uniforms; every uniform is found
+struct TypeOnly;
+
+unsafe impl Shader for TypeOnly {
+  type StageRepr = ();
+
+  type ProgramRepr = ();
+
+  type UniformBuilderRepr = ();
+
+  unsafe fn new_stage(&mut self, _: StageType, _: &str) -> Result<(), StageError> {
+    Ok(())
+  }
+
+  unsafe fn new_program(
+    &mut self,
+    _: &(),
+    _: Option<TessellationStages<()>>,
+    _: Option<&()>,
+    _: &(),
+  ) -> Result<(), ProgramError> {
+    Ok(())
+  }
+
+  unsafe fn apply_semantics<Sem>(_: &mut ()) -> Result<Vec<VertexAttribWarning>, ProgramError>
+  where
+    Sem: Semantics,
+  {
+    Ok(Vec::new())
+  }
+
+  unsafe fn new_uniform_builder(_: &mut ()) -> Result<(), ProgramError> {
+    Ok(())
+  }
+
+  unsafe fn ask_uniform<T>(_: &mut (), _: &str) -> Result<Uniform<T>, UniformWarning>
+  where
+    T: Uniformable<Self>,
+  {
+    Ok(Uniform::new(0))
+  }
+
+  unsafe fn unbound<T>(_: &mut ()) -> Uniform<T>
+  where
+    T: Uniformable<Self>,
+  {
+    Uniform::new(-1)
+  }
+}
+
+macro_rules! impl_type_only_uniformable {
+  ($t:ty, $ty:ident) => {
+    unsafe impl Uniformable<TypeOnly> for $t {
+      unsafe fn ty() -> UniformType {
+        UniformType::$ty
+      }
+
+      unsafe fn update(self, _: &mut (), _: &Uniform<Self>) {}
+    }
+  };
+}
+
+impl_type_only_uniformable!(f32, Float);
+impl_type_only_uniformable!([f32; 4], Vec4);
+impl_type_only_uniformable!(&'static [[f32; 4]], Vec4);
+impl_type_only_uniformable!([[f32; 4]; 4], M44);
+impl_type_only_uniformable!(BufferBinding<f32>, BufferBinding);
+impl_type_only_uniformable!(TextureBinding<Dim2, Shadow>, Sampler2DShadow);
+impl_type_only_uniformable!(TextureBinding<Dim2Array, Shadow>, Sampler2DArrayShadow);
+
+// backend recording the names of the uniforms asked while building uniform interfaces
+#[derive(Default)]
+struct NameRecorder {
+  names: Rc<RefCell<Vec<String>>>,
+}
+
+unsafe impl Shader for NameRecorder {
+  type StageRepr = ();
+
+  type ProgramRepr = Rc<RefCell<Vec<String>>>;
+
+  type UniformBuilderRepr = Rc<RefCell<Vec<String>>>;
+
+  unsafe fn new_stage(&mut self, _: StageType, _: &str) -> Result<(), StageError> {
+    Ok(())
+  }
+
+  unsafe fn new_program(
+    &mut self,
+    _: &(),
+    _: Option<TessellationStages<()>>,
+    _: Option<&()>,
+    _: &(),
+  ) -> Result<Self::ProgramRepr, ProgramError> {
+    Ok(self.names.clone())
+  }
+
+  unsafe fn apply_semantics<Sem>(
+    _: &mut Self::ProgramRepr,
+  ) -> Result<Vec<VertexAttribWarning>, ProgramError>
+  where
+    Sem: Semantics,
+  {
+    Ok(Vec::new())
+  }
+
+  unsafe fn new_uniform_builder(
+    program: &mut Self::ProgramRepr,
+  ) -> Result<Self::UniformBuilderRepr, ProgramError> {
+    Ok(program.clone())
+  }
+
+  unsafe fn ask_uniform<T>(
+    names: &mut Self::UniformBuilderRepr,
+    name: &str,
+  ) -> Result<Uniform<T>, UniformWarning>
+  where
+    T: Uniformable<Self>,
+  {
+    let mut names = names.borrow_mut();
+    names.push(name.to_owned());
+    Ok(Uniform::new(names.len() as i32 - 1))
+  }
+
+  unsafe fn unbound<T>(_: &mut Self::UniformBuilderRepr) -> Uniform<T>
+  where
+    T: Uniformable<Self>,
+  {
+    Uniform::new(-1)
+  }
+}
+
+unsafe impl Uniformable<NameRecorder> for f32 {
+  unsafe fn ty() -> UniformType {
+    UniformType::Float
+  }
+
+  unsafe fn update(self, _: &mut Rc<RefCell<Vec<String>>>, _: &Uniform<Self>) {}
+}
+
+unsafe impl Uniformable<NameRecorder> for [f32; 4] {
+  unsafe fn ty() -> UniformType {
+    UniformType::Vec4
+  }
+
+  unsafe fn update(self, _: &mut Rc<RefCell<Vec<String>>>, _: &Uniform<Self>) {}
+}
+
+#[derive(Default)]
+struct NameRecorderContext(NameRecorder);
+
+unsafe impl GraphicsContext for NameRecorderContext {
+  type Backend = NameRecorder;
+
+  fn backend(&mut self) -> &mut NameRecorder {
+    &mut self.0
+  }
+}
 
 #[test]
 fn derive_uniform_interface() {
@@ -39,3 +209,109 @@ fn derive_unbound_renamed_uniform_interface() {
     _t2: Uniform<f32>,
   }
 }
+
+#[test]
+fn derive_uniform_declarations() {
+  #[derive(UniformInterface)]
+  struct SimpleUniformInterface {
+    #[uniform(name = "time", unbound)]
+    _t: Uniform<f32>,
+    _resolution: Uniform<[f32; 4]>,
+    #[uniform(name = "view")]
+    _view: Uniform<[[f32; 4]; 4]>,
+    #[uniform(name = "lights")]
+    _lights: Uniform<BufferBinding<f32>>,
+    #[uniform(name = "colors", len = 8)]
+    _colors: Uniform<&'static [[f32; 4]]>,
+  }
+
+  assert_eq!(
+    <SimpleUniformInterface as UniformDeclarations<TypeOnly>>::uniform_declarations(),
+    "uniform float time;\nuniform vec4 _resolution;\nuniform mat4 view;\nuniform vec4 colors[8];\n"
+  );
+  assert_eq!(
+    <() as UniformDeclarations<TypeOnly>>::uniform_declarations(),
+    ""
+  );
+}
+
+#[test]
+fn derive_unsized_slice_uniform_interface() {
+  #[derive(UniformInterface)]
+  struct SliceUniformInterface {
+    #[uniform(name = "time")]
+    _t: Uniform<f32>,
+    #[uniform(name = "colors")]
+    _colors: Uniform<&'static [[f32; 4]]>,
+  }
+
+  // slices without a length are left to the sources to declare
+  assert_eq!(
+    <SliceUniformInterface as UniformDeclarations<TypeOnly>>::uniform_declarations(),
+    "uniform float time;\n"
+  );
+}
+
+#[test]
+fn derive_flattened_uniform_interface() {
+  #[derive(UniformInterface)]
+  struct Light {
+    _color: Uniform<[f32; 4]>,
+    #[uniform(unbound)]
+    _intensity: Uniform<f32>,
+  }
+
+  #[derive(UniformInterface)]
+  struct Scene {
+    #[uniform(flatten)]
+    _sun: Light,
+    #[uniform(name = "lights", flatten)]
+    _lights: [Light; 8],
+  }
+
+  #[derive(UniformInterface)]
+  struct SimpleUniformInterface {
+    _t: Uniform<f32>,
+    #[uniform(flatten)]
+    _scene: Scene,
+  }
+
+  assert_eq!(
+    <SimpleUniformInterface as UniformDeclarations<TypeOnly>>::uniform_declarations(),
+    "uniform float _t;\n"
+  );
+
+  let mut ctx = NameRecorderContext::default();
+  ProgramBuilder::<_, (), (), SimpleUniformInterface>::new(&mut ctx)
+    .from_strings("", None, None, "")
+    .unwrap();
+
+  let mut expected = vec![
+    "_t".to_owned(),
+    "_scene._sun._color".to_owned(),
+    "_scene._sun._intensity".to_owned(),
+  ];
+
+  for i in 0..8 {
+    expected.push(format!("_scene.lights[{}]._color", i));
+    expected.push(format!("_scene.lights[{}]._intensity", i));
+  }
+
+  assert_eq!(*ctx.0.names.borrow(), expected);
+}
+
+#[test]
+fn derive_shadow_sampler_declarations() {
+  #[derive(UniformInterface)]
+  struct ShadowUniformInterface {
+    #[uniform(name = "shadow_map")]
+    _shadow_map: Uniform<TextureBinding<Dim2, Shadow>>,
+    #[uniform(name = "cascades")]
+    _cascades: Uniform<TextureBinding<Dim2Array, Shadow>>,
+  }
+
+  assert_eq!(
+    <ShadowUniformInterface as UniformDeclarations<TypeOnly>>::uniform_declarations(),
+    "uniform sampler2DShadow shadow_map;\nuniform sampler2DArrayShadow cascades;\n"
+  );
+}
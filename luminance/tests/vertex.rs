@@ -1,7 +1,8 @@
 #![cfg(feature = "derive")]
 
 use luminance::vertex::{
-  HasSemantics, Semantics, Vertex, VertexAttrib, VertexBufferDesc, VertexInstancing,
+  HasSemantics, Normalized, Semantics, SemanticsDeclarations, Vertex, VertexAttrib,
+  VertexAttribDesc, VertexAttribDim, VertexAttribType, VertexBufferDesc, VertexInstancing,
 };
 use luminance::{Semantics, Vertex};
 
@@ -80,3 +81,57 @@ fn derive_struct_tuple_vertex() {
     #[vertex(normalized = "true")] VertexColor,
   );
 }
+
+#[test]
+fn derive_semantics_declarations() {
+  #[derive(Clone, Copy, Debug, Eq, PartialEq, Semantics)]
+  pub enum Semantics {
+    #[sem(name = "position", repr = "[f32; 3]", wrapper = "VertexPosition")]
+    Position,
+    #[sem(name = "uv", repr = "[f32; 2]", wrapper = "VertexUV")]
+    UV,
+    #[sem(name = "bone_ids", repr = "[u32; 4]", wrapper = "VertexBoneIDs")]
+    BoneIDs,
+  }
+
+  assert_eq!(
+    Semantics::input_declarations(),
+    "in vec3 position;\nin vec2 uv;\nin uvec4 bone_ids;\n"
+  );
+  assert_eq!(<() as SemanticsDeclarations>::input_declarations(), "");
+}
+
+#[test]
+fn vertex_attrib_glsl_type() {
+  let desc = |ty, dim| VertexAttribDesc {
+    ty,
+    dim,
+    unit_size: 4,
+    align: 4,
+  };
+
+  assert_eq!(
+    desc(VertexAttribType::Floating, VertexAttribDim::Dim1).glsl_type(),
+    "float"
+  );
+  assert_eq!(
+    desc(
+      VertexAttribType::Integral(Normalized::No),
+      VertexAttribDim::Dim3
+    )
+    .glsl_type(),
+    "ivec3"
+  );
+  assert_eq!(
+    desc(
+      VertexAttribType::Unsigned(Normalized::Yes),
+      VertexAttribDim::Dim4
+    )
+    .glsl_type(),
+    "vec4"
+  );
+  assert_eq!(
+    desc(VertexAttribType::Boolean, VertexAttribDim::Dim2).glsl_type(),
+    "uvec2"
+  );
+}
@@ -0,0 +1,172 @@
+use luminance::backend::shader::{Shader, Uniformable};
+use luminance::context::GraphicsContext;
+use luminance::shader::preprocessor::PreprocessError;
+use luminance::shader::watcher::{FileWatcher, ProgramSources, ProgramWatchError, ProgramWatcher};
+use luminance::shader::{
+  ProgramError, StageError, StageType, TessellationStages, Uniform, UniformWarning,
+  VertexAttribWarning,
+};
+use luminance::vertex::Semantics;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// backend accepting any source
+struct Lenient;
+
+unsafe impl Shader for Lenient {
+  type StageRepr = ();
+
+  type ProgramRepr = ();
+
+  type UniformBuilderRepr = ();
+
+  unsafe fn new_stage(&mut self, _: StageType, _: &str) -> Result<(), StageError> {
+    Ok(())
+  }
+
+  unsafe fn new_program(
+    &mut self,
+    _: &(),
+    _: Option<TessellationStages<()>>,
+    _: Option<&()>,
+    _: &(),
+  ) -> Result<(), ProgramError> {
+    Ok(())
+  }
+
+  unsafe fn apply_semantics<Sem>(_: &mut ()) -> Result<Vec<VertexAttribWarning>, ProgramError>
+  where
+    Sem: Semantics,
+  {
+    Ok(Vec::new())
+  }
+
+  unsafe fn new_uniform_builder(_: &mut ()) -> Result<(), ProgramError> {
+    Ok(())
+  }
+
+  unsafe fn ask_uniform<T>(_: &mut (), _: &str) -> Result<Uniform<T>, UniformWarning>
+  where
+    T: Uniformable<Self>,
+  {
+    Ok(Uniform::new(0))
+  }
+
+  unsafe fn unbound<T>(_: &mut ()) -> Uniform<T>
+  where
+    T: Uniformable<Self>,
+  {
+    Uniform::new(-1)
+  }
+}
+
+struct LenientContext(Lenient);
+
+unsafe impl GraphicsContext for LenientContext {
+  type Backend = Lenient;
+
+  fn backend(&mut self) -> &mut Lenient {
+    &mut self.0
+  }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join(format!("luminance-{}-{}", name, std::process::id()));
+  fs::create_dir_all(&dir).unwrap();
+  dir
+}
+
+// rewrite a file until its modification time changes, as file systems might have a coarse
+// resolution; return whether the watcher noticed it
+fn modify(watcher: &mut FileWatcher, path: &PathBuf) -> bool {
+  let start = Instant::now();
+
+  while start.elapsed() < Duration::from_secs(5) {
+    fs::write(path, "void main() { }").unwrap();
+
+    if watcher.changed() {
+      return true;
+    }
+
+    thread::sleep(Duration::from_millis(50));
+  }
+
+  false
+}
+
+#[test]
+fn file_watcher_detects_changes() {
+  let dir = temp_dir("file-watcher");
+  let shader = dir.join("shader.glsl");
+  let missing = dir.join("missing.glsl");
+  fs::write(&shader, "void main() {}").unwrap();
+  let _ = fs::remove_file(&missing);
+
+  let mut watcher = FileWatcher::new(vec![&shader, &missing]);
+  assert_eq!(watcher.paths().count(), 2);
+  assert!(!watcher.changed());
+
+  // modification
+  assert!(modify(&mut watcher, &shader));
+  assert!(!watcher.changed());
+
+  // creation
+  fs::write(&missing, "").unwrap();
+  assert!(watcher.changed());
+  assert!(!watcher.changed());
+
+  // removal
+  fs::remove_file(&shader).unwrap();
+  assert!(watcher.changed());
+  assert!(!watcher.changed());
+
+  fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn program_watcher_watches_missing_includes() {
+  let dir = temp_dir("program-watcher");
+  let vertex = dir.join("main.vert");
+  let fragment = dir.join("main.frag");
+  let common = dir.join("common.glsl");
+  fs::write(&vertex, "void main() {}").unwrap();
+  fs::write(&fragment, "void main() {}").unwrap();
+  let _ = fs::remove_file(&common);
+
+  let mut ctx = LenientContext(Lenient);
+  let mut watcher =
+    ProgramWatcher::<_, (), (), ()>::new(&mut ctx, ProgramSources::new(&vertex, &fragment))
+      .unwrap();
+  assert!(!watcher.poll(&mut ctx).unwrap());
+
+  // include a file that doesn’t exist yet
+  let start = Instant::now();
+  let result = loop {
+    fs::write(&vertex, "#include \"common.glsl\"\nvoid main() {}").unwrap();
+
+    match watcher.poll(&mut ctx) {
+      Ok(false) if start.elapsed() < Duration::from_secs(5) => {
+        thread::sleep(Duration::from_millis(50))
+      }
+      result => break result,
+    }
+  };
+
+  assert!(matches!(
+    result,
+    Err(ProgramWatchError::PreprocessError(
+      PreprocessError::IncludeNotFound { .. }
+    ))
+  ));
+  assert!(watcher.files().any(|path| path == common));
+  assert!(watcher.files().any(|path| path == fragment));
+
+  // creating it rebuilds the program
+  fs::write(&common, "").unwrap();
+  assert!(watcher.poll(&mut ctx).unwrap());
+  assert!(!watcher.poll(&mut ctx).unwrap());
+
+  fs::remove_dir_all(&dir).unwrap();
+}